@@ -193,14 +193,20 @@ fn main() {
                 victim_id,
                 weapon,
                 is_headshot,
+                assister_ids,
             } => {
                 println!(
-                    "[{}] Kill: Player {} -> Player {} with {:?}{}",
+                    "[{}] Kill: Player {} -> Player {} with {:?}{}{}",
                     timestamp,
                     colorize_player_id(killer_id),
                     colorize_player_id(victim_id),
                     weapon,
-                    if is_headshot { " (HS)" } else { "" }
+                    if is_headshot { " (HS)" } else { "" },
+                    if assister_ids.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" | Assists: {:?}", assister_ids)
+                    }
                 );
             }
             vctcareer_backend::sim::GameEvent::Damage {
@@ -224,11 +230,13 @@ fn main() {
             vctcareer_backend::sim::GameEvent::SpikePlant {
                 timestamp,
                 planter_id,
+                site,
             } => {
                 println!(
-                    "[{}] Spike Planted by Player {}",
+                    "[{}] Spike Planted by Player {}{}",
                     timestamp,
-                    colorize_player_id(planter_id)
+                    colorize_player_id(planter_id),
+                    site.map(|s| format!(" on {:?}", s)).unwrap_or_default()
                 );
             }
             vctcareer_backend::sim::GameEvent::SpikeDefuse {
@@ -264,6 +272,38 @@ fn main() {
                     timestamp, round_number
                 );
             }
+            vctcareer_backend::sim::GameEvent::DrawVote {
+                timestamp,
+                votes_for,
+                votes_against,
+                passed,
+                ..
+            } => {
+                println!(
+                    "[{}] Draw vote {}-{}: {}",
+                    timestamp,
+                    votes_for,
+                    votes_against,
+                    if passed { "match drawn" } else { "play on" }
+                );
+            }
+            vctcareer_backend::sim::GameEvent::TeamBuy {
+                timestamp,
+                team,
+                buy_type,
+                team_credits,
+                drops,
+                ..
+            } => {
+                println!(
+                    "[{}] {:?} {} with {} credits ({} drops)",
+                    timestamp,
+                    team,
+                    buy_type.label(),
+                    team_credits,
+                    drops.len()
+                );
+            }
         }
     }
 }
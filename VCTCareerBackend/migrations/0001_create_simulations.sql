@@ -0,0 +1,51 @@
+-- Simulations with their event logs and final player stats
+
+CREATE TABLE simulations (
+    id TEXT PRIMARY KEY,
+    seed BIGINT NOT NULL,              -- the u64 seed's bits
+    map TEXT NOT NULL,
+    patch_version TEXT NOT NULL,
+    overtime JSONB NOT NULL,
+    roster JSONB NOT NULL,             -- players as they started the match
+    team_a TEXT,                       -- roster that started on attack
+    team_b TEXT,                       -- roster that started on defense
+    status TEXT NOT NULL,              -- 'in_progress' or 'completed'
+    tick_count BIGINT NOT NULL,
+    team_a_score SMALLINT NOT NULL,
+    team_b_score SMALLINT NOT NULL,
+    winner TEXT,                       -- 'TeamA' or 'TeamB'; NULL until decided or for a draw
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX simulations_status_idx ON simulations (status);
+CREATE INDEX simulations_completed_at_idx ON simulations (completed_at);
+CREATE INDEX simulations_team_a_idx ON simulations (team_a);
+CREATE INDEX simulations_team_b_idx ON simulations (team_b);
+
+CREATE TABLE simulation_events (
+    simulation_id TEXT NOT NULL REFERENCES simulations (id) ON DELETE CASCADE,
+    seq INTEGER NOT NULL,              -- position in the simulation's event log
+    event_type TEXT NOT NULL,
+    timestamp_ms BIGINT NOT NULL,
+    payload JSONB NOT NULL,
+    PRIMARY KEY (simulation_id, seq)
+);
+
+CREATE TABLE simulation_player_stats (
+    simulation_id TEXT NOT NULL REFERENCES simulations (id) ON DELETE CASCADE,
+    player_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    agent TEXT NOT NULL,
+    team TEXT NOT NULL,                -- 'TeamA' or 'TeamB'
+    kills INTEGER NOT NULL,
+    deaths INTEGER NOT NULL,
+    assists INTEGER NOT NULL,
+    damage_dealt INTEGER NOT NULL,
+    headshot_percentage REAL NOT NULL,
+    PRIMARY KEY (simulation_id, player_id)
+);
+
+CREATE INDEX simulation_player_stats_player_idx ON simulation_player_stats (player_id);
+CREATE INDEX simulation_player_stats_name_idx ON simulation_player_stats (lower(name));
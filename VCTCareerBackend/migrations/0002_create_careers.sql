@@ -0,0 +1,15 @@
+-- Career saves. The whole career lives in `data`; the columns beside it are
+-- copies of the fields careers get looked up by.
+
+CREATE TABLE careers (
+    id TEXT PRIMARY KEY,
+    player_name TEXT NOT NULL,
+    current_team TEXT,                 -- NULL while a free agent
+    season INTEGER NOT NULL,
+    data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX careers_current_team_idx ON careers (current_team);
+CREATE INDEX careers_player_name_idx ON careers (lower(player_name));
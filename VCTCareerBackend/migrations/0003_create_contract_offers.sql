@@ -0,0 +1,14 @@
+-- Contract offers from teams to careers. As with careers, the offer and its
+-- negotiation live in `data`; the columns beside it are for lookups.
+
+CREATE TABLE contract_offers (
+    id TEXT PRIMARY KEY,
+    career_id TEXT NOT NULL REFERENCES careers (id) ON DELETE CASCADE,
+    team_name TEXT NOT NULL,
+    status TEXT NOT NULL,              -- "Open", "Accepted", "Declined", "Withdrawn" or "Expired"
+    data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX contract_offers_career_idx ON contract_offers (career_id, status);
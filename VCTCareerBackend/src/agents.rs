@@ -0,0 +1,323 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::sim::Agent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum AgentRole {
+    Duelist,
+    Initiator,
+    Controller,
+    Sentinel,
+}
+
+/// What an ability does once it's used in a round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum AbilityEffect {
+    Flash,    // blinds or concusses the opponent in a duel
+    Recon,    // reveals enemy positions before a duel
+    Damage,   // chip damage or area denial that softens the opponent
+    Mobility, // dashes and teleports to take fights on the user's terms
+    Trap,     // slows and tripwires, only pay off while holding a site
+    Smoke,    // blocks vision, used by defenders to stall a plant
+    Heal,     // restores a teammate's health
+}
+
+impl AbilityEffect {
+    /// Win chance added to a duel by using this effect, or `None` if the
+    /// effect isn't used in duels.
+    pub fn duel_bonus(&self, defending: bool) -> Option<f32> {
+        match self {
+            AbilityEffect::Flash => Some(0.15),
+            AbilityEffect::Recon => Some(0.08),
+            AbilityEffect::Damage => Some(0.06),
+            AbilityEffect::Mobility => Some(0.05),
+            AbilityEffect::Trap if defending => Some(0.08),
+            AbilityEffect::Trap | AbilityEffect::Smoke | AbilityEffect::Heal => None,
+        }
+    }
+
+    /// Whether using the effect on an opponent counts towards an assist.
+    pub fn debuffs_target(&self) -> bool {
+        matches!(
+            self,
+            AbilityEffect::Flash
+                | AbilityEffect::Recon
+                | AbilityEffect::Damage
+                | AbilityEffect::Trap
+        )
+    }
+}
+
+/// What casting an ultimate does to the round.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UltimateEffect {
+    TeamBoost(f32), // duel win chance for the caster's team until the round ends
+    Revive,         // brings a dead teammate back at full health
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ultimate {
+    pub name: &'static str,
+    pub cost: u32, // ultimate points needed to cast
+    pub effect: UltimateEffect,
+}
+
+const fn ultimate(name: &'static str, cost: u32, effect: UltimateEffect) -> Ultimate {
+    Ultimate { name, cost, effect }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ability {
+    pub name: &'static str,
+    pub cost: u32,
+    pub max_charges: usize,
+    pub free_charges: usize, // refilled at the start of every round
+    pub effect: AbilityEffect,
+}
+
+const fn ability(
+    name: &'static str,
+    cost: u32,
+    max_charges: usize,
+    free_charges: usize,
+    effect: AbilityEffect,
+) -> Ability {
+    Ability {
+        name,
+        cost,
+        max_charges,
+        free_charges,
+        effect,
+    }
+}
+
+use AbilityEffect::*;
+
+const JETT: &[Ability] = &[
+    ability("Cloudburst", 200, 2, 0, Smoke),
+    ability("Updraft", 150, 2, 0, Mobility),
+    ability("Tailwind", 0, 1, 1, Mobility),
+];
+const RAZE: &[Ability] = &[
+    ability("Boom Bot", 300, 1, 0, Recon),
+    ability("Paint Shells", 200, 1, 0, Damage),
+    ability("Blast Pack", 0, 1, 1, Mobility),
+];
+const PHOENIX: &[Ability] = &[
+    ability("Blaze", 200, 1, 0, Smoke),
+    ability("Curveball", 250, 2, 0, Flash),
+    ability("Hot Hands", 0, 1, 1, Damage),
+];
+const BREACH: &[Ability] = &[
+    ability("Aftershock", 200, 1, 0, Damage),
+    ability("Flashpoint", 250, 2, 0, Flash),
+    ability("Fault Line", 0, 1, 1, Flash),
+];
+const SOVA: &[Ability] = &[
+    ability("Owl Drone", 400, 1, 0, Recon),
+    ability("Shock Bolt", 150, 2, 0, Damage),
+    ability("Recon Bolt", 0, 1, 1, Recon),
+];
+const SAGE: &[Ability] = &[
+    ability("Barrier Orb", 400, 1, 0, Smoke),
+    ability("Slow Orb", 200, 2, 0, Trap),
+    ability("Healing Orb", 0, 1, 1, Heal),
+];
+const OMEN: &[Ability] = &[
+    ability("Shrouded Step", 100, 2, 0, Mobility),
+    ability("Paranoia", 300, 1, 0, Flash),
+    ability("Dark Cover", 0, 2, 2, Smoke),
+];
+const BRIMSTONE: &[Ability] = &[
+    ability("Stim Beacon", 200, 1, 0, Mobility),
+    ability("Incendiary", 250, 1, 0, Damage),
+    ability("Sky Smoke", 0, 3, 3, Smoke),
+];
+const VIPER: &[Ability] = &[
+    ability("Snake Bite", 200, 1, 0, Damage),
+    ability("Poison Cloud", 0, 1, 1, Smoke),
+    ability("Toxic Screen", 0, 1, 1, Smoke),
+];
+const CYPHER: &[Ability] = &[
+    ability("Trapwire", 200, 2, 0, Trap),
+    ability("Cyber Cage", 100, 2, 0, Smoke),
+    ability("Spycam", 0, 1, 1, Recon),
+];
+const KILLJOY: &[Ability] = &[
+    ability("Alarmbot", 200, 1, 0, Trap),
+    ability("Nanoswarm", 200, 2, 0, Damage),
+    ability("Turret", 0, 1, 1, Trap),
+];
+const SKYE: &[Ability] = &[
+    ability("Regrowth", 200, 1, 0, Heal),
+    ability("Trailblazer", 250, 1, 0, Recon),
+    ability("Guiding Light", 0, 2, 2, Flash),
+];
+const YORU: &[Ability] = &[
+    ability("Fakeout", 100, 1, 0, Mobility),
+    ability("Blindside", 250, 2, 0, Flash),
+    ability("Gatecrash", 0, 1, 1, Mobility),
+];
+const ASTRA: &[Ability] = &[
+    ability("Gravity Well", 150, 1, 0, Trap),
+    ability("Nova Pulse", 150, 1, 0, Flash),
+    ability("Nebula", 0, 2, 2, Smoke),
+];
+const KAYO: &[Ability] = &[
+    ability("FRAG/ment", 200, 1, 0, Damage),
+    ability("FLASH/drive", 250, 2, 0, Flash),
+    ability("ZERO/point", 0, 1, 1, Recon),
+];
+const CHAMBER: &[Ability] = &[
+    ability("Trademark", 200, 1, 0, Trap),
+    ability("Headhunter", 150, 1, 0, Damage),
+    ability("Rendezvous", 0, 1, 1, Mobility),
+];
+const NEON: &[Ability] = &[
+    ability("Fast Lane", 300, 1, 0, Smoke),
+    ability("Relay Bolt", 200, 1, 0, Flash),
+    ability("High Gear", 0, 1, 1, Mobility),
+];
+const FADE: &[Ability] = &[
+    ability("Prowler", 250, 2, 0, Recon),
+    ability("Seize", 200, 1, 0, Trap),
+    ability("Haunt", 0, 1, 1, Recon),
+];
+const HARBOR: &[Ability] = &[
+    ability("Cove", 350, 1, 0, Smoke),
+    ability("Cascade", 150, 1, 0, Smoke),
+    ability("High Tide", 0, 1, 1, Smoke),
+];
+const GEKKO: &[Ability] = &[
+    ability("Mosh Pit", 250, 1, 0, Damage),
+    ability("Wingman", 300, 1, 0, Recon),
+    ability("Dizzy", 0, 1, 1, Flash),
+];
+const DEADLOCK: &[Ability] = &[
+    ability("GravNet", 200, 1, 0, Trap),
+    ability("Barrier Mesh", 400, 1, 0, Smoke),
+    ability("Sonic Sensor", 0, 1, 1, Trap),
+];
+const ISO: &[Ability] = &[
+    ability("Contingency", 200, 1, 0, Smoke),
+    ability("Undercut", 200, 1, 0, Damage),
+    ability("Double Tap", 0, 1, 1, Mobility),
+];
+const CLOVE: &[Ability] = &[
+    ability("Pick-Me-Up", 100, 1, 0, Heal),
+    ability("Meddle", 250, 1, 0, Damage),
+    ability("Ruse", 0, 2, 2, Smoke),
+];
+
+impl Agent {
+    pub fn role(&self) -> AgentRole {
+        match self {
+            Agent::Jett | Agent::Raze | Agent::Phoenix | Agent::Yoru | Agent::Neon | Agent::Iso => {
+                AgentRole::Duelist
+            }
+            Agent::Breach
+            | Agent::Sova
+            | Agent::Skye
+            | Agent::Kayo
+            | Agent::Fade
+            | Agent::Gekko => AgentRole::Initiator,
+            Agent::Omen
+            | Agent::Brimstone
+            | Agent::Viper
+            | Agent::Astra
+            | Agent::Harbor
+            | Agent::Clove => AgentRole::Controller,
+            Agent::Sage | Agent::Cypher | Agent::Killjoy | Agent::Chamber | Agent::Deadlock => {
+                AgentRole::Sentinel
+            }
+        }
+    }
+
+    pub fn ultimate(&self) -> Ultimate {
+        use UltimateEffect::{Revive, TeamBoost};
+        match self {
+            Agent::Jett => ultimate("Blade Storm", 8, TeamBoost(0.12)),
+            Agent::Raze => ultimate("Showstopper", 8, TeamBoost(0.12)),
+            Agent::Phoenix => ultimate("Run It Back", 6, TeamBoost(0.10)),
+            Agent::Breach => ultimate("Rolling Thunder", 8, TeamBoost(0.15)),
+            Agent::Sova => ultimate("Hunter's Fury", 8, TeamBoost(0.12)),
+            Agent::Sage => ultimate("Resurrection", 8, Revive),
+            Agent::Omen => ultimate("From the Shadows", 7, TeamBoost(0.06)),
+            Agent::Brimstone => ultimate("Orbital Strike", 8, TeamBoost(0.10)),
+            Agent::Viper => ultimate("Viper's Pit", 9, TeamBoost(0.12)),
+            Agent::Cypher => ultimate("Neural Theft", 6, TeamBoost(0.08)),
+            Agent::Killjoy => ultimate("Lockdown", 9, TeamBoost(0.15)),
+            Agent::Skye => ultimate("Seekers", 8, TeamBoost(0.10)),
+            Agent::Yoru => ultimate("Dimensional Drift", 8, TeamBoost(0.08)),
+            Agent::Astra => ultimate("Cosmic Divide", 7, TeamBoost(0.08)),
+            Agent::Kayo => ultimate("NULL/cmd", 8, TeamBoost(0.12)),
+            Agent::Chamber => ultimate("Tour De Force", 8, TeamBoost(0.12)),
+            Agent::Neon => ultimate("Overdrive", 7, TeamBoost(0.10)),
+            Agent::Fade => ultimate("Nightfall", 8, TeamBoost(0.12)),
+            Agent::Harbor => ultimate("Reckoning", 8, TeamBoost(0.12)),
+            Agent::Gekko => ultimate("Thrash", 8, TeamBoost(0.10)),
+            Agent::Deadlock => ultimate("Annihilation", 8, TeamBoost(0.10)),
+            Agent::Iso => ultimate("Kill Contract", 8, TeamBoost(0.10)),
+            Agent::Clove => ultimate("Not Dead Yet", 8, TeamBoost(0.08)),
+        }
+    }
+
+    /// Basic abilities the agent can buy or gets for free each round.
+    /// Ultimates are handled separately by `ultimate`.
+    pub fn kit(&self) -> &'static [Ability] {
+        match self {
+            Agent::Jett => JETT,
+            Agent::Raze => RAZE,
+            Agent::Phoenix => PHOENIX,
+            Agent::Breach => BREACH,
+            Agent::Sova => SOVA,
+            Agent::Sage => SAGE,
+            Agent::Omen => OMEN,
+            Agent::Brimstone => BRIMSTONE,
+            Agent::Viper => VIPER,
+            Agent::Cypher => CYPHER,
+            Agent::Killjoy => KILLJOY,
+            Agent::Skye => SKYE,
+            Agent::Yoru => YORU,
+            Agent::Astra => ASTRA,
+            Agent::Kayo => KAYO,
+            Agent::Chamber => CHAMBER,
+            Agent::Neon => NEON,
+            Agent::Fade => FADE,
+            Agent::Harbor => HARBOR,
+            Agent::Gekko => GEKKO,
+            Agent::Deadlock => DEADLOCK,
+            Agent::Iso => ISO,
+            Agent::Clove => CLOVE,
+        }
+    }
+
+    pub fn all() -> [Agent; 23] {
+        [
+            Agent::Jett,
+            Agent::Raze,
+            Agent::Phoenix,
+            Agent::Breach,
+            Agent::Sova,
+            Agent::Sage,
+            Agent::Omen,
+            Agent::Brimstone,
+            Agent::Viper,
+            Agent::Cypher,
+            Agent::Killjoy,
+            Agent::Skye,
+            Agent::Yoru,
+            Agent::Astra,
+            Agent::Kayo,
+            Agent::Chamber,
+            Agent::Neon,
+            Agent::Fade,
+            Agent::Harbor,
+            Agent::Gekko,
+            Agent::Deadlock,
+            Agent::Iso,
+            Agent::Clove,
+        ]
+    }
+}
@@ -0,0 +1,422 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+use crate::ranked::RankTier;
+use crate::sim::PlayerSkills;
+
+pub const MAX_ATTRIBUTE: u8 = 100;
+
+// Points one training session is worth, split across the focus attributes
+const SESSION_POINTS: f32 = 0.25;
+
+// Where morale settles with nothing going on
+const NEUTRAL_MORALE: f32 = 0.6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum Attribute {
+    Headshot,
+    Aim,
+    Igl,
+    Movement,
+    GameSense,
+    Clutch,
+    Entry,
+    Support,
+    Utility,
+}
+
+impl Attribute {
+    /// In the order the career page lists them.
+    pub const ALL: [Attribute; 9] = [
+        Attribute::Headshot,
+        Attribute::Aim,
+        Attribute::Igl,
+        Attribute::Movement,
+        Attribute::GameSense,
+        Attribute::Clutch,
+        Attribute::Entry,
+        Attribute::Support,
+        Attribute::Utility,
+    ];
+
+    /// Mechanical attributes peak early and are the first to go with age.
+    pub fn is_mechanical(&self) -> bool {
+        matches!(
+            self,
+            Attribute::Headshot | Attribute::Aim | Attribute::Movement | Attribute::Entry
+        )
+    }
+}
+
+/// The nine attributes on the career page, each out of 100.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PlayerAttributes {
+    pub headshot: u8,
+    pub aim: u8,
+    pub igl: u8,
+    pub movement: u8,
+    pub game_sense: u8,
+    pub clutch: u8,
+    pub entry: u8,
+    pub support: u8,
+    pub utility: u8,
+}
+
+impl PlayerAttributes {
+    /// Where a new player starts: mechanics follow ranked, the rest follows
+    /// how much organised play they have behind them.
+    pub fn starting(current_rank: &str, past_experience: &str) -> Self {
+        let mechanics: u8 = match RankTier::from_str(current_rank.trim()) {
+            Ok(RankTier::Iron) => 20,
+            Ok(RankTier::Bronze) => 25,
+            Ok(RankTier::Silver) => 30,
+            Ok(RankTier::Gold) => 35,
+            Ok(RankTier::Platinum) => 40,
+            Ok(RankTier::Diamond) => 45,
+            Ok(RankTier::Ascendant) => 52,
+            Ok(RankTier::Immortal) => 60,
+            Ok(RankTier::Radiant) => 68,
+            Err(_) => 30,
+        };
+        let experience: u8 = match past_experience {
+            "Tier 1" => 20,
+            "Tier 2" => 14,
+            "Tier 3" => 8,
+            _ => 0,
+        };
+        let teamplay = 30 + experience;
+
+        PlayerAttributes {
+            headshot: mechanics,
+            aim: mechanics,
+            igl: teamplay,
+            movement: mechanics,
+            game_sense: (mechanics + teamplay) / 2,
+            clutch: (mechanics + teamplay) / 2,
+            entry: mechanics,
+            support: teamplay,
+            utility: teamplay,
+        }
+    }
+
+    pub fn get(&self, attribute: Attribute) -> u8 {
+        match attribute {
+            Attribute::Headshot => self.headshot,
+            Attribute::Aim => self.aim,
+            Attribute::Igl => self.igl,
+            Attribute::Movement => self.movement,
+            Attribute::GameSense => self.game_sense,
+            Attribute::Clutch => self.clutch,
+            Attribute::Entry => self.entry,
+            Attribute::Support => self.support,
+            Attribute::Utility => self.utility,
+        }
+    }
+
+    fn get_mut(&mut self, attribute: Attribute) -> &mut u8 {
+        match attribute {
+            Attribute::Headshot => &mut self.headshot,
+            Attribute::Aim => &mut self.aim,
+            Attribute::Igl => &mut self.igl,
+            Attribute::Movement => &mut self.movement,
+            Attribute::GameSense => &mut self.game_sense,
+            Attribute::Clutch => &mut self.clutch,
+            Attribute::Entry => &mut self.entry,
+            Attribute::Support => &mut self.support,
+            Attribute::Utility => &mut self.utility,
+        }
+    }
+
+    /// The four simulation skills, before form and morale. Each blends the
+    /// attributes that feed it, e.g. entry and clutch both show up as aim
+    /// in a duel.
+    pub fn skills(&self) -> PlayerSkills {
+        let blend = |parts: &[(Attribute, f32)]| {
+            parts
+                .iter()
+                .map(|(attribute, weight)| self.get(*attribute) as f32 * weight)
+                .sum::<f32>()
+                / 100.0
+        };
+        PlayerSkills {
+            aim: blend(&[
+                (Attribute::Aim, 0.6),
+                (Attribute::Entry, 0.2),
+                (Attribute::Clutch, 0.2),
+            ]),
+            hs: blend(&[(Attribute::Headshot, 0.8), (Attribute::Aim, 0.2)]),
+            movement: blend(&[(Attribute::Movement, 0.7), (Attribute::Entry, 0.3)]),
+            util: blend(&[
+                (Attribute::Utility, 0.5),
+                (Attribute::GameSense, 0.2),
+                (Attribute::Support, 0.2),
+                (Attribute::Igl, 0.1),
+            ]),
+        }
+    }
+
+    /// The rating teams judge the player by: every attribute counted equally.
+    pub fn overall(&self) -> u32 {
+        let total: u32 = Attribute::ALL.iter().map(|a| self.get(*a) as u32).sum();
+        (total as f32 / Attribute::ALL.len() as f32).round() as u32
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for attribute in Attribute::ALL {
+            let value = self.get(attribute);
+            if value > MAX_ATTRIBUTE {
+                return Err(format!(
+                    "Attribute {:?} must be at most {}, got {}",
+                    attribute, MAX_ATTRIBUTE, value
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum TrainingFocus {
+    #[default]
+    Balanced, // a little of everything
+    Aim,      // aim and headshots
+    Movement, // movement and entry
+    Utility,  // utility and support
+    Teamplay, // IGL and game sense
+    Clutch,   // clutch and game sense
+}
+
+impl TrainingFocus {
+    pub fn attributes(&self) -> &'static [Attribute] {
+        match self {
+            TrainingFocus::Balanced => &Attribute::ALL,
+            TrainingFocus::Aim => &[Attribute::Aim, Attribute::Headshot],
+            TrainingFocus::Movement => &[Attribute::Movement, Attribute::Entry],
+            TrainingFocus::Utility => &[Attribute::Utility, Attribute::Support],
+            TrainingFocus::Teamplay => &[Attribute::Igl, Attribute::GameSense],
+            TrainingFocus::Clutch => &[Attribute::Clutch, Attribute::GameSense],
+        }
+    }
+}
+
+pub fn parse_training_focus(focus: &str) -> Result<TrainingFocus, String> {
+    match focus.to_ascii_lowercase().as_str() {
+        "balanced" => Ok(TrainingFocus::Balanced),
+        "aim" => Ok(TrainingFocus::Aim),
+        "movement" => Ok(TrainingFocus::Movement),
+        "utility" => Ok(TrainingFocus::Utility),
+        "teamplay" => Ok(TrainingFocus::Teamplay),
+        "clutch" => Ok(TrainingFocus::Clutch),
+        _ => Err(format!("Unknown training focus: {}", focus)),
+    }
+}
+
+/// How the player is doing right now, on top of their attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PlayerCondition {
+    pub form: f32,   // -1.0 (slump) to 1.0 (on fire); follows recent matches
+    pub morale: f32, // 0.0 to 1.0; follows results
+}
+
+impl Default for PlayerCondition {
+    fn default() -> Self {
+        PlayerCondition {
+            form: 0.0,
+            morale: NEUTRAL_MORALE,
+        }
+    }
+}
+
+impl PlayerCondition {
+    /// Scales the simulation skills: up to 5% either way for form and 4% for
+    /// morale.
+    pub fn apply(&self, skills: PlayerSkills) -> PlayerSkills {
+        let factor =
+            1.0 + 0.05 * self.form + 0.04 * (self.morale - NEUTRAL_MORALE) / (1.0 - NEUTRAL_MORALE);
+        let scale = |skill: f32| (skill * factor).clamp(0.0, 1.0);
+        PlayerSkills {
+            aim: scale(skills.aim),
+            hs: scale(skills.hs),
+            movement: scale(skills.movement),
+            util: scale(skills.util),
+        }
+    }
+
+    // How much a training session sticks; a player in a slump or miserable
+    // gets less out of it
+    fn training_quality(&self) -> f32 {
+        (1.0 + 0.25 * self.form + 0.5 * (self.morale - NEUTRAL_MORALE)).clamp(0.5, 1.5)
+    }
+}
+
+/// Fractions of a point banked towards each attribute's next change;
+/// negative when age is wearing it down.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AttributeProgress {
+    pub headshot: f32,
+    pub aim: f32,
+    pub igl: f32,
+    pub movement: f32,
+    pub game_sense: f32,
+    pub clutch: f32,
+    pub entry: f32,
+    pub support: f32,
+    pub utility: f32,
+}
+
+impl AttributeProgress {
+    fn get_mut(&mut self, attribute: Attribute) -> &mut f32 {
+        match attribute {
+            Attribute::Headshot => &mut self.headshot,
+            Attribute::Aim => &mut self.aim,
+            Attribute::Igl => &mut self.igl,
+            Attribute::Movement => &mut self.movement,
+            Attribute::GameSense => &mut self.game_sense,
+            Attribute::Clutch => &mut self.clutch,
+            Attribute::Entry => &mut self.entry,
+            Attribute::Support => &mut self.support,
+            Attribute::Utility => &mut self.utility,
+        }
+    }
+}
+
+/// Everything about how a player is developing, saved with the career.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Development {
+    pub focus: TrainingFocus,
+    pub condition: PlayerCondition,
+    pub progress: AttributeProgress,
+}
+
+/// What the player did in a week, as far as development is concerned.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WeekPerformance {
+    pub training_sessions: u32,
+    pub scrims_played: u32,
+    pub scrims_won: u32,
+    pub series_played: u32,
+    pub series_won: u32,
+    pub maps_played: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+}
+
+impl WeekPerformance {
+    /// Kills plus half the assists per death; around 1.0 is an average game.
+    pub fn rating(&self) -> Option<f32> {
+        (self.maps_played > 0)
+            .then(|| (self.kills as f32 + 0.5 * self.assists as f32) / self.deaths.max(1) as f32)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AttributeChange {
+    pub attribute: Attribute,
+    pub from: u8,
+    pub to: u8,
+}
+
+/// How quickly a player of `age` improves, relative to someone in their early
+/// twenties.
+pub fn growth_rate(age: u32) -> f32 {
+    match age {
+        0..=19 => 1.3,
+        20..=22 => 1.0,
+        23..=25 => 0.75,
+        26..=28 => 0.5,
+        29..=31 => 0.3,
+        _ => 0.15,
+    }
+}
+
+/// Points a week `attribute` loses to age. Mechanics start slipping after 26,
+/// the rest after 31, and it speeds up every year past that.
+pub fn weekly_decline(age: u32, attribute: Attribute) -> f32 {
+    let onset = if attribute.is_mechanical() { 26 } else { 31 };
+    age.saturating_sub(onset) as f32 * 0.015
+}
+
+// Gains taper off as an attribute closes in on the cap
+fn headroom(value: u8) -> f32 {
+    ((MAX_ATTRIBUTE - value.min(MAX_ATTRIBUTE)) as f32 / 50.0).min(1.0)
+}
+
+/// One week of development: training, what the week's matches taught, and
+/// age, banked into `progress` and applied to `attributes` a whole point at
+/// a time. Form and morale then move with the week's results.
+pub fn develop_week(
+    attributes: &mut PlayerAttributes,
+    development: &mut Development,
+    age: u32,
+    week: &WeekPerformance,
+) -> Vec<AttributeChange> {
+    let growth = growth_rate(age);
+    let mut gains = AttributeProgress::default();
+
+    // Training, split over the focus and worth more in good spirits
+    let focus = development.focus.attributes();
+    let session = SESSION_POINTS / focus.len() as f32 * development.condition.training_quality();
+    for attribute in focus {
+        *gains.get_mut(*attribute) += session * week.training_sessions as f32;
+    }
+
+    // Scrims are reps for the team side of the game
+    let scrim_points = 0.01 * week.scrims_played as f32;
+    gains.igl += scrim_points;
+    gains.game_sense += scrim_points;
+
+    // Official maps teach most, and playing well teaches the gunfight side
+    if let Some(rating) = week.rating() {
+        let maps = week.maps_played as f32;
+        let fragging = 0.05 * maps * rating.clamp(0.5, 2.0);
+        gains.aim += fragging;
+        gains.headshot += fragging;
+        gains.entry += fragging;
+        let assists_per_map = week.assists as f32 / maps;
+        let helping = 0.03 * maps * (assists_per_map / 3.0).clamp(0.5, 2.0);
+        gains.support += helping;
+        gains.utility += helping;
+        gains.game_sense += 0.04 * maps;
+        gains.igl += 0.04 * maps;
+        gains.clutch += 0.05 * week.series_won as f32;
+    }
+
+    let mut changes = Vec::new();
+    for attribute in Attribute::ALL {
+        let value = attributes.get(attribute);
+        let gain = *gains.get_mut(attribute) * growth * headroom(value);
+        let banked = development.progress.get_mut(attribute);
+        *banked += gain - weekly_decline(age, attribute);
+
+        let whole = banked.trunc();
+        let new_value = (value as f32 + whole).clamp(0.0, MAX_ATTRIBUTE as f32) as u8;
+        *banked -= whole;
+        if new_value != value {
+            *attributes.get_mut(attribute) = new_value;
+            changes.push(AttributeChange {
+                attribute,
+                from: value,
+                to: new_value,
+            });
+        }
+    }
+
+    update_condition(&mut development.condition, week);
+    changes
+}
+
+fn update_condition(condition: &mut PlayerCondition, week: &WeekPerformance) {
+    condition.form = match week.rating() {
+        Some(rating) => 0.5 * condition.form + 0.5 * (rating - 1.0).clamp(-1.0, 1.0),
+        None => 0.8 * condition.form,
+    };
+
+    let series_lost = week.series_played - week.series_won;
+    let scrims_lost = week.scrims_played - week.scrims_won;
+    let mut morale = condition.morale + 0.08 * week.series_won as f32 - 0.06 * series_lost as f32
+        + 0.01 * (week.scrims_won as f32 - scrims_lost as f32);
+    morale += 0.1 * (NEUTRAL_MORALE - morale);
+    condition.morale = morale.clamp(0.0, 1.0);
+}
@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+
+use crate::sim::{ArmorType, Weapon, WeaponStats};
+
+/// Where balance files are read from unless the `BALANCE_PATCH_DIR`
+/// environment variable says otherwise. Relative to the working directory,
+/// like the other data files under src/.
+pub const DEFAULT_BALANCE_PATCH_DIR: &str = "src/balance_patches";
+pub const DEFAULT_PATCH_VERSION: &str = "baseline";
+
+/// Directory holding one `<version>.json` balance file per game patch.
+pub fn balance_patch_dir() -> String {
+    env::var("BALANCE_PATCH_DIR").unwrap_or_else(|_| DEFAULT_BALANCE_PATCH_DIR.to_string())
+}
+
+// The default patch is compiled in so a simulation can always be built, even
+// when the data directory isn't next to the binary
+const DEFAULT_PATCH_JSON: &str = include_str!("balance_patches/baseline.json");
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArmorCosts {
+    pub light: u32,
+    pub heavy: u32,
+}
+
+impl ArmorCosts {
+    pub fn cost(&self, armor: &ArmorType) -> u32 {
+        match armor {
+            ArmorType::None => 0,
+            ArmorType::Light => self.light,
+            ArmorType::Heavy => self.heavy,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EconomyConfig {
+    pub starting_credits: u32,
+    pub credit_cap: u32,
+    pub kill_reward: u32,
+    pub round_win_reward: u32,
+    pub loss_bonus: Vec<u32>, // indexed by consecutive losses before this one
+    pub spike_plant_reward: u32, // each attacker, on a lost round they planted in
+}
+
+impl EconomyConfig {
+    /// Loss bonus paid after `loss_streak` earlier consecutive losses. Streaks
+    /// longer than the ladder keep paying its last step.
+    pub fn loss_bonus_for_streak(&self, loss_streak: u8) -> u32 {
+        self.loss_bonus
+            .get(loss_streak as usize)
+            .or(self.loss_bonus.last())
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// Tunable weights of the duel model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombatWeights {
+    pub aim_weight: f32,        // share of aim in a player's gunfight skill
+    pub headshot_weight: f32,   // share of headshot skill
+    pub skill_gap_scale: f32,   // how far a full skill gap moves an even duel
+    pub peek_advantage_ms: f32, // head start a perfect mover gets on the first shot when peeking
+    pub strafe_penalty: f32,    // hit chance a perfect mover takes off shots aimed at them
+    pub utility_weight: f32,    // how much util skill scales what an ability is worth
+}
+
+impl Default for CombatWeights {
+    fn default() -> Self {
+        CombatWeights {
+            aim_weight: 0.7,
+            headshot_weight: 0.3,
+            skill_gap_scale: 0.3,
+            peek_advantage_ms: 80.0,
+            strafe_penalty: 0.15,
+            utility_weight: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceConfig {
+    pub version: String,
+    pub armor_costs: ArmorCosts,
+    pub economy: EconomyConfig,
+    #[serde(default)]
+    pub combat: CombatWeights,
+    pub weapons: HashMap<Weapon, WeaponStats>,
+}
+
+impl Default for BalanceConfig {
+    fn default() -> Self {
+        Self::from_json(DEFAULT_PATCH_JSON).expect("built-in balance patch is invalid")
+    }
+}
+
+impl BalanceConfig {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let config: BalanceConfig = serde_json::from_str(json)
+            .map_err(|e| format!("Failed to parse balance file: {}", e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Loads the balance file for a named patch from `balance_patch_dir`.
+    pub fn load(version: &str) -> Result<Self, String> {
+        // Versions become file names, so keep them from escaping the directory
+        if version.is_empty()
+            || !version
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+            || version.starts_with('.')
+        {
+            return Err(format!("Invalid patch version: {}", version));
+        }
+
+        let path = format!("{}/{}.json", balance_patch_dir(), version);
+        let json_str =
+            fs::read_to_string(&path).map_err(|_| format!("Unknown patch version: {}", version))?;
+        let config = Self::from_json(&json_str)?;
+
+        if config.version != version {
+            return Err(format!(
+                "Balance file {} declares version {}",
+                path, config.version
+            ));
+        }
+        Ok(config)
+    }
+
+    /// Patch versions with a balance file in `balance_patch_dir`, sorted by name.
+    pub fn available_versions() -> Result<Vec<String>, String> {
+        let entries = fs::read_dir(balance_patch_dir())
+            .map_err(|e| format!("Failed to read balance directory: {}", e))?;
+
+        let mut versions: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_string_lossy().into_owned())
+            })
+            .collect();
+        versions.sort();
+        Ok(versions)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for weapon in Weapon::all() {
+            let Some(stats) = self.weapons.get(&weapon) else {
+                return Err(format!(
+                    "Balance {} has no stats for {:?}",
+                    self.version, weapon
+                ));
+            };
+            if stats.fire_rate <= 0.0 {
+                return Err(format!(
+                    "Balance {} gives {:?} a non-positive fire rate",
+                    self.version, weapon
+                ));
+            }
+            if stats.range_falloff.windows(2).any(|w| w[0].0 >= w[1].0) {
+                return Err(format!(
+                    "Balance {} has unordered falloff bands for {:?}",
+                    self.version, weapon
+                ));
+            }
+        }
+        let combat = &self.combat;
+        if combat.aim_weight < 0.0
+            || combat.headshot_weight < 0.0
+            || combat.aim_weight + combat.headshot_weight <= 0.0
+        {
+            return Err(format!(
+                "Balance {} needs positive aim and headshot weights",
+                self.version
+            ));
+        }
+        if !(0.0..1.0).contains(&combat.strafe_penalty) {
+            return Err(format!(
+                "Balance {} has a strafe penalty outside [0, 1)",
+                self.version
+            ));
+        }
+        if self.economy.loss_bonus.is_empty() {
+            return Err(format!(
+                "Balance {} has an empty loss bonus ladder",
+                self.version
+            ));
+        }
+        Ok(())
+    }
+}
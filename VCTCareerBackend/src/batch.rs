@@ -0,0 +1,383 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::thread;
+use utoipa::ToSchema;
+
+use crate::balance::BalanceConfig;
+use crate::maps::{Map, parse_map};
+use crate::models::SimulationPlayer;
+use crate::series::SeriesTeam;
+use crate::sim::{Player, PlayerStats, SimulationPhase, Team, ValorantSimulation};
+use crate::simulation_manager::build_player;
+
+pub const MAX_BATCH_ITERATIONS: u32 = 1000;
+
+// Two-sided 95% normal quantile for the win probability interval
+const CONFIDENCE_Z: f64 = 1.96;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BatchSimulationRequest {
+    pub players: Vec<SimulationPlayer>, // "Attackers" are team A, "Defenders" team B
+    pub iterations: u32,                // At most MAX_BATCH_ITERATIONS
+    #[serde(default, deserialize_with = "crate::ranked::deserialize_maps_param")]
+    pub maps: Vec<String>, // Each match is played on one of these; omit for every map
+    pub seed: Option<u64>,              // Omit for a random seed
+    pub patch_version: Option<String>,  // Omit for the baseline balance patch
+}
+
+/// Team A's chance of winning a match, with a 95% Wilson score interval.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WinProbability {
+    pub estimate: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ScoreFrequency {
+    pub team_a_score: u8,
+    pub team_b_score: u8,
+    pub count: u32,
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StatDistribution {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: u32,
+    pub median: f64,
+    pub max: u32,
+}
+
+/// How one player's per-match stats spread over the whole batch.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchPlayerStats {
+    pub player_id: u32,
+    pub name: String,
+    pub team: SeriesTeam,
+    pub kills: StatDistribution,
+    pub deaths: StatDistribution,
+    pub assists: StatDistribution,
+    pub damage_dealt: StatDistribution,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchResult {
+    pub iterations: u32,
+    pub seed: u64,
+    pub team_a_wins: u32,
+    pub team_b_wins: u32,
+    pub draws: u32,
+    pub team_a_win_probability: WinProbability,
+    pub score_distribution: Vec<ScoreFrequency>, // Most common scorelines first
+    pub average_rounds: f64,
+    pub players: Vec<BatchPlayerStats>,
+}
+
+/// What the batch keeps from each match it plays.
+struct MatchOutcome {
+    team_a_score: u8,
+    team_b_score: u8,
+    winner: Option<SeriesTeam>, // None for a draw
+    player_stats: Vec<PlayerStats>,
+}
+
+/// Plays `iterations` seeded matches between the two rosters in `players`
+/// across all available threads. Team A is whoever is on "Attackers"; it
+/// starts on attack in even-numbered matches and on defense in odd ones, so
+/// neither roster gets the first half on its better side every time. Each
+/// match is played on a map drawn from `pool`.
+///
+/// Every match seed comes from `seed`, so a batch replays exactly however
+/// many threads ran it.
+pub fn run_batch(
+    players: &[Player],
+    iterations: u32,
+    seed: u64,
+    pool: &[Map],
+    balance: &BalanceConfig,
+) -> Result<BatchResult, String> {
+    if iterations == 0 {
+        return Err("Batch needs at least one iteration".to_string());
+    }
+    if iterations > MAX_BATCH_ITERATIONS {
+        return Err(format!(
+            "Batch is limited to {} iterations",
+            MAX_BATCH_ITERATIONS
+        ));
+    }
+    if pool.is_empty() {
+        return Err("Batch needs at least one map".to_string());
+    }
+    let team_a_id = players
+        .iter()
+        .find(|p| p.team == Team::Attackers)
+        .map(|p| p.id)
+        .ok_or("Batch needs players on both teams")?;
+    if !players.iter().any(|p| p.team == Team::Defenders) {
+        return Err("Batch needs players on both teams".to_string());
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let matches: Vec<(u64, Map)> = (0..iterations)
+        .map(|_| {
+            (
+                rng.random(),
+                *pool.choose(&mut rng).expect("pool is not empty"),
+            )
+        })
+        .collect();
+
+    let threads = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(matches.len());
+    let chunk_size = matches.len().div_ceil(threads);
+    let outcomes = thread::scope(|scope| {
+        let handles: Vec<_> = matches
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(chunk_index, chunk)| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .enumerate()
+                        .map(|(offset, (match_seed, map))| {
+                            let team_a_attacks =
+                                (chunk_index * chunk_size + offset).is_multiple_of(2);
+                            play_match(
+                                players,
+                                team_a_id,
+                                team_a_attacks,
+                                *match_seed,
+                                *map,
+                                balance,
+                            )
+                        })
+                        .collect::<Result<Vec<_>, String>>()
+                })
+            })
+            .collect();
+
+        let mut outcomes = Vec::with_capacity(matches.len());
+        for handle in handles {
+            let chunk = handle
+                .join()
+                .map_err(|_| "Batch simulation thread panicked".to_string())??;
+            outcomes.extend(chunk);
+        }
+        Ok::<_, String>(outcomes)
+    })?;
+
+    Ok(summarize(players, iterations, seed, &outcomes))
+}
+
+fn play_match(
+    players: &[Player],
+    team_a_id: u32,
+    team_a_attacks: bool,
+    seed: u64,
+    map: Map,
+    balance: &BalanceConfig,
+) -> Result<MatchOutcome, String> {
+    let mut sim = ValorantSimulation::with_balance(seed, balance.clone());
+    sim.set_map(map)?;
+    for player in players {
+        let mut player = player.clone();
+        if !team_a_attacks {
+            player.team = match player.team {
+                Team::Attackers => Team::Defenders,
+                Team::Defenders => Team::Attackers,
+            };
+        }
+        sim.add_player(player);
+    }
+    sim.run_simulation_to_completion()?;
+
+    // Sides swap during the match, so find where team A ended up
+    let team_a_side = sim.players[&team_a_id].team.clone();
+    let (team_a_score, team_b_score) = match team_a_side {
+        Team::Attackers => (sim.state.attacker_score, sim.state.defender_score),
+        Team::Defenders => (sim.state.defender_score, sim.state.attacker_score),
+    };
+    let winner = match &sim.state.phase {
+        SimulationPhase::MatchEnd {
+            winner: Some(side), ..
+        } if *side == team_a_side => Some(SeriesTeam::TeamA),
+        SimulationPhase::MatchEnd {
+            winner: Some(_), ..
+        } => Some(SeriesTeam::TeamB),
+        SimulationPhase::MatchEnd { winner: None, .. } => None,
+        _ => return Err(format!("Match with seed {} did not finish", seed)),
+    };
+
+    Ok(MatchOutcome {
+        team_a_score,
+        team_b_score,
+        winner,
+        player_stats: sim.get_player_stats(),
+    })
+}
+
+fn summarize(
+    players: &[Player],
+    iterations: u32,
+    seed: u64,
+    outcomes: &[MatchOutcome],
+) -> BatchResult {
+    let count = |team| outcomes.iter().filter(|o| o.winner == team).count() as u32;
+    let team_a_wins = count(Some(SeriesTeam::TeamA));
+    let team_b_wins = count(Some(SeriesTeam::TeamB));
+    let draws = count(None);
+
+    let mut scorelines: HashMap<(u8, u8), u32> = HashMap::new();
+    for outcome in outcomes {
+        *scorelines
+            .entry((outcome.team_a_score, outcome.team_b_score))
+            .or_insert(0) += 1;
+    }
+    let mut score_distribution: Vec<ScoreFrequency> = scorelines
+        .into_iter()
+        .map(|((team_a_score, team_b_score), count)| ScoreFrequency {
+            team_a_score,
+            team_b_score,
+            count,
+            share: count as f64 / iterations as f64,
+        })
+        .collect();
+    score_distribution.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then(b.team_a_score.cmp(&a.team_a_score))
+            .then(a.team_b_score.cmp(&b.team_b_score))
+    });
+
+    // Every round has a winner, so the scores add up to the rounds played
+    let total_rounds: u32 = outcomes
+        .iter()
+        .map(|o| (o.team_a_score + o.team_b_score) as u32)
+        .sum();
+
+    let mut sorted_players: Vec<&Player> = players.iter().collect();
+    sorted_players.sort_by_key(|p| p.id);
+    let player_stats = sorted_players
+        .into_iter()
+        .map(|player| {
+            let samples = |stat: fn(&PlayerStats) -> u32| {
+                let values: Vec<u32> = outcomes
+                    .iter()
+                    .filter_map(|o| o.player_stats.iter().find(|s| s.player_id == player.id))
+                    .map(stat)
+                    .collect();
+                distribution(values)
+            };
+            BatchPlayerStats {
+                player_id: player.id,
+                name: player.name.clone(),
+                team: match player.team {
+                    Team::Attackers => SeriesTeam::TeamA,
+                    Team::Defenders => SeriesTeam::TeamB,
+                },
+                kills: samples(|s| s.kills),
+                deaths: samples(|s| s.deaths),
+                assists: samples(|s| s.assists),
+                damage_dealt: samples(|s| s.damage_dealt),
+            }
+        })
+        .collect();
+
+    BatchResult {
+        iterations,
+        seed,
+        team_a_wins,
+        team_b_wins,
+        draws,
+        team_a_win_probability: wilson_interval(team_a_wins, iterations),
+        score_distribution,
+        average_rounds: total_rounds as f64 / iterations as f64,
+        players: player_stats,
+    }
+}
+
+/// Wilson score interval for `successes` out of `trials`. Unlike the normal
+/// approximation it stays inside [0, 1] when one roster wins nearly every match.
+pub fn wilson_interval(successes: u32, trials: u32) -> WinProbability {
+    if trials == 0 {
+        return WinProbability {
+            estimate: 0.0,
+            lower: 0.0,
+            upper: 1.0,
+        };
+    }
+
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let z2 = CONFIDENCE_Z * CONFIDENCE_Z;
+    let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let margin = CONFIDENCE_Z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+    WinProbability {
+        estimate: p,
+        lower: (center - margin).max(0.0),
+        upper: (center + margin).min(1.0),
+    }
+}
+
+pub fn distribution(mut values: Vec<u32>) -> StatDistribution {
+    if values.is_empty() {
+        return StatDistribution {
+            mean: 0.0,
+            std_dev: 0.0,
+            min: 0,
+            median: 0.0,
+            max: 0,
+        };
+    }
+
+    values.sort_unstable();
+    let n = values.len();
+    let mean = values.iter().map(|&v| v as f64).sum::<f64>() / n as f64;
+    let variance = values
+        .iter()
+        .map(|&v| (v as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n as f64;
+    let median = if n.is_multiple_of(2) {
+        (values[n / 2 - 1] + values[n / 2]) as f64 / 2.0
+    } else {
+        values[n / 2] as f64
+    };
+    StatDistribution {
+        mean,
+        std_dev: variance.sqrt(),
+        min: values[0],
+        median,
+        max: values[n - 1],
+    }
+}
+
+pub fn simulate_batch(request: BatchSimulationRequest) -> Result<BatchResult, String> {
+    let pool = if request.maps.is_empty() {
+        Map::all().to_vec()
+    } else {
+        request
+            .maps
+            .iter()
+            .map(|name| parse_map(name))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    let balance = match request.patch_version.as_deref() {
+        Some(version) => BalanceConfig::load(version)?,
+        None => BalanceConfig::default(),
+    };
+    let players = request
+        .players
+        .into_iter()
+        .map(build_player)
+        .collect::<Result<Vec<_>, _>>()?;
+    let seed = request.seed.unwrap_or_else(|| rand::rng().random());
+
+    run_batch(&players, request.iterations, seed, &pool, &balance)
+}
@@ -0,0 +1,740 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::attributes::{AttributeChange, WeekPerformance, develop_week};
+use crate::balance::BalanceConfig;
+use crate::career::{Career, CareerHistoryEntry, Contract, InboxMessage};
+use crate::maps::Map;
+use crate::series::{SeriesFormat, SeriesTeam, run_series};
+use crate::sim::{Agent, Player, PlayerSkills, Team};
+
+// Oldest messages fall off the inbox past this many
+const MAX_INBOX_MESSAGES: usize = 50;
+
+// The career player's id in simulated matches; teammates follow, then opponents
+const CAREER_PLAYER_ID: u32 = 1;
+
+/// Which competition a career plays its season in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum Circuit {
+    Vct,
+    Challengers,
+    Premier,
+    #[default]
+    Ranked,
+}
+
+impl Circuit {
+    /// The circuit a placement tier (as in `placement::StartingTier::as_str`) plays in.
+    pub fn from_starting_tier(tier: &str) -> Circuit {
+        if tier.starts_with("Tier 1") {
+            Circuit::Vct
+        } else if tier.starts_with("Tier 2") {
+            Circuit::Challengers
+        } else if tier.starts_with("Tier 3") {
+            Circuit::Premier
+        } else {
+            Circuit::Ranked
+        }
+    }
+
+    /// The `tier` column of the teams table for this circuit's teams.
+    pub fn team_tier(&self) -> Option<i16> {
+        match self {
+            Circuit::Vct => Some(1),
+            Circuit::Challengers => Some(2),
+            Circuit::Premier => Some(3),
+            Circuit::Ranked => None,
+        }
+    }
+
+    /// The circuit teams with this `tier` in the teams table play in.
+    pub fn from_team_tier(tier: i16) -> Option<Circuit> {
+        match tier {
+            1 => Some(Circuit::Vct),
+            2 => Some(Circuit::Challengers),
+            3 => Some(Circuit::Premier),
+            _ => None,
+        }
+    }
+
+    // Typical skill of the players this circuit's teams field
+    fn skill_level(&self) -> f32 {
+        match self {
+            Circuit::Vct => 0.75,
+            Circuit::Challengers => 0.62,
+            Circuit::Premier => 0.5,
+            Circuit::Ranked => 0.4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum StageKind {
+    League,   // one series a week for every team
+    Playoffs, // only for teams that went .500 or better in the last league stage; a loss knocks them out
+    Ranked,   // no official matches
+    Offseason,
+}
+
+struct StageTemplate {
+    name: &'static str,
+    kind: StageKind,
+    weeks: u32,
+    format: SeriesFormat,
+}
+
+const fn stage(
+    name: &'static str,
+    kind: StageKind,
+    weeks: u32,
+    format: SeriesFormat,
+) -> StageTemplate {
+    StageTemplate {
+        name,
+        kind,
+        weeks,
+        format,
+    }
+}
+
+const VCT_SEASON: &[StageTemplate] = &[
+    stage("Kickoff", StageKind::League, 4, SeriesFormat::Bo3),
+    stage("Masters 1", StageKind::Playoffs, 2, SeriesFormat::Bo3),
+    stage("Stage 1", StageKind::League, 6, SeriesFormat::Bo3),
+    stage("Masters 2", StageKind::Playoffs, 2, SeriesFormat::Bo3),
+    stage("Stage 2", StageKind::League, 6, SeriesFormat::Bo3),
+    stage("Champions", StageKind::Playoffs, 3, SeriesFormat::Bo5),
+    stage("Offseason", StageKind::Offseason, 8, SeriesFormat::Bo3),
+];
+
+const CHALLENGERS_SEASON: &[StageTemplate] = &[
+    stage(
+        "Challengers Split 1",
+        StageKind::League,
+        8,
+        SeriesFormat::Bo3,
+    ),
+    stage("Split Break", StageKind::Offseason, 2, SeriesFormat::Bo3),
+    stage(
+        "Challengers Split 2",
+        StageKind::League,
+        8,
+        SeriesFormat::Bo3,
+    ),
+    stage("Ascension", StageKind::Playoffs, 2, SeriesFormat::Bo3),
+    stage("Offseason", StageKind::Offseason, 8, SeriesFormat::Bo3),
+];
+
+const PREMIER_SEASON: &[StageTemplate] = &[
+    stage("Premier Stage 1", StageKind::League, 8, SeriesFormat::Bo1),
+    stage("Stage Break", StageKind::Offseason, 2, SeriesFormat::Bo1),
+    stage("Premier Stage 2", StageKind::League, 8, SeriesFormat::Bo1),
+    stage(
+        "Premier Playoffs",
+        StageKind::Playoffs,
+        1,
+        SeriesFormat::Bo1,
+    ),
+    stage("Offseason", StageKind::Offseason, 8, SeriesFormat::Bo1),
+];
+
+const RANKED_SEASON: &[StageTemplate] = &[
+    stage("Act 1", StageKind::Ranked, 8, SeriesFormat::Bo1),
+    stage("Act 2", StageKind::Ranked, 8, SeriesFormat::Bo1),
+    stage("Act 3", StageKind::Ranked, 8, SeriesFormat::Bo1),
+];
+
+fn season_stages(circuit: Circuit) -> &'static [StageTemplate] {
+    match circuit {
+        Circuit::Vct => VCT_SEASON,
+        Circuit::Challengers => CHALLENGERS_SEASON,
+        Circuit::Premier => PREMIER_SEASON,
+        Circuit::Ranked => RANKED_SEASON,
+    }
+}
+
+pub fn season_weeks(circuit: Circuit) -> u32 {
+    season_stages(circuit).iter().map(|stage| stage.weeks).sum()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StageInfo {
+    pub name: String,
+    pub kind: StageKind,
+    pub start_week: u32,
+    pub end_week: u32, // inclusive
+    pub format: SeriesFormat,
+}
+
+/// The season's stages in order.
+pub fn season_schedule(circuit: Circuit) -> Vec<StageInfo> {
+    let mut start_week = 1;
+    season_stages(circuit)
+        .iter()
+        .map(|stage| {
+            let info = StageInfo {
+                name: stage.name.to_string(),
+                kind: stage.kind,
+                start_week,
+                end_week: start_week + stage.weeks - 1,
+                format: stage.format,
+            };
+            start_week += stage.weeks;
+            info
+        })
+        .collect()
+}
+
+/// The stage `week` of the season falls in.
+pub fn stage_for_week(circuit: Circuit, week: u32) -> Result<StageInfo, String> {
+    season_schedule(circuit)
+        .into_iter()
+        .find(|stage| (stage.start_week..=stage.end_week).contains(&week))
+        .ok_or_else(|| {
+            format!(
+                "Week {} is outside the {}-week {:?} season",
+                week,
+                season_weeks(circuit),
+                circuit
+            )
+        })
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Record {
+    pub wins: u32,
+    pub losses: u32,
+}
+
+impl Record {
+    fn add(&mut self, won: bool) {
+        if won {
+            self.wins += 1;
+        } else {
+            self.losses += 1;
+        }
+    }
+}
+
+/// Where a career is in its season, saved with the career.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CareerCalendar {
+    pub circuit: Circuit,
+    pub week: u32,                  // the next week to be played, from 1
+    pub seed: u64,                  // every week's results follow from this
+    pub stage_record: Record,       // official series in the current stage
+    pub season_record: Record,      // official series this season
+    pub last_league_record: Record, // decides who makes the next playoffs
+    pub in_contention: bool,        // still alive in the current playoffs
+    #[serde(default)]
+    pub next_circuit: Option<Circuit>, // where a signed contract moves the career next season
+}
+
+impl Default for CareerCalendar {
+    fn default() -> Self {
+        CareerCalendar {
+            circuit: Circuit::default(),
+            week: 1,
+            seed: 0,
+            stage_record: Record::default(),
+            season_record: Record::default(),
+            last_league_record: Record::default(),
+            in_contention: false,
+            next_circuit: None,
+        }
+    }
+}
+
+impl CareerCalendar {
+    pub fn new(circuit: Circuit, seed: u64) -> Self {
+        CareerCalendar {
+            circuit,
+            seed,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SeasonCalendar {
+    pub season: u32,
+    pub circuit: Circuit,
+    pub week: u32,
+    pub season_weeks: u32,
+    pub current_stage: StageInfo,
+    pub stages: Vec<StageInfo>,
+    pub stage_record: Record,
+    pub season_record: Record,
+}
+
+pub fn season_calendar(career: &Career) -> Result<SeasonCalendar, String> {
+    let calendar = &career.calendar;
+    Ok(SeasonCalendar {
+        season: career.season,
+        circuit: calendar.circuit,
+        week: calendar.week,
+        season_weeks: season_weeks(calendar.circuit),
+        current_stage: stage_for_week(calendar.circuit, calendar.week)?,
+        stages: season_schedule(calendar.circuit),
+        stage_record: calendar.stage_record,
+        season_record: calendar.season_record,
+    })
+}
+
+/// One official series the career player's team played.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CareerMatch {
+    pub stage: String,
+    pub team: String,
+    pub opponent: String,
+    pub format: SeriesFormat,
+    pub seed: u64,
+    pub maps_won: u8,
+    pub maps_lost: u8,
+    pub won: bool,
+    pub kills: u32, // the career player's, over the whole series
+    pub deaths: u32,
+    pub assists: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WeekReport {
+    pub season: u32,
+    pub week: u32,
+    pub stage: StageInfo,
+    pub training_sessions: u32,
+    pub attribute_changes: Vec<AttributeChange>,
+    pub scrims_played: u32,
+    pub scrims_won: u32,
+    pub matches: Vec<CareerMatch>,
+    pub messages: Vec<InboxMessage>, // what arrived in the inbox this week
+    pub career: Career,              // after the week
+}
+
+/// What a player does with a week, by the kind of stage it falls in.
+fn weekly_schedule(kind: StageKind, has_team: bool) -> (u32, u32) {
+    // (training sessions, scrims)
+    match (kind, has_team) {
+        (StageKind::League, true) => (3, 4),
+        (StageKind::Playoffs, true) => (2, 2),
+        (StageKind::Offseason, true) => (4, 6),
+        (StageKind::Ranked, true) => (4, 4),
+        // Free agents grind ranked and put the team's hours into training
+        (_, false) => (5, 0),
+    }
+}
+
+/// Plays out the career's next week: training, scrims, any official series
+/// and what lands in the inbox because of them. Stage and season changes
+/// happen as the week closes. Each week's results follow from the calendar
+/// seed, so replaying the same week gives the same outcome.
+///
+/// `opponents` are team names to draw official opponents from; generic names
+/// are used if it is empty.
+pub fn advance_week(
+    career: &mut Career,
+    opponents: &[String],
+    balance: &BalanceConfig,
+) -> Result<WeekReport, String> {
+    let season = career.season;
+    let week = career.calendar.week;
+    let circuit = career.calendar.circuit;
+    let stage = stage_for_week(circuit, week)?;
+    let mut rng = StdRng::seed_from_u64(
+        career
+            .calendar
+            .seed
+            .wrapping_add((season as u64) << 16)
+            .wrapping_add(week as u64),
+    );
+    let mut messages = Vec::new();
+
+    if week == stage.start_week {
+        start_stage(career, &stage, &mut messages);
+    }
+
+    let team_name = career.current_team.clone();
+    let (training_sessions, scrims_played) = weekly_schedule(stage.kind, team_name.is_some());
+
+    let skill = career.skills();
+    let team_strength = (player_strength(&skill) + 4.0 * circuit.skill_level()) / 5.0;
+    let mut scrims_won = 0;
+    for _ in 0..scrims_played {
+        let opponent_strength = circuit.skill_level() + rng.random_range(-0.05..=0.05);
+        let win_chance = 1.0 / (1.0 + (-(team_strength - opponent_strength) * 12.0).exp());
+        if rng.random_bool(win_chance.clamp(0.0, 1.0) as f64) {
+            scrims_won += 1;
+        }
+    }
+
+    let mut matches = Vec::new();
+    let plays_official = match stage.kind {
+        StageKind::League => true,
+        StageKind::Playoffs => career.calendar.in_contention,
+        StageKind::Ranked | StageKind::Offseason => false,
+    };
+    if let Some(team) = team_name.filter(|_| plays_official) {
+        let opponent = opponents
+            .iter()
+            .filter(|name| **name != team)
+            .collect::<Vec<_>>()
+            .choose(&mut rng)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| format!("{:?} Opponent {}", circuit, rng.random_range(1..=12)));
+        let result = play_official_match(career, &stage, &team, &opponent, &mut rng, balance)?;
+
+        career.calendar.stage_record.add(result.won);
+        career.calendar.season_record.add(result.won);
+        if stage.kind == StageKind::Playoffs && !result.won {
+            career.calendar.in_contention = false;
+        }
+        messages.push(InboxMessage {
+            id: 0,
+            subject: format!(
+                "{} {}-{} {}",
+                result.team, result.maps_won, result.maps_lost, result.opponent
+            ),
+            from: stage.name.clone(),
+            content: format!(
+                "{} {} {} {}-{} in {}. You went {}/{}/{}.",
+                result.team,
+                if result.won { "beat" } else { "lost to" },
+                result.opponent,
+                result.maps_won,
+                result.maps_lost,
+                stage.name,
+                result.kills,
+                result.deaths,
+                result.assists
+            ),
+            read: false,
+        });
+        matches.push(result);
+    }
+
+    let performance = WeekPerformance {
+        training_sessions,
+        scrims_played,
+        scrims_won,
+        series_played: matches.len() as u32,
+        series_won: matches.iter().filter(|m| m.won).count() as u32,
+        maps_played: matches
+            .iter()
+            .map(|m| (m.maps_won + m.maps_lost) as u32)
+            .sum(),
+        kills: matches.iter().map(|m| m.kills).sum(),
+        deaths: matches.iter().map(|m| m.deaths).sum(),
+        assists: matches.iter().map(|m| m.assists).sum(),
+    };
+    let attribute_changes = develop_week(
+        &mut career.attributes,
+        &mut career.development,
+        career.profile.age,
+        &performance,
+    );
+
+    career.calendar.week += 1;
+    if week == stage.end_week {
+        end_stage(career, &stage, &mut messages);
+    }
+    if career.calendar.week > season_weeks(circuit) {
+        end_season(career, &mut messages);
+    }
+
+    deliver(career, &mut messages);
+    Ok(WeekReport {
+        season,
+        week,
+        stage,
+        training_sessions,
+        attribute_changes,
+        scrims_played,
+        scrims_won,
+        matches,
+        messages,
+        career: career.clone(),
+    })
+}
+
+fn start_stage(career: &mut Career, stage: &StageInfo, messages: &mut Vec<InboxMessage>) {
+    let calendar = &mut career.calendar;
+    calendar.stage_record = Record::default();
+    let content = match stage.kind {
+        StageKind::League if career.current_team.is_some() => format!(
+            "{} starts this week: one {:?} series a week until week {}.",
+            stage.name, stage.format, stage.end_week
+        ),
+        StageKind::League => format!(
+            "{} starts this week. Sign with a team to play in it.",
+            stage.name
+        ),
+        StageKind::Playoffs => {
+            let record = calendar.last_league_record;
+            calendar.in_contention = career.current_team.is_some()
+                && record.wins + record.losses > 0
+                && record.wins >= record.losses;
+            if calendar.in_contention {
+                format!(
+                    "Your {}-{} record is good enough for {}. Lose a series and you're out.",
+                    record.wins, record.losses, stage.name
+                )
+            } else {
+                format!(
+                    "You didn't qualify for {}. Use the time to train.",
+                    stage.name
+                )
+            }
+        }
+        StageKind::Ranked => format!("{} of ranked starts this week.", stage.name),
+        StageKind::Offseason => format!(
+            "{} runs until week {}. Nothing official until then.",
+            stage.name, stage.end_week
+        ),
+    };
+    messages.push(InboxMessage {
+        id: 0,
+        subject: format!("{} begins", stage.name),
+        from: "League Office".to_string(),
+        content,
+        read: false,
+    });
+}
+
+fn end_stage(career: &mut Career, stage: &StageInfo, messages: &mut Vec<InboxMessage>) {
+    let record = career.calendar.stage_record;
+    let played = record.wins + record.losses > 0;
+    if stage.kind == StageKind::League {
+        career.calendar.last_league_record = record;
+    }
+    if !played {
+        return;
+    }
+    let description = format!(
+        "{} {}: {}-{}",
+        career.season, stage.name, record.wins, record.losses
+    );
+    career.history.push(CareerHistoryEntry {
+        season: career.season,
+        description: description.clone(),
+    });
+    messages.push(InboxMessage {
+        id: 0,
+        subject: format!("{} is over", stage.name),
+        from: "League Office".to_string(),
+        content: format!("Final record: {}.", description),
+        read: false,
+    });
+}
+
+fn end_season(career: &mut Career, messages: &mut Vec<InboxMessage>) {
+    let finished = career.season;
+    let record = career.calendar.season_record;
+    career.history.push(CareerHistoryEntry {
+        season: finished,
+        description: format!(
+            "Season {} finished {}-{}",
+            finished, record.wins, record.losses
+        ),
+    });
+
+    career.season += 1;
+    career.profile.age += 1;
+    career.calendar.week = 1;
+    career.calendar.season_record = Record::default();
+    career.calendar.last_league_record = Record::default();
+    career.calendar.in_contention = false;
+
+    let mut content = format!(
+        "Season {} is done at {}-{}. Season {} starts next week.",
+        finished, record.wins, record.losses, career.season
+    );
+    // Move first, so a contract with a team in another circuit is timed from
+    // the season the player actually joins them
+    if let Some(circuit) = career.calendar.next_circuit.take()
+        && let Some(contract) = &career.contract
+    {
+        career.calendar.circuit = circuit;
+        career.current_team = Some(contract.team.clone());
+        content.push_str(&format!(
+            " With your new team you'll play {:?} from season {}.",
+            circuit, career.season
+        ));
+    }
+    if let Some(contract) = &career.contract
+        && contract_runs_out(contract, career.season, career.calendar.circuit)
+    {
+        content.push_str(&format!(
+            " Your contract with {} has run out, so you're a free agent.",
+            contract.team
+        ));
+        career.history.push(CareerHistoryEntry {
+            season: finished,
+            description: format!("Contract with {} expired", contract.team),
+        });
+        career.contract = None;
+        career.current_team = None;
+    }
+    messages.push(InboxMessage {
+        id: 0,
+        subject: format!("Season {} wrap-up", finished),
+        from: "Commissioner".to_string(),
+        content,
+        read: false,
+    });
+}
+
+/// Whether a contract's term is over by the start of `season`. The term runs
+/// from the week the player joined, a season being a year, and a contract
+/// ending partway through a season lasts until that season is over.
+fn contract_runs_out(contract: &Contract, season: u32, circuit: Circuit) -> bool {
+    let joined_months = contract.start_week.saturating_sub(1) * 12 / season_weeks(circuit);
+    let end_months = contract.start_season * 12 + joined_months + contract.contract_length_months;
+    season * 12 >= end_months
+}
+
+/// Numbers this week's messages after what's already in the inbox and puts
+/// them at the top, dropping the oldest past the cap.
+pub(crate) fn deliver(career: &mut Career, messages: &mut [InboxMessage]) {
+    let first_id = career.inbox.iter().map(|m| m.id).max().unwrap_or(0) + 1;
+    for (id, message) in (first_id..).zip(messages.iter_mut()) {
+        message.id = id;
+        career.inbox.insert(0, message.clone());
+    }
+    career.inbox.truncate(MAX_INBOX_MESSAGES);
+}
+
+fn player_strength(skills: &PlayerSkills) -> f32 {
+    (skills.aim + skills.hs + skills.movement + skills.util) / 4.0
+}
+
+// A teammate or opponent around the circuit's usual level
+fn circuit_skills(level: f32, rng: &mut StdRng) -> PlayerSkills {
+    let mut skill = || (level + rng.random_range(-0.05..=0.05)).clamp(0.0, 1.0);
+    PlayerSkills {
+        aim: skill(),
+        hs: skill(),
+        movement: skill(),
+        util: skill(),
+    }
+}
+
+fn agent_for_role(role: Option<&str>) -> Agent {
+    match role.map(|r| r.to_ascii_lowercase()).as_deref() {
+        Some("controller") => Agent::Omen,
+        Some("initiator") => Agent::Sova,
+        Some("sentinel") => Agent::Killjoy,
+        _ => Agent::Jett,
+    }
+}
+
+fn play_official_match(
+    career: &Career,
+    stage: &StageInfo,
+    team: &str,
+    opponent: &str,
+    rng: &mut StdRng,
+    balance: &BalanceConfig,
+) -> Result<CareerMatch, String> {
+    let level = career.calendar.circuit.skill_level();
+    let player_agent = agent_for_role(career.profile.role.as_deref());
+    let mut teammate_agents = [
+        Agent::Jett,
+        Agent::Omen,
+        Agent::Sova,
+        Agent::Killjoy,
+        Agent::Breach,
+    ]
+    .into_iter()
+    .filter(|agent| *agent != player_agent)
+    .collect::<Vec<_>>()
+    .into_iter();
+
+    let mut players = vec![Player::new(
+        CAREER_PLAYER_ID,
+        career.profile.name.clone(),
+        player_agent,
+        Team::Attackers,
+        career.skills(),
+    )];
+    for id in 2..=5 {
+        let agent = teammate_agents.next().unwrap_or(Agent::Breach);
+        players.push(Player::new(
+            id,
+            format!("{} #{}", team, id),
+            agent,
+            Team::Attackers,
+            circuit_skills(level, rng),
+        ));
+    }
+    for (offset, agent) in [
+        Agent::Jett,
+        Agent::Omen,
+        Agent::Sova,
+        Agent::Killjoy,
+        Agent::Breach,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let id = 6 + offset as u32;
+        players.push(Player::new(
+            id,
+            format!("{} #{}", opponent, offset + 1),
+            agent,
+            Team::Defenders,
+            circuit_skills(level, rng),
+        ));
+    }
+
+    let seed: u64 = rng.random();
+    let series = run_series(stage.format, &Map::all(), &players, seed, balance)?;
+    let (kills, deaths, assists) = series
+        .maps
+        .iter()
+        .flat_map(|map| &map.player_stats)
+        .filter(|stats| stats.player_id == CAREER_PLAYER_ID)
+        .fold((0, 0, 0), |(k, d, a), stats| {
+            (k + stats.kills, d + stats.deaths, a + stats.assists)
+        });
+
+    Ok(CareerMatch {
+        stage: stage.name.clone(),
+        team: team.to_string(),
+        opponent: opponent.to_string(),
+        format: stage.format,
+        seed,
+        maps_won: series.team_a_maps,
+        maps_lost: series.team_b_maps,
+        won: series.winner == SeriesTeam::TeamA,
+        kills,
+        deaths,
+        assists,
+    })
+}
+
+/// Names of the teams in `circuit` from the teams table, to draw official
+/// opponents from.
+pub async fn circuit_teams(
+    pool: &deadpool_postgres::Pool,
+    circuit: Circuit,
+) -> Result<Vec<String>, String> {
+    let Some(tier) = circuit.team_tier() else {
+        return Ok(Vec::new());
+    };
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    let rows = client
+        .query(
+            "SELECT team_name FROM teams WHERE tier = $1 ORDER BY team_name",
+            &[&tier],
+        )
+        .await
+        .map_err(|e| format!("Failed to fetch teams: {}", e))?;
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
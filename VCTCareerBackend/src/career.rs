@@ -0,0 +1,388 @@
+use deadpool_postgres::Pool;
+use serde::{Deserialize, Serialize};
+use tokio_postgres::GenericClient;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::attributes::{Development, PlayerAttributes};
+use crate::calendar::{CareerCalendar, Circuit};
+use crate::placement::{PlacementRequest, PlacementResult, place_for_season};
+use crate::sim::PlayerSkills;
+
+/// The season a new career starts in.
+pub const FIRST_SEASON: u32 = 2025;
+
+const MIN_AGE: u32 = 13;
+const MAX_AGE: u32 = 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateCareerRequest {
+    pub name: Option<String>, // Omit to be called "Player"
+    pub age: u32,
+    pub current_rank: String,
+    pub past_experience: String, // "Tier 1", "Tier 2", "Tier 3" or "None"
+    pub division: String,
+    pub role: Option<String>, // e.g. "Duelist"; omit if undecided
+    #[serde(default)]
+    pub rr: Option<u32>, // RR into the current rank, for placement
+    #[serde(default)]
+    pub hidden_mmr: Option<i32>, // for placement
+    #[serde(default)]
+    pub placement_season: Option<String>, // Omit for the default placement model
+}
+
+impl CreateCareerRequest {
+    pub fn placement_request(&self) -> PlacementRequest {
+        PlacementRequest {
+            age: self.age,
+            current_rank: self.current_rank.clone(),
+            past_experience: self.past_experience.clone(),
+            region: self.division.clone(),
+            rr: self.rr,
+            hidden_mmr: self.hidden_mmr,
+            season: self.placement_season.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PlayerProfile {
+    pub name: String,
+    pub age: u32,
+    pub current_rank: String,
+    pub past_experience: String,
+    pub division: String,
+    pub role: Option<String>,
+    pub starting_tier: String, // Where placement put the player
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Contract {
+    pub team: String,
+    pub region: String,
+    pub yearly_salary: u32,
+    pub contract_length_months: u32,
+    pub start_season: u32, // The season the player joins the team
+    #[serde(default)]
+    pub start_week: u32, // and the week; 0 on contracts saved before it was kept
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CareerHistoryEntry {
+    pub season: u32,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct InboxMessage {
+    pub id: u32,
+    pub subject: String,
+    pub from: String,
+    pub content: String,
+    pub read: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Career {
+    pub id: String,
+    pub profile: PlayerProfile,
+    pub attributes: PlayerAttributes,
+    pub current_team: Option<String>, // None while a free agent
+    pub contract: Option<Contract>,
+    pub season: u32,
+    pub history: Vec<CareerHistoryEntry>, // Oldest first
+    pub inbox: Vec<InboxMessage>,         // Newest first
+    #[serde(default)]
+    pub calendar: CareerCalendar, // Where the career is in the season
+    #[serde(default)]
+    pub development: Development, // Training focus, form and morale
+    pub created_at: String,               // RFC 3339, UTC; set by the database
+    pub updated_at: String,
+}
+
+/// The parts of a career the player edits directly; omitted fields are left
+/// alone. Contracts only change by accepting offers and attributes only by
+/// weekly development.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct UpdateCareerRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub role: Option<String>, // e.g. "Duelist"
+    #[serde(default)]
+    pub inbox: Vec<InboxReadFlag>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InboxReadFlag {
+    pub id: u32, // An inbox message id
+    pub read: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetTrainingFocusRequest {
+    pub focus: String, // "Balanced", "Aim", "Movement", "Utility", "Teamplay" or "Clutch"
+}
+
+impl Career {
+    /// A fresh free-agent career placed by the season's placement model. Not
+    /// yet saved, so the timestamps are empty.
+    pub fn new(id: Uuid, request: &CreateCareerRequest) -> Result<Self, String> {
+        let placement = place_for_season(&request.placement_request())?;
+        Self::with_placement(id, request, &placement)
+    }
+
+    /// `new` for a caller that has already placed the player.
+    pub fn with_placement(
+        id: Uuid,
+        request: &CreateCareerRequest,
+        placement: &PlacementResult,
+    ) -> Result<Self, String> {
+        let starting_tier = placement.tier_name.clone();
+        let name = request
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or("Player")
+            .to_string();
+
+        let career = Career {
+            id: id.to_string(),
+            profile: PlayerProfile {
+                name,
+                age: request.age,
+                current_rank: request.current_rank.clone(),
+                past_experience: request.past_experience.clone(),
+                division: request.division.clone(),
+                role: request.role.clone(),
+                starting_tier: starting_tier.clone(),
+            },
+            attributes: PlayerAttributes::starting(&request.current_rank, &request.past_experience),
+            current_team: None,
+            contract: None,
+            season: FIRST_SEASON,
+            history: vec![CareerHistoryEntry {
+                season: FIRST_SEASON,
+                description: format!("Started a career in {}", starting_tier),
+            }],
+            inbox: vec![InboxMessage {
+                id: 1,
+                subject: "Welcome to the League!".to_string(),
+                from: "Commissioner".to_string(),
+                content: format!(
+                    "Congratulations on going pro! Placement has put you in {}. \
+                     Teams will be in touch with offers, so keep an eye on your inbox.",
+                    starting_tier
+                ),
+                read: false,
+            }],
+            calendar: CareerCalendar::new(
+                Circuit::from_starting_tier(&starting_tier),
+                id.as_u64_pair().0,
+            ),
+            development: Development::default(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        career.validate()?;
+        Ok(career)
+    }
+
+    /// What the simulation plays the player with: their attributes, lifted
+    /// or dragged down by form and morale.
+    pub fn skills(&self) -> PlayerSkills {
+        self.development.condition.apply(self.attributes.skills())
+    }
+
+    pub fn apply_update(&mut self, update: UpdateCareerRequest) -> Result<(), String> {
+        let mut updated = self.clone();
+        if let Some(name) = update.name {
+            updated.profile.name = name.trim().to_string();
+        }
+        if let Some(role) = update.role {
+            updated.profile.role = Some(role);
+        }
+        for flag in update.inbox {
+            let message = updated
+                .inbox
+                .iter_mut()
+                .find(|message| message.id == flag.id)
+                .ok_or_else(|| format!("No inbox message with id {}", flag.id))?;
+            message.read = flag.read;
+        }
+        updated.validate()?;
+        *self = updated;
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.profile.name.trim().is_empty() {
+            return Err("Player name cannot be empty".to_string());
+        }
+        if !(MIN_AGE..=MAX_AGE).contains(&self.profile.age) {
+            return Err(format!(
+                "Age must be between {} and {}, got {}",
+                MIN_AGE, MAX_AGE, self.profile.age
+            ));
+        }
+        self.attributes.validate()?;
+        if let Some(contract) = &self.contract {
+            // Until a signing for next season starts the player has no team
+            let joined = contract.start_season <= self.season;
+            let expected_team = joined.then_some(contract.team.as_str());
+            if self.current_team.as_deref() != expected_team {
+                return Err(format!(
+                    "Contract is with {} but the current team is {}",
+                    contract.team,
+                    self.current_team.as_deref().unwrap_or("none")
+                ));
+            }
+            if contract.contract_length_months == 0 {
+                return Err("Contract length must be at least one month".to_string());
+            }
+        }
+        if self.season < FIRST_SEASON {
+            return Err(format!("Season cannot be before {}", FIRST_SEASON));
+        }
+        Ok(())
+    }
+}
+
+const CAREER_COLUMNS: &str = "data::text,
+    to_char(created_at AT TIME ZONE 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"'),
+    to_char(updated_at AT TIME ZONE 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"')";
+
+fn career_from_row(row: &tokio_postgres::Row) -> Result<Career, String> {
+    let mut career: Career = serde_json::from_str(row.get(0))
+        .map_err(|e| format!("Stored career is unreadable: {}", e))?;
+    career.created_at = row.get(1);
+    career.updated_at = row.get(2);
+    Ok(career)
+}
+
+fn career_data(career: &Career) -> Result<String, String> {
+    serde_json::to_string(career).map_err(|e| e.to_string())
+}
+
+/// Inserts a career built by `Career::new`, returning it with its timestamps.
+pub async fn insert_career(pool: &Pool, career: &Career) -> Result<Career, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    let row = client
+        .query_one(
+            &format!(
+                "INSERT INTO careers (id, player_name, current_team, season, data)
+                 VALUES ($1, $2, $3, $4, $5::text::jsonb)
+                 RETURNING {}",
+                CAREER_COLUMNS
+            ),
+            &[
+                &career.id,
+                &career.profile.name,
+                &career.current_team,
+                &(career.season as i32),
+                &career_data(career)?,
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to save career: {}", e))?;
+    career_from_row(&row)
+}
+
+/// `None` if there is no career with that id.
+pub async fn get_career(pool: &Pool, career_id: &str) -> Result<Option<Career>, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    read_career(&**client, career_id).await
+}
+
+/// `get_career` on a client or transaction the caller already holds.
+pub(crate) async fn read_career<C: GenericClient>(
+    client: &C,
+    career_id: &str,
+) -> Result<Option<Career>, String> {
+    let row = client
+        .query_opt(
+            &format!("SELECT {} FROM careers WHERE id = $1", CAREER_COLUMNS),
+            &[&career_id],
+        )
+        .await
+        .map_err(|e| format!("Failed to load career: {}", e))?;
+    row.as_ref().map(career_from_row).transpose()
+}
+
+/// `read_career` that also locks the row until the transaction ends, so
+/// nothing else can change the career between reading and writing it back.
+pub async fn read_career_for_update<C: GenericClient>(
+    transaction: &C,
+    career_id: &str,
+) -> Result<Option<Career>, String> {
+    let row = transaction
+        .query_opt(
+            &format!(
+                "SELECT {} FROM careers WHERE id = $1 FOR UPDATE",
+                CAREER_COLUMNS
+            ),
+            &[&career_id],
+        )
+        .await
+        .map_err(|e| format!("Failed to load career: {}", e))?;
+    row.as_ref().map(career_from_row).transpose()
+}
+
+/// Writes `career` over the stored copy. `None` if it was never saved or has
+/// been deleted.
+pub async fn save_career(pool: &Pool, career: &Career) -> Result<Option<Career>, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    write_career(&**client, career).await
+}
+
+/// `save_career` on a client or transaction the caller already holds.
+pub async fn write_career<C: GenericClient>(
+    client: &C,
+    career: &Career,
+) -> Result<Option<Career>, String> {
+    career.validate()?;
+    let row = client
+        .query_opt(
+            &format!(
+                "UPDATE careers
+                 SET player_name = $2, current_team = $3, season = $4,
+                     data = $5::text::jsonb, updated_at = now()
+                 WHERE id = $1
+                 RETURNING {}",
+                CAREER_COLUMNS
+            ),
+            &[
+                &career.id,
+                &career.profile.name,
+                &career.current_team,
+                &(career.season as i32),
+                &career_data(career)?,
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to save career: {}", e))?;
+    row.as_ref().map(career_from_row).transpose()
+}
+
+/// Whether there was a career to delete.
+pub async fn delete_career(pool: &Pool, career_id: &str) -> Result<bool, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    let deleted = client
+        .execute("DELETE FROM careers WHERE id = $1", &[&career_id])
+        .await
+        .map_err(|e| format!("Failed to delete career: {}", e))?;
+    Ok(deleted > 0)
+}
@@ -0,0 +1,738 @@
+use deadpool_postgres::Pool;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokio_postgres::GenericClient;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::calendar::{Circuit, deliver, season_weeks};
+use crate::career::{
+    Career, CareerHistoryEntry, Contract, InboxMessage, read_career, read_career_for_update,
+    write_career,
+};
+use crate::offers::{Offer, OfferRequest, generate_offers};
+
+/// Why settling an offer failed, so the API can tell a refused request from
+/// a database failure.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OfferError {
+    Rejected(String), // the offer isn't open, the counter is invalid or the team can't pay
+    Database(String),
+}
+
+// The database helpers report their errors as plain strings
+impl From<String> for OfferError {
+    fn from(error: String) -> Self {
+        OfferError::Database(error)
+    }
+}
+
+/// In-game weeks an offer stays open after it's made.
+pub const OFFER_VALID_WEEKS: u32 = 2;
+/// Counters a team sits through before it walks away.
+pub const MAX_COUNTERS: u32 = 3;
+pub const MIN_CONTRACT_MONTHS: u32 = 6;
+pub const MAX_CONTRACT_MONTHS: u32 = 60;
+
+// How far past its opening salary a team that badly wants the player will go
+const MAX_STRETCH: f32 = 0.3;
+// Every counter the team doesn't agree to wears its interest down by this
+const INTEREST_LOST_PER_COUNTER: f32 = 0.1;
+// Cut to what a team pays for each year away from the length it wants
+const LENGTH_PENALTY_PER_YEAR: f32 = 0.1;
+// Teams want players at or above this overall for longer, and the rest for shorter
+const LONG_TERM_OVERALL: u32 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum OfferStatus {
+    Open,
+    Accepted,
+    Declined,  // by the player
+    Withdrawn, // by the team
+    Expired,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct NegotiationStep {
+    pub by: String, // the player's name or the team's
+    pub yearly_salary: u32,
+    pub contract_length_months: u32,
+    pub note: String,
+}
+
+/// A team's offer to a career and where negotiating it has got to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ContractOffer {
+    pub id: String,
+    pub career_id: String,
+    pub team: String,
+    pub region: String,
+    pub tier: Option<i16>, // the team's tier, as in the teams table
+    pub yearly_salary: u32,
+    pub contract_length_months: u32,
+    pub status: OfferStatus,
+    pub interest: f32,              // 0-1; how much the team wants the player
+    pub opening_salary: u32,        // what the team first valued the player at
+    pub opening_length_months: u32, // the length the team wants
+    pub overall_at_offer: u32,      // the player's overall when the offer was made
+    pub counters: u32,
+    pub expires_season: u32, // the offer can be accepted until this week is played
+    pub expires_week: u32,
+    pub negotiation: Vec<NegotiationStep>, // Oldest first
+    pub created_at: String,                // RFC 3339, UTC; set by the database
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CareerOffersRequest {
+    pub count: usize,
+    pub tier: Option<i16>, // Omit for the career's circuit, or tier 3 from ranked
+    pub region: Option<String>, // Omit for teams from every region
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CounterOfferRequest {
+    pub yearly_salary: u32,
+    pub contract_length_months: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum TeamResponse {
+    Agreed,    // the offer now has the player's terms; accept it to sign
+    Countered, // the team met the player partway
+    Declined,  // over the team's budget; the offer stands as it was
+    Withdrew,  // the team has walked away
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CounterOutcome {
+    pub response: TeamResponse,
+    pub message: String,
+    pub offer: ContractOffer,
+}
+
+impl CounterOfferRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.yearly_salary == 0 {
+            return Err("Yearly salary must be positive".to_string());
+        }
+        if !(MIN_CONTRACT_MONTHS..=MAX_CONTRACT_MONTHS).contains(&self.contract_length_months) {
+            return Err(format!(
+                "Contract length must be between {} and {} months, got {}",
+                MIN_CONTRACT_MONTHS, MAX_CONTRACT_MONTHS, self.contract_length_months
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ContractOffer {
+    /// Opens negotiations on a generated offer, valid for `OFFER_VALID_WEEKS`
+    /// from where the career is now. Not yet saved, so the timestamps are empty.
+    pub fn new(id: Uuid, career: &Career, offer: Offer, tier: Option<i16>, interest: f32) -> Self {
+        let mut expires_season = career.season;
+        let mut expires_week = career.calendar.week + OFFER_VALID_WEEKS;
+        let weeks = season_weeks(career.calendar.circuit);
+        if expires_week > weeks {
+            expires_season += 1;
+            expires_week -= weeks;
+        }
+        ContractOffer {
+            id: id.to_string(),
+            career_id: career.id.clone(),
+            negotiation: vec![NegotiationStep {
+                by: offer.team.clone(),
+                yearly_salary: offer.yearlysalary,
+                contract_length_months: offer.contract_length_months,
+                note: "Opening offer".to_string(),
+            }],
+            team: offer.team,
+            region: offer.region,
+            tier,
+            yearly_salary: offer.yearlysalary,
+            contract_length_months: offer.contract_length_months,
+            status: OfferStatus::Open,
+            interest: interest.clamp(0.0, 1.0),
+            opening_salary: offer.yearlysalary,
+            opening_length_months: offer.contract_length_months,
+            overall_at_offer: career.attributes.overall(),
+            counters: 0,
+            expires_season,
+            expires_week,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    pub fn is_expired(&self, career: &Career) -> bool {
+        (career.season, career.calendar.week) > (self.expires_season, self.expires_week)
+    }
+
+    /// Marks an open offer the career's clock has run past as expired.
+    /// Whether it changed.
+    pub fn refresh(&mut self, career: &Career) -> bool {
+        if self.status == OfferStatus::Open && self.is_expired(career) {
+            self.status = OfferStatus::Expired;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn check_open(&self, career: &Career) -> Result<(), String> {
+        if self.status != OfferStatus::Open {
+            return Err(format!("The offer from {} is {:?}", self.team, self.status));
+        }
+        if self.is_expired(career) {
+            return Err(format!("The offer from {} has expired", self.team));
+        }
+        Ok(())
+    }
+}
+
+/// How much a team in `tier` wants a player: more the further the player's
+/// overall sits above what the tier usually fields, give or take a little.
+pub fn team_interest(overall: u32, tier: Option<i16>, rng: &mut impl Rng) -> f32 {
+    let expected = match tier {
+        Some(1) => 62.0,
+        Some(2) => 52.0,
+        Some(3) => 42.0,
+        _ => 35.0,
+    };
+    (0.5 + (overall as f32 - expected) / 30.0 + rng.random_range(-0.1..=0.1)).clamp(0.05, 1.0)
+}
+
+/// The most `offer`'s team will pay a year for a contract of
+/// `length_months`, before its budget has a say: its opening valuation,
+/// moved by how the player's overall has changed since, stretched by its
+/// interest and cut for lengths it doesn't want.
+pub fn team_limit(offer: &ContractOffer, overall: u32, length_months: u32) -> u32 {
+    let growth = overall.max(1) as f32 / offer.overall_at_offer.max(1) as f32;
+    let stretch = 1.0 + MAX_STRETCH * offer.interest;
+    let years_longer = (length_months as f32 - offer.opening_length_months as f32) / 12.0;
+    let unwanted_years = if overall >= LONG_TERM_OVERALL {
+        -years_longer
+    } else {
+        years_longer
+    }
+    .max(0.0);
+    let length = (1.0 - LENGTH_PENALTY_PER_YEAR * unwanted_years).max(0.5);
+    (offer.opening_salary as f32 * growth * stretch * length) as u32
+}
+
+/// The team's answer to the player's counter. `room` is the team's budget
+/// minus its expenses. The team agrees to anything within its limit; past
+/// it, every counter costs interest until the team walks away.
+pub fn respond_to_counter(
+    offer: &mut ContractOffer,
+    counter: &CounterOfferRequest,
+    career: &Career,
+    room: i64,
+) -> Result<CounterOutcome, String> {
+    offer.check_open(career)?;
+    counter.validate()?;
+    offer.counters += 1;
+    offer.negotiation.push(NegotiationStep {
+        by: career.profile.name.clone(),
+        yearly_salary: counter.yearly_salary,
+        contract_length_months: counter.contract_length_months,
+        note: "Counter".to_string(),
+    });
+
+    let limit = (team_limit(
+        offer,
+        career.attributes.overall(),
+        counter.contract_length_months,
+    ) as i64)
+        .min(room.max(0));
+    let (response, message) = if counter.yearly_salary as i64 <= limit {
+        offer.yearly_salary = counter.yearly_salary;
+        offer.contract_length_months = counter.contract_length_months;
+        (
+            TeamResponse::Agreed,
+            format!(
+                "{} agree to ${} a year over {} months. Accept the offer to sign.",
+                offer.team, offer.yearly_salary, offer.contract_length_months
+            ),
+        )
+    } else {
+        offer.interest = (offer.interest - INTEREST_LOST_PER_COUNTER).max(0.0);
+        if offer.counters >= MAX_COUNTERS || offer.interest <= 0.0 {
+            offer.status = OfferStatus::Withdrawn;
+            (
+                TeamResponse::Withdrew,
+                format!(
+                    "{} have pulled their offer after {} counter(s).",
+                    offer.team, offer.counters
+                ),
+            )
+        } else if counter.yearly_salary as i64 > room {
+            (
+                TeamResponse::Declined,
+                format!(
+                    "{} can't fit ${} a year in their budget. Their offer stands.",
+                    offer.team, counter.yearly_salary
+                ),
+            )
+        } else {
+            // Partway to the player, but never past what the team will pay for this length
+            offer.yearly_salary = offer.yearly_salary.midpoint(limit as u32).min(limit as u32);
+            offer.contract_length_months = counter.contract_length_months;
+            (
+                TeamResponse::Countered,
+                format!(
+                    "{} come back with ${} a year over {} months.",
+                    offer.team, offer.yearly_salary, offer.contract_length_months
+                ),
+            )
+        }
+    };
+    offer.negotiation.push(NegotiationStep {
+        by: offer.team.clone(),
+        yearly_salary: offer.yearly_salary,
+        contract_length_months: offer.contract_length_months,
+        note: message.clone(),
+    });
+    Ok(CounterOutcome {
+        response,
+        message,
+        offer: offer.clone(),
+    })
+}
+
+/// Signs `offer` onto `career`: the contract, the team, a history entry and
+/// an inbox message. A team from another circuit moves the career there from
+/// next season. `room` is the team's budget minus its expenses.
+pub fn sign_offer(
+    career: &mut Career,
+    offer: &mut ContractOffer,
+    room: i64,
+) -> Result<Contract, String> {
+    offer.check_open(career)?;
+    if let Some(contract) = &career.contract {
+        return Err(format!("Already under contract with {}", contract.team));
+    }
+    if offer.yearly_salary as i64 > room {
+        return Err(format!(
+            "{} can no longer afford ${} a year",
+            offer.team, offer.yearly_salary
+        ));
+    }
+
+    // Rosters are locked mid-season, so a team in another circuit takes the
+    // player on from the start of next season and the term counts from there
+    let next_circuit = offer
+        .tier
+        .and_then(Circuit::from_team_tier)
+        .filter(|circuit| *circuit != career.calendar.circuit);
+    let (start_season, start_week) = match next_circuit {
+        Some(_) => (career.season + 1, 1),
+        None => (career.season, career.calendar.week),
+    };
+    let contract = Contract {
+        team: offer.team.clone(),
+        region: offer.region.clone(),
+        yearly_salary: offer.yearly_salary,
+        contract_length_months: offer.contract_length_months,
+        start_season,
+        start_week,
+    };
+    offer.status = OfferStatus::Accepted;
+    // A team in another circuit only takes the player on when the move happens
+    if next_circuit.is_none() {
+        career.current_team = Some(contract.team.clone());
+    }
+    career.contract = Some(contract.clone());
+    career.history.push(CareerHistoryEntry {
+        season: career.season,
+        description: format!(
+            "Signed with {} for ${} a year over {} months",
+            contract.team, contract.yearly_salary, contract.contract_length_months
+        ),
+    });
+
+    let mut content = format!(
+        "Welcome to {}! You're signed for ${} a year over {} months.",
+        contract.team, contract.yearly_salary, contract.contract_length_months
+    );
+    career.calendar.next_circuit = next_circuit;
+    if let Some(circuit) = next_circuit {
+        content.push_str(&format!(
+            " Rosters are locked for this season, so you'll join them in {:?} next season.",
+            circuit
+        ));
+    }
+    deliver(
+        career,
+        &mut [InboxMessage {
+            id: 0,
+            subject: format!("Signed with {}", contract.team),
+            from: contract.team.clone(),
+            content,
+            read: false,
+        }],
+    );
+    career.validate()?;
+    Ok(contract)
+}
+
+const OFFER_COLUMNS: &str = "data::text,
+    to_char(created_at AT TIME ZONE 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"'),
+    to_char(updated_at AT TIME ZONE 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"')";
+
+fn offer_from_row(row: &tokio_postgres::Row) -> Result<ContractOffer, String> {
+    let mut offer: ContractOffer = serde_json::from_str(row.get(0))
+        .map_err(|e| format!("Stored offer is unreadable: {}", e))?;
+    offer.created_at = row.get(1);
+    offer.updated_at = row.get(2);
+    Ok(offer)
+}
+
+fn offer_data(offer: &ContractOffer) -> Result<String, String> {
+    serde_json::to_string(offer).map_err(|e| e.to_string())
+}
+
+async fn insert_offer<C: GenericClient>(
+    client: &C,
+    offer: &ContractOffer,
+) -> Result<ContractOffer, String> {
+    let row = client
+        .query_one(
+            &format!(
+                "INSERT INTO contract_offers (id, career_id, team_name, status, data)
+                 VALUES ($1, $2, $3, $4, $5::text::jsonb)
+                 RETURNING {}",
+                OFFER_COLUMNS
+            ),
+            &[
+                &offer.id,
+                &offer.career_id,
+                &offer.team,
+                &format!("{:?}", offer.status),
+                &offer_data(offer)?,
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to save offer: {}", e))?;
+    offer_from_row(&row)
+}
+
+/// Saves a change to an offer that is still open. `None` if the stored offer
+/// has been accepted, declined or otherwise settled since it was read, in
+/// which case nothing is written.
+async fn write_offer<C: GenericClient>(
+    client: &C,
+    offer: &ContractOffer,
+) -> Result<Option<ContractOffer>, String> {
+    let row = client
+        .query_opt(
+            &format!(
+                "UPDATE contract_offers
+                 SET status = $2, data = $3::text::jsonb, updated_at = now()
+                 WHERE id = $1 AND status = 'Open'
+                 RETURNING {}",
+                OFFER_COLUMNS
+            ),
+            &[
+                &offer.id,
+                &format!("{:?}", offer.status),
+                &offer_data(offer)?,
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to save offer: {}", e))?;
+    row.as_ref().map(offer_from_row).transpose()
+}
+
+fn no_longer_open(offer: &ContractOffer) -> OfferError {
+    OfferError::Rejected(format!("The offer from {} is no longer open", offer.team))
+}
+
+async fn read_offers<C: GenericClient>(
+    client: &C,
+    career_id: &str,
+) -> Result<Vec<ContractOffer>, String> {
+    client
+        .query(
+            &format!(
+                "SELECT {} FROM contract_offers WHERE career_id = $1 ORDER BY created_at DESC, id",
+                OFFER_COLUMNS
+            ),
+            &[&career_id],
+        )
+        .await
+        .map_err(|e| format!("Failed to load offers: {}", e))?
+        .iter()
+        .map(offer_from_row)
+        .collect()
+}
+
+async fn read_offer<C: GenericClient>(
+    client: &C,
+    career_id: &str,
+    offer_id: &str,
+) -> Result<Option<ContractOffer>, String> {
+    let row = client
+        .query_opt(
+            &format!(
+                "SELECT {} FROM contract_offers WHERE id = $1 AND career_id = $2",
+                OFFER_COLUMNS
+            ),
+            &[&offer_id, &career_id],
+        )
+        .await
+        .map_err(|e| format!("Failed to load offer: {}", e))?;
+    row.as_ref().map(offer_from_row).transpose()
+}
+
+/// What the team has to spend: its budget minus its expenses.
+async fn team_room<C: GenericClient>(client: &C, team: &str) -> Result<i64, String> {
+    let row = client
+        .query_opt(
+            "SELECT budget, expenses FROM teams WHERE team_name = $1",
+            &[&team],
+        )
+        .await
+        .map_err(|e| format!("Failed to load team: {}", e))?
+        .ok_or_else(|| format!("Team {} no longer exists", team))?;
+    let budget: Option<i64> = row.get(0);
+    let expenses: Option<i64> = row.get(1);
+    Ok(budget.unwrap_or(0) - expenses.unwrap_or(0))
+}
+
+/// Asks teams for offers with `generate_offers` and saves each one for the
+/// career to negotiate. `None` if there is no career with that id.
+pub async fn create_offers(
+    pool: &Pool,
+    career_id: &str,
+    request: &CareerOffersRequest,
+) -> Result<Option<Vec<ContractOffer>>, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    let Some(career) = read_career(&**client, career_id).await? else {
+        return Ok(None);
+    };
+    let tier = request
+        .tier
+        .or(career.calendar.circuit.team_tier())
+        .unwrap_or(3);
+    let overall = career.attributes.overall();
+    let generated = generate_offers(
+        pool,
+        &OfferRequest {
+            tier: tier.to_string(),
+            count: request.count,
+            region: request.region.clone(),
+            overall: Some(overall),
+        },
+    )
+    .await?;
+
+    let mut rng = rand::rng();
+    let mut offers = Vec::new();
+    for offer in generated {
+        if career.current_team.as_deref() == Some(offer.team.as_str()) {
+            continue;
+        }
+        let interest = team_interest(overall, Some(tier), &mut rng);
+        let offer = ContractOffer::new(Uuid::new_v4(), &career, offer, Some(tier), interest);
+        offers.push(insert_offer(&**client, &offer).await?);
+    }
+    Ok(Some(offers))
+}
+
+/// Every offer the career has had, newest first, with any the career's
+/// clock has run past marked expired. `None` if there is no career with
+/// that id.
+pub async fn list_offers(
+    pool: &Pool,
+    career_id: &str,
+) -> Result<Option<Vec<ContractOffer>>, String> {
+    let mut client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    let transaction = client
+        .transaction()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let Some(career) = read_career_for_update(&*transaction, career_id).await? else {
+        return Ok(None);
+    };
+    let mut offers = read_offers(&*transaction, career_id).await?;
+    for offer in offers.iter_mut() {
+        if offer.refresh(&career)
+            && let Some(saved) = write_offer(&*transaction, offer).await?
+        {
+            *offer = saved;
+        }
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(|e| format!("Failed to save offers: {}", e))?;
+    Ok(Some(offers))
+}
+
+/// `None` if there is no such offer for the career.
+pub async fn counter_offer(
+    pool: &Pool,
+    career_id: &str,
+    offer_id: &str,
+    counter: &CounterOfferRequest,
+) -> Result<Option<CounterOutcome>, OfferError> {
+    let mut client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    let transaction = client
+        .transaction()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    // Locked like accept_offer, so the two can't both settle the same offer
+    let Some(career) = read_career_for_update(&*transaction, career_id).await? else {
+        return Ok(None);
+    };
+    let Some(mut offer) = read_offer(&*transaction, career_id, offer_id).await? else {
+        return Ok(None);
+    };
+    if offer.refresh(&career) {
+        expire_offer(transaction, &offer).await?;
+        return Err(OfferError::Rejected(format!(
+            "The offer from {} has expired",
+            offer.team
+        )));
+    }
+    let room = team_room(&*transaction, &offer.team).await?;
+    let mut outcome =
+        respond_to_counter(&mut offer, counter, &career, room).map_err(OfferError::Rejected)?;
+    outcome.offer = write_offer(&*transaction, &offer)
+        .await?
+        .ok_or_else(|| no_longer_open(&offer))?;
+    transaction
+        .commit()
+        .await
+        .map_err(|e| format!("Failed to save counter: {}", e))?;
+    Ok(Some(outcome))
+}
+
+/// Signs the offer: the career gets the contract, the team's budget pays
+/// the first year's salary, and the career's other open offers are
+/// declined, all or nothing. `None` if there is no such offer for the career.
+pub async fn accept_offer(
+    pool: &Pool,
+    career_id: &str,
+    offer_id: &str,
+) -> Result<Option<Career>, OfferError> {
+    let mut client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    let transaction = client
+        .transaction()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    // Locked so two acceptances can't both find the player without a contract
+    let Some(mut career) = read_career_for_update(&*transaction, career_id).await? else {
+        return Ok(None);
+    };
+    let Some(mut offer) = read_offer(&*transaction, career_id, offer_id).await? else {
+        return Ok(None);
+    };
+    if offer.refresh(&career) {
+        expire_offer(transaction, &offer).await?;
+        return Err(OfferError::Rejected(format!(
+            "The offer from {} has expired",
+            offer.team
+        )));
+    }
+    let room = team_room(&*transaction, &offer.team).await?;
+    let contract = sign_offer(&mut career, &mut offer, room).map_err(OfferError::Rejected)?;
+
+    // Checked again in the update so two signings can't overspend the budget
+    let charged = transaction
+        .execute(
+            "UPDATE teams SET budget = budget - $2
+             WHERE team_name = $1 AND budget - COALESCE(expenses, 0) >= $2",
+            &[&contract.team, &(contract.yearly_salary as i64)],
+        )
+        .await
+        .map_err(|e| format!("Failed to charge {}: {}", contract.team, e))?;
+    if charged == 0 {
+        return Err(OfferError::Rejected(format!(
+            "{} can no longer afford ${} a year",
+            contract.team, contract.yearly_salary
+        )));
+    }
+
+    write_offer(&*transaction, &offer)
+        .await?
+        .ok_or_else(|| no_longer_open(&offer))?;
+    for mut other in read_offers(&*transaction, career_id).await? {
+        if other.id != offer.id && other.status == OfferStatus::Open {
+            other.status = OfferStatus::Declined;
+            write_offer(&*transaction, &other).await?;
+        }
+    }
+    let saved = write_career(&*transaction, &career).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(|e| format!("Failed to sign contract: {}", e))?;
+    Ok(saved)
+}
+
+/// `None` if there is no such offer for the career.
+pub async fn decline_offer(
+    pool: &Pool,
+    career_id: &str,
+    offer_id: &str,
+) -> Result<Option<ContractOffer>, OfferError> {
+    let mut client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    let transaction = client
+        .transaction()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let Some(career) = read_career_for_update(&*transaction, career_id).await? else {
+        return Ok(None);
+    };
+    let Some(mut offer) = read_offer(&*transaction, career_id, offer_id).await? else {
+        return Ok(None);
+    };
+    if offer.refresh(&career) {
+        expire_offer(transaction, &offer).await?;
+        return Err(OfferError::Rejected(format!(
+            "The offer from {} has expired",
+            offer.team
+        )));
+    }
+    offer.check_open(&career).map_err(OfferError::Rejected)?;
+    offer.status = OfferStatus::Declined;
+    let declined = write_offer(&*transaction, &offer)
+        .await?
+        .ok_or_else(|| no_longer_open(&offer))?;
+    transaction
+        .commit()
+        .await
+        .map_err(|e| format!("Failed to decline offer: {}", e))?;
+    Ok(Some(declined))
+}
+
+/// Saves an offer `refresh` has just marked expired. Committed on its own,
+/// since the request that found it goes on to fail and would otherwise roll
+/// the expiry back.
+async fn expire_offer(
+    transaction: deadpool_postgres::Transaction<'_>,
+    offer: &ContractOffer,
+) -> Result<(), String> {
+    write_offer(&*transaction, offer).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(|e| format!("Failed to expire offer: {}", e))
+}
@@ -1,50 +1,8 @@
-use crate::models::CareerInfo;
-use crate::models::StartingTier;
 use actix_web::{HttpResponse, Responder, web};
 use dotenv::dotenv;
 use serde::Deserialize;
 use tokio_postgres::NoTls;
 
-pub fn weighted_tier(info: &CareerInfo) -> StartingTier {
-    let mut score = 0.0;
-    let rank_score: f32 = match info.current_rank.as_str() {
-        "Radiant" => 5.0,
-        "Immortal" => 4.0,
-        "Ascendant" => 2.0,
-        "Diamond" => 1.0,
-        "Platinum" => 0.5,
-        _ => 0.0,
-    };
-    score += rank_score;
-    let exp_score = match info.past_experience.as_str() {
-        "Tier 1" => 4.0,
-        "Tier 2" => 3.0,
-        "Tier 3" => 2.0,
-        _ => 0.0,
-    };
-    if rank_score >= 2.0 {
-        score += exp_score;
-    } else if exp_score > 0.0 {
-        score += 1.0;
-    }
-    match info.division.to_lowercase().as_str() {
-        "na" | "eu" => score += 1.0,
-        _ => {}
-    }
-    match score {
-        s if s >= 9.0 => StartingTier::Tier1,
-        s if s >= 6.0 => StartingTier::Tier2,
-        s if s >= 3.0 => {
-            if (17..=24).contains(&info.age) {
-                StartingTier::Tier3
-            } else {
-                StartingTier::RankedPlay
-            }
-        }
-        _ => StartingTier::RankedPlay,
-    }
-}
-
 #[derive(Debug, Deserialize)]
 pub struct TeamQuery {
     pub team_name: Option<String>,
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::balance::{BalanceConfig, EconomyConfig};
+use crate::sim::{ArmorType, RoundEndReason, Team, Weapon};
+
+/// How a team spends its credits for a round. Decided once per team at the
+/// start of the buy phase, then every player buys to match it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum BuyType {
+    Pistol,
+    Eco,
+    Bonus,   // won last round but can't full buy, so play the guns that survived
+    HalfBuy, // SMGs and light armor, keeping something back
+    Force,   // spend everything, saving won't help
+    FullBuy, // rifles and heavy armor, with drops for anyone short
+}
+
+impl BuyType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BuyType::Pistol => "Pistol",
+            BuyType::Eco => "Eco",
+            BuyType::Bonus => "Bonus",
+            BuyType::HalfBuy => "Half Buy",
+            BuyType::Force => "Force Buy",
+            BuyType::FullBuy => "Full Buy",
+        }
+    }
+}
+
+/// A rifle one player bought for a teammate who couldn't afford their own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct WeaponDrop {
+    pub buyer_id: u32,
+    pub recipient_id: u32,
+    pub weapon: Weapon,
+}
+
+/// What a team knows about its economy going into the buy phase.
+#[derive(Debug, Clone, Default)]
+pub struct TeamBuyContext {
+    pub credits: Vec<u32>, // one entry per player
+    pub loss_streak: u8,
+    pub won_last_round: bool,
+    pub pistol_round: bool,
+    pub after_pistol_round: bool,
+    pub last_round_of_half: bool, // credits are reset afterwards anyway
+    pub facing_elimination: bool, // losing this round loses the match
+}
+
+/// Rifle and heavy armor, the standard full buy.
+pub fn full_buy_cost(balance: &BalanceConfig) -> u32 {
+    balance.weapons[&Weapon::Vandal].price + balance.armor_costs.cost(&ArmorType::Heavy)
+}
+
+/// SMG and light armor.
+pub fn half_buy_cost(balance: &BalanceConfig) -> u32 {
+    balance.weapons[&Weapon::Spectre].price + balance.armor_costs.cost(&ArmorType::Light)
+}
+
+pub fn decide_team_buy(context: &TeamBuyContext, balance: &BalanceConfig) -> BuyType {
+    if context.pistol_round {
+        return BuyType::Pistol;
+    }
+    if context.credits.is_empty() {
+        return BuyType::Eco;
+    }
+
+    // Drops let rich players cover poor ones, so the team average is what counts
+    let average = context.credits.iter().sum::<u32>() / context.credits.len() as u32;
+    let full_cost = full_buy_cost(balance);
+
+    if average >= full_cost {
+        return BuyType::FullBuy;
+    }
+    if context.last_round_of_half || context.facing_elimination {
+        return BuyType::Force;
+    }
+    if context.won_last_round {
+        // After a pistol win the opponents are on an eco, so cheap SMGs are enough
+        return if context.after_pistol_round {
+            BuyType::HalfBuy
+        } else {
+            BuyType::Bonus
+        };
+    }
+    // Buy light only if the team can still full buy next round after losing
+    // this one; otherwise save for it
+    let loss_bonus = balance.economy.loss_bonus_for_streak(context.loss_streak);
+    let half_cost = half_buy_cost(balance);
+    if average >= half_cost && average - half_cost + loss_bonus >= full_cost {
+        return BuyType::HalfBuy;
+    }
+    BuyType::Eco
+}
+
+/// How a round ended, as far as the credit rules care.
+#[derive(Debug, Clone)]
+pub struct RoundOutcome {
+    pub winner: Team,
+    pub reason: RoundEndReason,
+    pub spike_planted: bool,
+}
+
+/// Round-end credits for one player, before the credit cap. Kill rewards are
+/// paid as the kills happen and aren't included.
+///
+/// - Winners get the round win reward.
+/// - Losers get the loss bonus for their team's current streak, except
+///   defenders still alive when the spike detonates: they saved instead of
+///   retaking and get nothing.
+/// - Attackers who lose a round after planting get the plant reward on top.
+pub fn round_credits(
+    team: &Team,
+    survived: bool,
+    loss_streak: u8,
+    outcome: &RoundOutcome,
+    economy: &EconomyConfig,
+) -> u32 {
+    if *team == outcome.winner {
+        return economy.round_win_reward;
+    }
+
+    let saved_through_detonation =
+        *team == Team::Defenders && survived && outcome.reason == RoundEndReason::SpikeDetonated;
+    let mut credits = if saved_through_detonation {
+        0
+    } else {
+        economy.loss_bonus_for_streak(loss_streak)
+    };
+
+    if *team == Team::Attackers && outcome.spike_planted {
+        credits += economy.spike_plant_reward;
+    }
+    credits
+}
+
+/// A team's loss streak after a round: a win resets it, a loss extends it.
+pub fn next_loss_streak(team: &Team, loss_streak: u8, outcome: &RoundOutcome) -> u8 {
+    if *team == outcome.winner {
+        0
+    } else {
+        loss_streak.saturating_add(1)
+    }
+}
+
+/// Adds credits to a balance without going over the cap.
+pub fn add_credits(current: u32, earned: u32, economy: &EconomyConfig) -> u32 {
+    (current + earned).min(economy.credit_cap)
+}
@@ -1,9 +1,22 @@
+pub mod agents;
+pub mod attributes;
+pub mod balance;
+pub mod batch;
+pub mod calendar;
+pub mod career;
+pub mod contracts;
 pub mod db;
+pub mod economy;
+pub mod maps;
 pub mod models;
 pub mod offers;
+pub mod persistence;
+pub mod placement;
 pub mod ranked;
+pub mod series;
 pub mod sim;
 pub mod simulation_manager;
+pub mod win_probability;
 
 // Re-export enums from simulation_manager for external use
 pub use simulation_manager::{AdvanceMode, SimulationCommand};
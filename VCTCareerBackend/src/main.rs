@@ -2,19 +2,19 @@ mod db;
 mod models;
 mod offers;
 
-use crate::db::weighted_tier;
 use crate::db::{TeamQuery, get_teams_handler};
 use crate::models::{
     AdvanceSimulationRequest, CareerInfo, CreateSimulationRequest, CreateSimulationResponse,
-    EventFilterRequest, SimulationControlRequest,
+    EventFilterRequest, EventStreamQuery, SimulationControlRequest,
 };
 use crate::offers::OfferRequest;
 use actix_cors::Cors;
-use actix_web::post;
 use actix_web::{App, HttpResponse, HttpServer, Responder, get, web};
+use actix_web::{delete, post, put};
 use deadpool_postgres::{Manager, Pool};
 use dotenv::dotenv;
 use log::debug;
+use rand::Rng;
 use rand::seq::{IndexedRandom, SliceRandom};
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -23,10 +23,22 @@ use tokio_postgres::Config;
 use tokio_postgres::NoTls;
 use utoipa::OpenApi;
 use utoipa_actix_web::AppExt;
+use vctcareer_backend::attributes::parse_training_focus;
+use vctcareer_backend::balance::BalanceConfig;
+use vctcareer_backend::batch;
+use vctcareer_backend::calendar;
+use vctcareer_backend::career;
+use vctcareer_backend::contracts;
+use vctcareer_backend::maps::parse_map;
+use vctcareer_backend::persistence;
+use vctcareer_backend::placement;
 use vctcareer_backend::ranked::{
     MapPoolRequest, MatchInput, RandomMapResponse, RankTier, RrEstimateResponse, estimate_rr_change,
 };
-use vctcareer_backend::sim::{Agent, Player, Team, ValorantSimulation};
+use vctcareer_backend::series::{self, SeriesManager, create_series_manager};
+use vctcareer_backend::sim::{
+    Agent, MatchTeams, OvertimeSettings, Player, Team, TiebreakMode, ValorantSimulation,
+};
 use vctcareer_backend::simulation_manager;
 
 type SimulationManager = Arc<Mutex<HashMap<uuid::Uuid, ValorantSimulation>>>;
@@ -71,6 +83,14 @@ fn parse_team(team_str: &str) -> Result<Team, String> {
         _ => Err(format!("Unknown team: {}", team_str)),
     }
 }
+
+fn parse_tiebreak_mode(mode_str: &str) -> Result<TiebreakMode, String> {
+    match mode_str {
+        "Tournament" => Ok(TiebreakMode::Tournament),
+        "Ranked" => Ok(TiebreakMode::Ranked),
+        _ => Err(format!("Unknown tiebreak mode: {}", mode_str)),
+    }
+}
 use utoipa_swagger_ui::SwaggerUi;
 use uuid::Uuid;
 
@@ -78,6 +98,8 @@ use uuid::Uuid;
 pub struct CreateCareerResponse {
     pub starting_tier: String,
     pub career_info: CareerInfo,
+    pub career_id: String, // The saved career, see /careers/{id}
+    pub placement: vctcareer_backend::placement::PlacementResult, // Why it placed where it did
 }
 
 #[get("/")]
@@ -102,17 +124,438 @@ async fn index() -> impl Responder {
     )
 )]
 #[post("/createCareer")]
-async fn create_career(info: web::Json<CareerInfo>) -> impl Responder {
+async fn create_career(pool: web::Data<Pool>, info: web::Json<CareerInfo>) -> impl Responder {
     debug!("POST /createCareer called with: {:?}", info);
-    let tier = weighted_tier(&info);
+    let info = info.into_inner();
+    let request = career::CreateCareerRequest {
+        name: None,
+        age: info.age,
+        current_rank: info.current_rank.clone(),
+        past_experience: info.past_experience.clone(),
+        division: info.division.clone(),
+        role: None,
+        rr: None,
+        hidden_mmr: None,
+        placement_season: None,
+    };
+    let placement = match placement::place_for_season(&request.placement_request()) {
+        Ok(placement) => placement,
+        Err(e) => return HttpResponse::BadRequest().body(e),
+    };
+    let new_career = match career::Career::with_placement(Uuid::new_v4(), &request, &placement) {
+        Ok(new_career) => new_career,
+        Err(e) => return HttpResponse::BadRequest().body(e),
+    };
+    let saved = match career::insert_career(pool.get_ref(), &new_career).await {
+        Ok(saved) => saved,
+        Err(e) => return HttpResponse::InternalServerError().body(e),
+    };
     let response = serde_json::json!({
-        "starting_tier": tier.as_str(),
-        "career_info": info.into_inner()
+        "starting_tier": placement.tier_name,
+        "career_info": info,
+        "career_id": saved.id,
+        "placement": placement
     });
     debug!("Response: {}", response);
     HttpResponse::Ok().json(response)
 }
 
+#[utoipa::path(
+    post,
+    path = "/placement",
+    request_body = vctcareer_backend::placement::PlacementRequest,
+    responses(
+        (status = 200, description = "Tier with each factor's contribution and the odds of every tier", body = vctcareer_backend::placement::PlacementResult),
+        (status = 400, description = "Unknown rank or placement season", body = String),
+    )
+)]
+#[post("/placement")]
+async fn preview_placement(
+    request: web::Json<vctcareer_backend::placement::PlacementRequest>,
+) -> impl Responder {
+    match placement::place_for_season(&request) {
+        Ok(result) => HttpResponse::Ok().json(result),
+        Err(e) => HttpResponse::BadRequest().body(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/careers",
+    request_body = vctcareer_backend::career::CreateCareerRequest,
+    responses(
+        (status = 200, description = "Career placed and saved", body = vctcareer_backend::career::Career),
+        (status = 400, description = "Invalid career details", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[post("/careers")]
+async fn create_career_save(
+    pool: web::Data<Pool>,
+    request: web::Json<career::CreateCareerRequest>,
+) -> impl Responder {
+    let new_career = match career::Career::new(Uuid::new_v4(), &request) {
+        Ok(new_career) => new_career,
+        Err(e) => return HttpResponse::BadRequest().body(e),
+    };
+    match career::insert_career(pool.get_ref(), &new_career).await {
+        Ok(saved) => HttpResponse::Ok().json(saved),
+        Err(e) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/careers/{id}",
+    params(
+        ("id" = String, Path, description = "Career ID")
+    ),
+    responses(
+        (status = 200, description = "Saved career", body = vctcareer_backend::career::Career),
+        (status = 404, description = "Career not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[get("/careers/{id}")]
+async fn get_career(pool: web::Data<Pool>, path: web::Path<String>) -> impl Responder {
+    match career::get_career(pool.get_ref(), &path.into_inner()).await {
+        Ok(Some(saved)) => HttpResponse::Ok().json(saved),
+        Ok(None) => HttpResponse::NotFound().body("Career not found"),
+        Err(e) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/careers/{id}",
+    params(
+        ("id" = String, Path, description = "Career ID")
+    ),
+    request_body = vctcareer_backend::career::UpdateCareerRequest,
+    responses(
+        (status = 200, description = "Career updated", body = vctcareer_backend::career::Career),
+        (status = 400, description = "Invalid name, or an unknown inbox message", body = String),
+        (status = 404, description = "Career not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[put("/careers/{id}")]
+async fn update_career(
+    pool: web::Data<Pool>,
+    path: web::Path<String>,
+    request: web::Json<career::UpdateCareerRequest>,
+) -> impl Responder {
+    let mut client = match pool.get().await {
+        Ok(client) => client,
+        Err(e) => return HttpResponse::InternalServerError().body(e.to_string()),
+    };
+    // The whole career is written back, so keep a week or a signing from
+    // landing in between and being reverted
+    let transaction = match client.transaction().await {
+        Ok(transaction) => transaction,
+        Err(e) => return HttpResponse::InternalServerError().body(e.to_string()),
+    };
+    let mut saved = match career::read_career_for_update(&*transaction, &path.into_inner()).await {
+        Ok(Some(saved)) => saved,
+        Ok(None) => return HttpResponse::NotFound().body("Career not found"),
+        Err(e) => return HttpResponse::InternalServerError().body(e),
+    };
+    if let Err(e) = saved.apply_update(request.into_inner()) {
+        return HttpResponse::BadRequest().body(e);
+    }
+    let updated = match career::write_career(&*transaction, &saved).await {
+        Ok(Some(updated)) => updated,
+        Ok(None) => return HttpResponse::NotFound().body("Career not found"),
+        Err(e) => return HttpResponse::InternalServerError().body(e),
+    };
+    match transaction.commit().await {
+        Ok(()) => HttpResponse::Ok().json(updated),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/careers/{id}",
+    params(
+        ("id" = String, Path, description = "Career ID")
+    ),
+    responses(
+        (status = 204, description = "Career deleted"),
+        (status = 404, description = "Career not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[delete("/careers/{id}")]
+async fn delete_career(pool: web::Data<Pool>, path: web::Path<String>) -> impl Responder {
+    match career::delete_career(pool.get_ref(), &path.into_inner()).await {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => HttpResponse::NotFound().body("Career not found"),
+        Err(e) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/careers/{id}/training",
+    params(
+        ("id" = String, Path, description = "Career ID")
+    ),
+    request_body = vctcareer_backend::career::SetTrainingFocusRequest,
+    responses(
+        (status = 200, description = "Training focus set", body = vctcareer_backend::career::Career),
+        (status = 400, description = "Unknown training focus", body = String),
+        (status = 404, description = "Career not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[put("/careers/{id}/training")]
+async fn set_training_focus(
+    pool: web::Data<Pool>,
+    path: web::Path<String>,
+    request: web::Json<career::SetTrainingFocusRequest>,
+) -> impl Responder {
+    let focus = match parse_training_focus(&request.focus) {
+        Ok(focus) => focus,
+        Err(e) => return HttpResponse::BadRequest().body(e),
+    };
+    let mut client = match pool.get().await {
+        Ok(client) => client,
+        Err(e) => return HttpResponse::InternalServerError().body(e.to_string()),
+    };
+    // Locked like update_career, so a week played meanwhile isn't thrown away
+    let transaction = match client.transaction().await {
+        Ok(transaction) => transaction,
+        Err(e) => return HttpResponse::InternalServerError().body(e.to_string()),
+    };
+    let mut saved = match career::read_career_for_update(&*transaction, &path.into_inner()).await {
+        Ok(Some(saved)) => saved,
+        Ok(None) => return HttpResponse::NotFound().body("Career not found"),
+        Err(e) => return HttpResponse::InternalServerError().body(e),
+    };
+    saved.development.focus = focus;
+    let updated = match career::write_career(&*transaction, &saved).await {
+        Ok(Some(updated)) => updated,
+        Ok(None) => return HttpResponse::NotFound().body("Career not found"),
+        Err(e) => return HttpResponse::InternalServerError().body(e),
+    };
+    match transaction.commit().await {
+        Ok(()) => HttpResponse::Ok().json(updated),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/careers/{id}/calendar",
+    params(
+        ("id" = String, Path, description = "Career ID")
+    ),
+    responses(
+        (status = 200, description = "The career's season, stage by stage", body = vctcareer_backend::calendar::SeasonCalendar),
+        (status = 404, description = "Career not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[get("/careers/{id}/calendar")]
+async fn get_career_calendar(pool: web::Data<Pool>, path: web::Path<String>) -> impl Responder {
+    match career::get_career(pool.get_ref(), &path.into_inner()).await {
+        Ok(Some(saved)) => match calendar::season_calendar(&saved) {
+            Ok(season) => HttpResponse::Ok().json(season),
+            Err(e) => HttpResponse::InternalServerError().body(e),
+        },
+        Ok(None) => HttpResponse::NotFound().body("Career not found"),
+        Err(e) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/careers/{id}/advance-week",
+    params(
+        ("id" = String, Path, description = "Career ID")
+    ),
+    responses(
+        (status = 200, description = "What happened this week, with the saved career after it", body = vctcareer_backend::calendar::WeekReport),
+        (status = 404, description = "Career not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[post("/careers/{id}/advance-week")]
+async fn advance_career_week(pool: web::Data<Pool>, path: web::Path<String>) -> impl Responder {
+    let mut client = match pool.get().await {
+        Ok(client) => client,
+        Err(e) => return HttpResponse::InternalServerError().body(e.to_string()),
+    };
+    // Held until the week is saved, so a second request for the same career
+    // waits and then plays the following week instead of replaying this one
+    let transaction = match client.transaction().await {
+        Ok(transaction) => transaction,
+        Err(e) => return HttpResponse::InternalServerError().body(e.to_string()),
+    };
+    let mut saved = match career::read_career_for_update(&*transaction, &path.into_inner()).await {
+        Ok(Some(saved)) => saved,
+        Ok(None) => return HttpResponse::NotFound().body("Career not found"),
+        Err(e) => return HttpResponse::InternalServerError().body(e),
+    };
+    let opponents = match calendar::circuit_teams(pool.get_ref(), saved.calendar.circuit).await {
+        Ok(opponents) => opponents,
+        Err(e) => return HttpResponse::InternalServerError().body(e),
+    };
+    // Official series are full simulations, so keep them off the async workers
+    let week = web::block(move || {
+        calendar::advance_week(&mut saved, &opponents, &BalanceConfig::default())
+    })
+    .await;
+    let mut report = match week {
+        Ok(Ok(report)) => report,
+        Ok(Err(e)) => return HttpResponse::InternalServerError().body(e),
+        Err(e) => return HttpResponse::InternalServerError().body(e.to_string()),
+    };
+    match career::write_career(&*transaction, &report.career).await {
+        Ok(Some(updated)) => report.career = updated,
+        Ok(None) => return HttpResponse::NotFound().body("Career not found"),
+        Err(e) => return HttpResponse::InternalServerError().body(e),
+    }
+    match transaction.commit().await {
+        Ok(()) => HttpResponse::Ok().json(report),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/careers/{id}/offers",
+    params(
+        ("id" = String, Path, description = "Career ID")
+    ),
+    request_body = vctcareer_backend::contracts::CareerOffersRequest,
+    responses(
+        (status = 200, description = "New offers, open for negotiation", body = Vec<vctcareer_backend::contracts::ContractOffer>),
+        (status = 404, description = "Career not found", body = String),
+        (status = 500, description = "No teams could make an offer, or internal server error", body = String),
+    )
+)]
+#[post("/careers/{id}/offers")]
+async fn create_contract_offers(
+    pool: web::Data<Pool>,
+    path: web::Path<String>,
+    request: web::Json<contracts::CareerOffersRequest>,
+) -> impl Responder {
+    match contracts::create_offers(pool.get_ref(), &path.into_inner(), &request).await {
+        Ok(Some(offers)) => HttpResponse::Ok().json(offers),
+        Ok(None) => HttpResponse::NotFound().body("Career not found"),
+        Err(e) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/careers/{id}/offers",
+    params(
+        ("id" = String, Path, description = "Career ID")
+    ),
+    responses(
+        (status = 200, description = "Every offer the career has had, newest first", body = Vec<vctcareer_backend::contracts::ContractOffer>),
+        (status = 404, description = "Career not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[get("/careers/{id}/offers")]
+async fn list_contract_offers(pool: web::Data<Pool>, path: web::Path<String>) -> impl Responder {
+    match contracts::list_offers(pool.get_ref(), &path.into_inner()).await {
+        Ok(Some(offers)) => HttpResponse::Ok().json(offers),
+        Ok(None) => HttpResponse::NotFound().body("Career not found"),
+        Err(e) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/careers/{id}/offers/{offer_id}/counter",
+    params(
+        ("id" = String, Path, description = "Career ID"),
+        ("offer_id" = String, Path, description = "Offer ID")
+    ),
+    request_body = vctcareer_backend::contracts::CounterOfferRequest,
+    responses(
+        (status = 200, description = "The team's response", body = vctcareer_backend::contracts::CounterOutcome),
+        (status = 400, description = "Invalid counter, or the offer is no longer open", body = String),
+        (status = 404, description = "Offer not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[post("/careers/{id}/offers/{offer_id}/counter")]
+async fn counter_contract_offer(
+    pool: web::Data<Pool>,
+    path: web::Path<(String, String)>,
+    request: web::Json<contracts::CounterOfferRequest>,
+) -> impl Responder {
+    let (career_id, offer_id) = path.into_inner();
+    match contracts::counter_offer(pool.get_ref(), &career_id, &offer_id, &request).await {
+        Ok(Some(outcome)) => HttpResponse::Ok().json(outcome),
+        Ok(None) => HttpResponse::NotFound().body("Offer not found"),
+        Err(contracts::OfferError::Rejected(e)) => HttpResponse::BadRequest().body(e),
+        Err(contracts::OfferError::Database(e)) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/careers/{id}/offers/{offer_id}/accept",
+    params(
+        ("id" = String, Path, description = "Career ID"),
+        ("offer_id" = String, Path, description = "Offer ID")
+    ),
+    responses(
+        (status = 200, description = "Contract signed", body = vctcareer_backend::career::Career),
+        (status = 400, description = "The offer is no longer open, the player is under contract or the team can't afford it", body = String),
+        (status = 404, description = "Offer not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[post("/careers/{id}/offers/{offer_id}/accept")]
+async fn accept_contract_offer(
+    pool: web::Data<Pool>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (career_id, offer_id) = path.into_inner();
+    match contracts::accept_offer(pool.get_ref(), &career_id, &offer_id).await {
+        Ok(Some(signed)) => HttpResponse::Ok().json(signed),
+        Ok(None) => HttpResponse::NotFound().body("Offer not found"),
+        Err(contracts::OfferError::Rejected(e)) => HttpResponse::BadRequest().body(e),
+        Err(contracts::OfferError::Database(e)) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/careers/{id}/offers/{offer_id}/decline",
+    params(
+        ("id" = String, Path, description = "Career ID"),
+        ("offer_id" = String, Path, description = "Offer ID")
+    ),
+    responses(
+        (status = 200, description = "Offer declined", body = vctcareer_backend::contracts::ContractOffer),
+        (status = 400, description = "The offer is no longer open", body = String),
+        (status = 404, description = "Offer not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[post("/careers/{id}/offers/{offer_id}/decline")]
+async fn decline_contract_offer(
+    pool: web::Data<Pool>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (career_id, offer_id) = path.into_inner();
+    match contracts::decline_offer(pool.get_ref(), &career_id, &offer_id).await {
+        Ok(Some(offer)) => HttpResponse::Ok().json(offer),
+        Ok(None) => HttpResponse::NotFound().body("Offer not found"),
+        Err(contracts::OfferError::Rejected(e)) => HttpResponse::BadRequest().body(e),
+        Err(contracts::OfferError::Database(e)) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/teams",
@@ -210,8 +653,182 @@ async fn random_map(query: web::Query<MapPoolRequest>) -> impl Responder {
     HttpResponse::Ok().json(RandomMapResponse { selected_map: map })
 }
 
+// Series API Endpoints
+
+#[utoipa::path(
+    post,
+    path = "/series",
+    request_body = vctcareer_backend::series::CreateSeriesRequest,
+    responses(
+        (status = 200, description = "Series played", body = vctcareer_backend::series::SeriesResult),
+        (status = 400, description = "Invalid request", body = String),
+    )
+)]
+#[post("/series")]
+async fn create_series(
+    series_manager: web::Data<SeriesManager>,
+    request: web::Json<vctcareer_backend::series::CreateSeriesRequest>,
+) -> impl Responder {
+    // Up to five full maps, so keep them off the async workers
+    let manager = series_manager.get_ref().clone();
+    match web::block(move || series::create_series(&manager, request.into_inner())).await {
+        Ok(Ok(result)) => HttpResponse::Ok().json(result),
+        Ok(Err(e)) => HttpResponse::BadRequest().body(e),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/series/{id}",
+    params(
+        ("id" = String, Path, description = "Series ID")
+    ),
+    responses(
+        (status = 200, description = "Series result", body = vctcareer_backend::series::SeriesResult),
+        (status = 404, description = "Series not found", body = String),
+    )
+)]
+#[get("/series/{id}")]
+async fn get_series(
+    series_manager: web::Data<SeriesManager>,
+    path: web::Path<String>,
+) -> impl Responder {
+    match series::get_series(&series_manager, &path.into_inner()) {
+        Ok(result) => HttpResponse::Ok().json(result),
+        Err(e) => HttpResponse::NotFound().body(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/series/{id}/scoreboard",
+    params(
+        ("id" = String, Path, description = "Series ID")
+    ),
+    responses(
+        (status = 200, description = "Stats summed over every map of the series", body = vctcareer_backend::series::SeriesScoreboard),
+        (status = 404, description = "Series not found", body = String),
+    )
+)]
+#[get("/series/{id}/scoreboard")]
+async fn get_series_scoreboard(
+    series_manager: web::Data<SeriesManager>,
+    path: web::Path<String>,
+) -> impl Responder {
+    match series::get_series_scoreboard(&series_manager, &path.into_inner()) {
+        Ok(scoreboard) => HttpResponse::Ok().json(scoreboard),
+        Err(e) => HttpResponse::NotFound().body(e),
+    }
+}
+
+// Match History API Endpoints
+
+#[utoipa::path(
+    get,
+    path = "/matches",
+    params(
+        ("player_id" = Option<u32>, Query, description = "Only matches this player played in"),
+        ("player_name" = Option<String>, Query, description = "Only matches a player with this name played in"),
+        ("team" = Option<String>, Query, description = "Only matches this roster played in"),
+        ("from" = Option<String>, Query, description = "Completed at or after this date, e.g. 2025-06-01"),
+        ("to" = Option<String>, Query, description = "Completed before this date"),
+        ("limit" = Option<i64>, Query, description = "Most matches to return (default 50, at most 500)"),
+    ),
+    responses(
+        (status = 200, description = "Completed matches, most recent first", body = Vec<vctcareer_backend::persistence::MatchSummary>),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[get("/matches")]
+async fn find_matches(
+    pool: web::Data<Pool>,
+    query: web::Query<vctcareer_backend::persistence::MatchQuery>,
+) -> impl Responder {
+    match persistence::find_matches(pool.get_ref(), &query).await {
+        Ok(matches) => HttpResponse::Ok().json(matches),
+        Err(e) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/matches/{id}",
+    params(
+        ("id" = String, Path, description = "Simulation ID of a completed match")
+    ),
+    responses(
+        (status = 200, description = "Match result with final player stats", body = vctcareer_backend::persistence::MatchDetail),
+        (status = 404, description = "Match not found", body = String),
+    )
+)]
+#[get("/matches/{id}")]
+async fn get_match(pool: web::Data<Pool>, path: web::Path<String>) -> impl Responder {
+    match persistence::get_match(pool.get_ref(), &path.into_inner()).await {
+        Ok(detail) => HttpResponse::Ok().json(detail),
+        Err(e) => HttpResponse::NotFound().body(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/matches/{id}/events",
+    params(
+        ("id" = String, Path, description = "Simulation ID")
+    ),
+    responses(
+        (status = 200, description = "Stored event log in order", body = Vec<vctcareer_backend::sim::GameEvent>),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[get("/matches/{id}/events")]
+async fn get_match_events(pool: web::Data<Pool>, path: web::Path<String>) -> impl Responder {
+    match persistence::get_match_events(pool.get_ref(), &path.into_inner()).await {
+        Ok(events) => HttpResponse::Ok().json(events),
+        Err(e) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
 // Simulation API Endpoints
 
+#[utoipa::path(
+    get,
+    path = "/simulation/patches",
+    responses(
+        (status = 200, description = "Patch versions a simulation can be balanced for", body = Vec<String>),
+        (status = 500, description = "Balance files could not be read", body = String),
+    )
+)]
+#[get("/simulation/patches")]
+async fn list_patch_versions() -> impl Responder {
+    match BalanceConfig::available_versions() {
+        Ok(versions) => HttpResponse::Ok().json(versions),
+        Err(e) => HttpResponse::InternalServerError().body(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/simulation/batch",
+    request_body = vctcareer_backend::batch::BatchSimulationRequest,
+    responses(
+        (status = 200, description = "Outcome distribution over every simulated match", body = vctcareer_backend::batch::BatchResult),
+        (status = 400, description = "Invalid request", body = String),
+        (status = 500, description = "Batch could not be run", body = String),
+    )
+)]
+#[post("/simulation/batch")]
+async fn simulate_batch(
+    request: web::Json<vctcareer_backend::batch::BatchSimulationRequest>,
+) -> impl Responder {
+    // Hundreds of full matches take a while, so keep them off the async workers
+    match web::block(move || batch::simulate_batch(request.into_inner())).await {
+        Ok(Ok(result)) => HttpResponse::Ok().json(result),
+        Ok(Err(e)) => HttpResponse::BadRequest().body(e),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/simulation/create",
@@ -226,7 +843,41 @@ async fn create_simulation(
     sim_manager: web::Data<SimulationManager>,
     request: web::Json<CreateSimulationRequest>,
 ) -> impl Responder {
-    let mut sim = ValorantSimulation::new();
+    let balance = match &request.patch_version {
+        Some(version) => match BalanceConfig::load(version) {
+            Ok(balance) => balance,
+            Err(e) => return HttpResponse::BadRequest().body(e),
+        },
+        None => BalanceConfig::default(),
+    };
+    let seed = request.seed.unwrap_or_else(|| rand::rng().random());
+    let mut sim = ValorantSimulation::with_balance(seed, balance);
+    if let Some(map_name) = &request.map {
+        let map = match parse_map(map_name) {
+            Ok(m) => m,
+            Err(e) => return HttpResponse::BadRequest().body(e),
+        };
+        if let Err(e) = sim.set_map(map) {
+            return HttpResponse::BadRequest().body(e);
+        }
+    }
+    if request.tiebreak.is_some() || request.sudden_death.is_some() {
+        let mut overtime = OvertimeSettings::default();
+        if let Some(tiebreak) = &request.tiebreak {
+            overtime.tiebreak = match parse_tiebreak_mode(tiebreak) {
+                Ok(mode) => mode,
+                Err(e) => return HttpResponse::BadRequest().body(e),
+            };
+        }
+        overtime.sudden_death = request.sudden_death.unwrap_or(false);
+        if let Err(e) = sim.set_overtime_settings(overtime) {
+            return HttpResponse::BadRequest().body(e);
+        }
+    }
+    sim.state.teams = MatchTeams {
+        starting_attackers: request.attacker_team.clone(),
+        starting_defenders: request.defender_team.clone(),
+    };
     let simulation_id = sim.get_current_state().id;
 
     // Convert and add players to simulation
@@ -361,15 +1012,28 @@ async fn control_simulation(
     request: web::Json<SimulationControlRequest>,
 ) -> impl Responder {
     let simulation_id = path.into_inner();
-    match simulation_manager::control_simulation_legacy(
+    if let Err(e) = simulation_manager::control_simulation_legacy(
         &sim_manager,
-        simulation_id,
+        simulation_id.clone(),
         request.action.clone(),
         request.speed,
     ) {
-        Ok(()) => HttpResponse::Ok().body("Control applied successfully"),
-        Err(e) => HttpResponse::BadRequest().body(e),
+        return HttpResponse::BadRequest().body(e);
+    }
+
+    // (Re)start server-driven playback so speed changes and resumes take effect immediately
+    if let Ok(Some(generation)) =
+        simulation_manager::claim_playback_driver(&sim_manager, &simulation_id)
+        && let Ok(uuid_id) = Uuid::parse_str(&simulation_id)
+    {
+        actix_web::rt::spawn(simulation_manager::drive_playback(
+            sim_manager.get_ref().clone(),
+            uuid_id,
+            generation,
+        ));
     }
+
+    HttpResponse::Ok().body("Control applied successfully")
 }
 
 #[utoipa::path(
@@ -410,6 +1074,93 @@ async fn get_simulation_events(
     }
 }
 
+/// Adapts a subscription channel into an SSE response body.
+struct SseEventBody {
+    receiver: simulation_manager::EventReceiver,
+}
+
+impl futures_core::Stream for SseEventBody {
+    type Item = Result<web::Bytes, actix_web::Error>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.receiver
+            .poll_recv(cx)
+            .map(|event| event.map(|e| Ok(web::Bytes::from(e.to_sse_frame()))))
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/simulation/{id}/events/stream",
+    params(
+        ("id" = String, Path, description = "Simulation ID"),
+        ("event_types" = Option<String>, Query, description = "Comma-separated event types to receive"),
+        ("player_ids" = Option<String>, Query, description = "Comma-separated player IDs to receive events for"),
+        ("from" = Option<usize>, Query, description = "Event index to resume from; the Last-Event-ID header is used if omitted"),
+    ),
+    responses(
+        (status = 200, description = "Server-Sent Events stream of simulation events", body = String, content_type = "text/event-stream"),
+        (status = 400, description = "Invalid request", body = String),
+        (status = 404, description = "Simulation not found", body = String),
+    )
+)]
+#[get("/simulation/{id}/events/stream")]
+async fn stream_simulation_events(
+    sim_manager: web::Data<SimulationManager>,
+    path: web::Path<String>,
+    query: web::Query<EventStreamQuery>,
+    req: actix_web::HttpRequest,
+) -> impl Responder {
+    let simulation_id = path.into_inner();
+    let query = query.into_inner();
+
+    let event_types = query.event_types.map(|types| {
+        types
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+    });
+    let player_ids = match query.player_ids {
+        Some(ids) => match ids
+            .split(',')
+            .filter(|id| !id.trim().is_empty())
+            .map(|id| id.trim().parse::<u32>())
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(ids) => Some(ids),
+            Err(_) => return HttpResponse::BadRequest().body("Invalid player_ids"),
+        },
+        None => None,
+    };
+    // Browsers send the id of the last frame they saw when they reconnect
+    let from = query.from.or_else(|| {
+        req.headers()
+            .get("Last-Event-ID")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<usize>().ok())
+            .map(|last| last + 1)
+    });
+
+    match simulation_manager::subscribe_to_events(
+        &sim_manager,
+        &simulation_id,
+        Uuid::new_v4().to_string(),
+        event_types,
+        player_ids,
+        from,
+    ) {
+        Ok(receiver) => HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .insert_header(("Cache-Control", "no-cache"))
+            .streaming(SseEventBody { receiver }),
+        Err(e) => HttpResponse::NotFound().body(e),
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/simulation/{id}/stats",
@@ -454,6 +1205,25 @@ async fn get_live_stats(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/simulation/{id}/win-probability",
+    responses(
+        (status = 200, description = "Win probability after every round start, kill, plant and round end, grouped by round", body = Vec<vctcareer_backend::win_probability::RoundWinProbability>),
+        (status = 404, description = "Simulation not found", body = String),
+    )
+)]
+#[get("/simulation/{id}/win-probability")]
+async fn get_win_probability_timeline(
+    sim_manager: web::Data<SimulationManager>,
+    path: web::Path<String>,
+) -> impl Responder {
+    match simulation_manager::get_win_probability_timeline(&sim_manager, &path.into_inner()) {
+        Ok(timeline) => HttpResponse::Ok().json(timeline),
+        Err(e) => HttpResponse::NotFound().body(e),
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/simulation/{id}/scoreboard",
@@ -517,6 +1287,54 @@ async fn create_checkpoint(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/simulation/{id}/checkpoints",
+    params(
+        ("id" = String, Path, description = "Simulation ID")
+    ),
+    responses(
+        (status = 200, description = "Checkpoints for the simulation", body = Vec<vctcareer_backend::simulation_manager::SimulationCheckpoint>),
+        (status = 404, description = "Simulation not found", body = String),
+    )
+)]
+#[get("/simulation/{id}/checkpoints")]
+async fn list_checkpoints(
+    sim_manager: web::Data<SimulationManager>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let simulation_id = path.into_inner();
+    match simulation_manager::list_checkpoints_legacy(&sim_manager, simulation_id) {
+        Ok(checkpoints) => HttpResponse::Ok().json(checkpoints),
+        Err(e) => HttpResponse::NotFound().body(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/simulation/{id}/checkpoint/{checkpoint_id}/restore",
+    params(
+        ("id" = String, Path, description = "Simulation ID"),
+        ("checkpoint_id" = String, Path, description = "Checkpoint ID")
+    ),
+    responses(
+        (status = 200, description = "Checkpoint restored", body = String),
+        (status = 400, description = "Checkpoint could not be restored", body = String),
+    )
+)]
+#[post("/simulation/{id}/checkpoint/{checkpoint_id}/restore")]
+async fn restore_checkpoint(
+    sim_manager: web::Data<SimulationManager>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (simulation_id, checkpoint_id) = path.into_inner();
+    match simulation_manager::restore_checkpoint_legacy(&sim_manager, simulation_id, checkpoint_id)
+    {
+        Ok(()) => HttpResponse::Ok().body("Checkpoint restored successfully"),
+        Err(e) => HttpResponse::BadRequest().body(e),
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/simulation/{id}/events/at/{timestamp}",
@@ -545,6 +1363,54 @@ async fn get_events_at_timestamp(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/simulation/{id}/rewind/{round}",
+    params(
+        ("id" = String, Path, description = "Simulation ID"),
+        ("round" = u8, Path, description = "Round to rewind to")
+    ),
+    responses(
+        (status = 200, description = "Simulation rewound", body = String),
+        (status = 400, description = "Simulation could not be rewound", body = String),
+    )
+)]
+#[post("/simulation/{id}/rewind/{round}")]
+async fn rewind_to_round(
+    sim_manager: web::Data<SimulationManager>,
+    path: web::Path<(String, u8)>,
+) -> impl Responder {
+    let (simulation_id, round) = path.into_inner();
+    match simulation_manager::rewind_to_round_legacy(&sim_manager, simulation_id, round) {
+        Ok(()) => HttpResponse::Ok().body("Simulation rewound successfully"),
+        Err(e) => HttpResponse::BadRequest().body(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/simulation/{id}/replay/{timestamp}",
+    params(
+        ("id" = String, Path, description = "Simulation ID"),
+        ("timestamp" = u64, Path, description = "Match timestamp in ms to replay from")
+    ),
+    responses(
+        (status = 200, description = "Simulation replayed to timestamp", body = String),
+        (status = 400, description = "Simulation could not be replayed", body = String),
+    )
+)]
+#[post("/simulation/{id}/replay/{timestamp}")]
+async fn replay_from(
+    sim_manager: web::Data<SimulationManager>,
+    path: web::Path<(String, u64)>,
+) -> impl Responder {
+    let (simulation_id, timestamp) = path.into_inner();
+    match simulation_manager::replay_from_legacy(&sim_manager, simulation_id, timestamp) {
+        Ok(()) => HttpResponse::Ok().body("Simulation replayed successfully"),
+        Err(e) => HttpResponse::BadRequest().body(e),
+    }
+}
+
 #[derive(serde::Deserialize)]
 struct TimestampQuery {
     window_ms: Option<u64>,
@@ -556,24 +1422,88 @@ async fn main() -> std::io::Result<()> {
     #[openapi(
         paths(
             create_career,
+            preview_placement,
+            create_career_save,
+            get_career,
+            update_career,
+            delete_career,
+            set_training_focus,
+            get_career_calendar,
+            advance_career_week,
+            create_contract_offers,
+            list_contract_offers,
+            counter_contract_offer,
+            accept_contract_offer,
+            decline_contract_offer,
             get_teams,
             generate_offers,
             estimate_rr,
             random_map,
+            list_patch_versions,
+            create_series,
+            get_series,
+            get_series_scoreboard,
+            find_matches,
+            get_match,
+            get_match_events,
+            simulate_batch,
             create_simulation,
             get_simulation_state,
             advance_simulation,
             control_simulation,
             get_simulation_events,
+            stream_simulation_events,
             get_simulation_stats,
             get_live_stats,
+            get_win_probability_timeline,
             get_scoreboard,
             get_economy_status,
             create_checkpoint,
-            get_events_at_timestamp
+            list_checkpoints,
+            restore_checkpoint,
+            get_events_at_timestamp,
+            rewind_to_round,
+            replay_from
         ),
         components(schemas(
             crate::models::CareerInfo,
+            vctcareer_backend::placement::StartingTier,
+            vctcareer_backend::placement::PlacementRequest,
+            vctcareer_backend::placement::PlacementResult,
+            vctcareer_backend::placement::PlacementFactor,
+            vctcareer_backend::placement::TierProbability,
+            vctcareer_backend::placement::TierThresholds,
+            vctcareer_backend::career::CreateCareerRequest,
+            vctcareer_backend::career::UpdateCareerRequest,
+            vctcareer_backend::career::InboxReadFlag,
+            vctcareer_backend::career::Career,
+            vctcareer_backend::career::PlayerProfile,
+            vctcareer_backend::attributes::PlayerAttributes,
+            vctcareer_backend::attributes::Attribute,
+            vctcareer_backend::attributes::AttributeChange,
+            vctcareer_backend::attributes::TrainingFocus,
+            vctcareer_backend::attributes::PlayerCondition,
+            vctcareer_backend::attributes::AttributeProgress,
+            vctcareer_backend::attributes::Development,
+            vctcareer_backend::career::SetTrainingFocusRequest,
+            vctcareer_backend::career::Contract,
+            vctcareer_backend::career::CareerHistoryEntry,
+            vctcareer_backend::career::InboxMessage,
+            vctcareer_backend::contracts::CareerOffersRequest,
+            vctcareer_backend::contracts::ContractOffer,
+            vctcareer_backend::contracts::OfferStatus,
+            vctcareer_backend::contracts::NegotiationStep,
+            vctcareer_backend::contracts::CounterOfferRequest,
+            vctcareer_backend::contracts::CounterOutcome,
+            vctcareer_backend::contracts::TeamResponse,
+            vctcareer_backend::calendar::Circuit,
+            vctcareer_backend::calendar::StageKind,
+            vctcareer_backend::calendar::StageInfo,
+            vctcareer_backend::calendar::Record,
+            vctcareer_backend::calendar::CareerCalendar,
+            vctcareer_backend::calendar::SeasonCalendar,
+            vctcareer_backend::calendar::CareerMatch,
+            vctcareer_backend::calendar::WeekReport,
             crate::models::Team,
             crate::models::CreateSimulationRequest,
             crate::models::CreateSimulationResponse,
@@ -581,9 +1511,13 @@ async fn main() -> std::io::Result<()> {
             crate::models::AdvanceSimulationRequest,
             crate::models::SimulationControlRequest,
             crate::models::EventFilterRequest,
+            crate::models::EventStreamQuery,
             crate::offers::OfferRequest,
             crate::offers::Offer,
             vctcareer_backend::simulation_manager::LiveStats,
+            vctcareer_backend::win_probability::WinProbabilityEstimate,
+            vctcareer_backend::win_probability::WinProbabilitySample,
+            vctcareer_backend::win_probability::RoundWinProbability,
             vctcareer_backend::simulation_manager::PlayerPerformance,
             vctcareer_backend::simulation_manager::Scoreboard,
             vctcareer_backend::simulation_manager::MatchScore,
@@ -591,7 +1525,35 @@ async fn main() -> std::io::Result<()> {
             vctcareer_backend::simulation_manager::PlayerRanking,
             vctcareer_backend::simulation_manager::EconomyStatus,
             vctcareer_backend::simulation_manager::SimulationCheckpoint,
+            vctcareer_backend::simulation_manager::StreamedEvent,
             vctcareer_backend::sim::SimulationState,
+            vctcareer_backend::maps::Map,
+            vctcareer_backend::maps::Site,
+            vctcareer_backend::economy::BuyType,
+            vctcareer_backend::series::CreateSeriesRequest,
+            vctcareer_backend::series::SeriesFormat,
+            vctcareer_backend::series::SeriesTeam,
+            vctcareer_backend::series::VetoAction,
+            vctcareer_backend::series::VetoStep,
+            vctcareer_backend::series::SeriesPlayer,
+            vctcareer_backend::series::MapResult,
+            vctcareer_backend::series::SeriesResult,
+            vctcareer_backend::series::SeriesMapScore,
+            vctcareer_backend::series::SeriesPlayerStats,
+            vctcareer_backend::series::SeriesScoreboard,
+            vctcareer_backend::persistence::MatchSummary,
+            vctcareer_backend::persistence::MatchPlayerStats,
+            vctcareer_backend::persistence::MatchDetail,
+            vctcareer_backend::sim::MatchTeams,
+            vctcareer_backend::batch::BatchSimulationRequest,
+            vctcareer_backend::batch::BatchResult,
+            vctcareer_backend::batch::WinProbability,
+            vctcareer_backend::batch::ScoreFrequency,
+            vctcareer_backend::batch::StatDistribution,
+            vctcareer_backend::batch::BatchPlayerStats,
+            vctcareer_backend::sim::OvertimeSettings,
+            vctcareer_backend::sim::TiebreakMode,
+            vctcareer_backend::economy::WeaponDrop,
             vctcareer_backend::sim::PlayerStats,
             vctcareer_backend::sim::GameEvent,
             vctcareer_backend::sim::Team,
@@ -616,6 +1578,37 @@ async fn main() -> std::io::Result<()> {
     let mgr = Manager::new(config, NoTls);
     let pool = Pool::builder(mgr).max_size(16).build().unwrap();
     let simulation_manager = create_simulation_manager();
+    let series_manager = create_series_manager();
+
+    // Bring the schema up to date and pick up matches that were still running
+    // when the server last stopped
+    match persistence::run_migrations(&pool).await {
+        Ok(applied) if !applied.is_empty() => println!("Applied migrations {:?}", applied),
+        Ok(_) => {}
+        Err(e) => eprintln!("Failed to run migrations: {}", e),
+    }
+    match persistence::restore_simulations(&pool, &simulation_manager).await {
+        Ok(report) => {
+            println!("Restored {} in-progress simulations", report.restored.len());
+            for failure in &report.failed {
+                eprintln!(
+                    "Failed to restore simulation {}: {}",
+                    failure.id, failure.error
+                );
+            }
+            if !report.failed.is_empty() {
+                eprintln!(
+                    "{} in-progress simulations could not be restored; they will be retried on the next start",
+                    report.failed.len()
+                );
+            }
+        }
+        Err(e) => eprintln!("Failed to restore simulations: {}", e),
+    }
+    actix_web::rt::spawn(persistence::run_sync_loop(
+        pool.clone(),
+        simulation_manager.clone(),
+    ));
     println!("Starting server at http://127.0.0.1:8080");
     HttpServer::new(move || {
         let cors = Cors::permissive();
@@ -623,21 +1616,48 @@ async fn main() -> std::io::Result<()> {
             .wrap(cors)
             .service(index)
             .service(create_career)
+            .service(preview_placement)
+            .service(create_career_save)
+            .service(get_career)
+            .service(update_career)
+            .service(delete_career)
+            .service(set_training_focus)
+            .service(get_career_calendar)
+            .service(advance_career_week)
+            .service(create_contract_offers)
+            .service(list_contract_offers)
+            .service(counter_contract_offer)
+            .service(accept_contract_offer)
+            .service(decline_contract_offer)
             .service(get_teams)
             .service(generate_offers)
             .service(estimate_rr)
             .service(random_map)
+            .service(list_patch_versions)
+            .service(create_series)
+            .service(get_series)
+            .service(get_series_scoreboard)
+            .service(find_matches)
+            .service(get_match)
+            .service(get_match_events)
+            .service(simulate_batch)
             .service(create_simulation)
             .service(get_simulation_state)
             .service(advance_simulation)
             .service(control_simulation)
             .service(get_simulation_events)
+            .service(stream_simulation_events)
             .service(get_simulation_stats)
             .service(get_live_stats)
+            .service(get_win_probability_timeline)
             .service(get_scoreboard)
             .service(get_economy_status)
             .service(create_checkpoint)
+            .service(list_checkpoints)
+            .service(restore_checkpoint)
             .service(get_events_at_timestamp)
+            .service(rewind_to_round)
+            .service(replay_from)
             .into_utoipa_app()
             .openapi(ApiDoc::openapi())
             .openapi_service(|api| {
@@ -645,6 +1665,7 @@ async fn main() -> std::io::Result<()> {
             })
             .app_data(web::Data::new(pool.clone()))
             .app_data(web::Data::new(simulation_manager.clone()))
+            .app_data(web::Data::new(series_manager.clone()))
             .into_app()
     })
     .bind(("127.0.0.1", 8080))?
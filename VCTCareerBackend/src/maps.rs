@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::sim::Timestamp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum Map {
+    Ascent,
+    Bind,
+    Haven,
+    Split,
+    Icebox,
+    Breeze,
+    Fracture,
+    Pearl,
+    Lotus,
+    Sunset,
+    Abyss,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum Site {
+    A,
+    B,
+    C,
+}
+
+/// Where a player is during a round. Attackers walk from spawn to the site
+/// they execute on; defenders hold a site and rotate when another one is hit.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, ToSchema)]
+pub enum Position {
+    #[default]
+    Spawn,
+    Moving {
+        site: Site,
+        arrive_at: Timestamp,
+    },
+    AtSite(Site),
+}
+
+impl Position {
+    /// The site the player is standing on, if any.
+    pub fn site(&self) -> Option<Site> {
+        match self {
+            Position::AtSite(site) => Some(*site),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SiteLayout {
+    pub site: Site,
+    pub attacker_travel_ms: u64,   // attacker spawn to the site
+    pub rotate_ms: u64,            // defenders rotating in from another site
+    pub choke_range_m: (f32, f32), // engagement distances around the site entrance
+    pub defender_advantage: f32,   // duel win chance defenders get for holding the site
+}
+
+const fn site(
+    site: Site,
+    attacker_travel_ms: u64,
+    rotate_ms: u64,
+    choke_range_m: (f32, f32),
+    defender_advantage: f32,
+) -> SiteLayout {
+    SiteLayout {
+        site,
+        attacker_travel_ms,
+        rotate_ms,
+        choke_range_m,
+        defender_advantage,
+    }
+}
+
+const ASCENT: &[SiteLayout] = &[
+    site(Site::A, 22_000, 14_000, (15.0, 35.0), 0.05),
+    site(Site::B, 20_000, 14_000, (10.0, 30.0), 0.04),
+];
+const BIND: &[SiteLayout] = &[
+    site(Site::A, 20_000, 12_000, (8.0, 25.0), 0.05),
+    site(Site::B, 22_000, 12_000, (10.0, 30.0), 0.04),
+];
+const HAVEN: &[SiteLayout] = &[
+    site(Site::A, 21_000, 13_000, (10.0, 30.0), 0.04),
+    site(Site::B, 18_000, 10_000, (8.0, 25.0), 0.03),
+    site(Site::C, 23_000, 15_000, (15.0, 40.0), 0.05),
+];
+const SPLIT: &[SiteLayout] = &[
+    site(Site::A, 21_000, 15_000, (15.0, 35.0), 0.07),
+    site(Site::B, 19_000, 15_000, (10.0, 30.0), 0.06),
+];
+const ICEBOX: &[SiteLayout] = &[
+    site(Site::A, 22_000, 16_000, (15.0, 45.0), 0.04),
+    site(Site::B, 21_000, 16_000, (15.0, 40.0), 0.05),
+];
+const BREEZE: &[SiteLayout] = &[
+    site(Site::A, 24_000, 20_000, (20.0, 50.0), 0.04),
+    site(Site::B, 22_000, 20_000, (20.0, 45.0), 0.05),
+];
+const FRACTURE: &[SiteLayout] = &[
+    site(Site::A, 18_000, 20_000, (10.0, 35.0), 0.03),
+    site(Site::B, 18_000, 20_000, (10.0, 35.0), 0.03),
+];
+const PEARL: &[SiteLayout] = &[
+    site(Site::A, 22_000, 16_000, (15.0, 40.0), 0.05),
+    site(Site::B, 23_000, 16_000, (15.0, 40.0), 0.05),
+];
+const LOTUS: &[SiteLayout] = &[
+    site(Site::A, 20_000, 12_000, (10.0, 30.0), 0.04),
+    site(Site::B, 18_000, 10_000, (8.0, 25.0), 0.03),
+    site(Site::C, 21_000, 12_000, (10.0, 30.0), 0.04),
+];
+const SUNSET: &[SiteLayout] = &[
+    site(Site::A, 20_000, 14_000, (10.0, 35.0), 0.04),
+    site(Site::B, 20_000, 14_000, (10.0, 30.0), 0.05),
+];
+const ABYSS: &[SiteLayout] = &[
+    site(Site::A, 21_000, 15_000, (15.0, 35.0), 0.04),
+    site(Site::B, 21_000, 15_000, (15.0, 35.0), 0.04),
+];
+
+impl Map {
+    pub fn all() -> [Map; 11] {
+        [
+            Map::Ascent,
+            Map::Bind,
+            Map::Haven,
+            Map::Split,
+            Map::Icebox,
+            Map::Breeze,
+            Map::Fracture,
+            Map::Pearl,
+            Map::Lotus,
+            Map::Sunset,
+            Map::Abyss,
+        ]
+    }
+
+    pub fn sites(&self) -> &'static [SiteLayout] {
+        match self {
+            Map::Ascent => ASCENT,
+            Map::Bind => BIND,
+            Map::Haven => HAVEN,
+            Map::Split => SPLIT,
+            Map::Icebox => ICEBOX,
+            Map::Breeze => BREEZE,
+            Map::Fracture => FRACTURE,
+            Map::Pearl => PEARL,
+            Map::Lotus => LOTUS,
+            Map::Sunset => SUNSET,
+            Map::Abyss => ABYSS,
+        }
+    }
+
+    pub fn site(&self, site: Site) -> Option<&'static SiteLayout> {
+        self.sites().iter().find(|layout| layout.site == site)
+    }
+}
+
+pub fn parse_map(map_str: &str) -> Result<Map, String> {
+    Map::all()
+        .into_iter()
+        .find(|map| format!("{:?}", map).eq_ignore_ascii_case(map_str))
+        .ok_or_else(|| format!("Unknown map: {}", map_str))
+}
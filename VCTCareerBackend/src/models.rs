@@ -12,25 +12,6 @@ pub struct CareerInfo {
     pub division: String,
 }
 
-#[derive(Serialize)]
-pub enum StartingTier {
-    RankedPlay,
-    Tier3,
-    Tier2,
-    Tier1,
-}
-
-impl StartingTier {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            StartingTier::RankedPlay => "Ranked Play",
-            StartingTier::Tier3 => "Tier 3 (College / Premier)",
-            StartingTier::Tier2 => "Tier 2 (College / Challengers)",
-            StartingTier::Tier1 => "Tier 1 (VCT)",
-        }
-    }
-}
-
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct Team {
     pub team_name: String,
@@ -45,6 +26,13 @@ pub struct Team {
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct CreateSimulationRequest {
     pub players: Vec<SimulationPlayer>,
+    pub seed: Option<u64>,             // Omit for a random seed
+    pub patch_version: Option<String>, // Omit for the baseline balance patch
+    pub map: Option<String>,           // Omit to play on Ascent
+    pub tiebreak: Option<String>,      // "Tournament" (default) or "Ranked"
+    pub sudden_death: Option<bool>,    // Omit for no sudden death in overtime
+    pub attacker_team: Option<String>, // Name of the roster starting on attack
+    pub defender_team: Option<String>, // Name of the roster starting on defense
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
@@ -73,7 +61,7 @@ pub struct AdvanceSimulationRequest {
 
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct SimulationControlRequest {
-    pub action: String, // "pause", "resume", "set_speed"
+    pub action: String, // "play", "pause", "resume", "set_speed"
     pub speed: Option<f32>,
 }
 
@@ -85,3 +73,10 @@ pub struct EventFilterRequest {
     pub start_timestamp: Option<u64>,
     pub end_timestamp: Option<u64>,
 }
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct EventStreamQuery {
+    pub event_types: Option<String>, // Comma-separated, e.g. "Kill,SpikePlant"
+    pub player_ids: Option<String>,  // Comma-separated, e.g. "1,6"
+    pub from: Option<usize>,         // Replay the log from this event index before going live
+}
@@ -0,0 +1,698 @@
+use deadpool_postgres::Pool;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::balance::{BalanceConfig, DEFAULT_PATCH_VERSION};
+use crate::maps::{Map, parse_map};
+use crate::series::SeriesTeam;
+use crate::sim::{
+    GameEvent, MatchTeams, OvertimeSettings, Player, PlayerStats, SimulationMode, SimulationPhase,
+    Team, ValorantSimulation,
+};
+use crate::simulation_manager::{SimulationManager, safe_lock};
+
+/// Schema migrations in the order they apply. Never edit one that has
+/// shipped; add a new one instead.
+pub const MIGRATIONS: &[(i32, &str, &str)] = &[
+    (
+        1,
+        "create_simulations",
+        include_str!("../migrations/0001_create_simulations.sql"),
+    ),
+    (
+        2,
+        "create_careers",
+        include_str!("../migrations/0002_create_careers.sql"),
+    ),
+    (
+        3,
+        "create_contract_offers",
+        include_str!("../migrations/0003_create_contract_offers.sql"),
+    ),
+];
+
+/// How often in-memory simulations are written back to Postgres.
+pub const SYNC_INTERVAL_SECS: u64 = 5;
+
+// Upper bound on ticks replayed to restore a simulation, as in `replay_from`
+const MAX_RESTORE_TICKS: u64 = 50000;
+
+/// Applies every migration not yet recorded in `schema_migrations`, each in
+/// its own transaction.
+pub async fn run_migrations(pool: &Pool) -> Result<Vec<i32>, String> {
+    let mut client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    client
+        .batch_execute(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+            )",
+        )
+        .await
+        .map_err(|e| format!("Failed to create schema_migrations: {}", e))?;
+
+    let applied: Vec<i32> = client
+        .query("SELECT version FROM schema_migrations", &[])
+        .await
+        .map_err(|e| format!("Failed to read schema_migrations: {}", e))?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    let mut newly_applied = Vec::new();
+    for (version, name, sql) in MIGRATIONS {
+        if applied.contains(version) {
+            continue;
+        }
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| format!("Failed to start migration {}: {}", version, e))?;
+        transaction
+            .batch_execute(sql)
+            .await
+            .map_err(|e| format!("Migration {} ({}) failed: {}", version, name, e))?;
+        transaction
+            .execute(
+                "INSERT INTO schema_migrations (version, name) VALUES ($1, $2)",
+                &[version, name],
+            )
+            .await
+            .map_err(|e| format!("Failed to record migration {}: {}", version, e))?;
+        transaction
+            .commit()
+            .await
+            .map_err(|e| format!("Failed to commit migration {}: {}", version, e))?;
+        newly_applied.push(*version);
+    }
+    Ok(newly_applied)
+}
+
+/// Everything needed to store a simulation and rebuild it later. A match is
+/// fully determined by its setup and how many ticks it has run, so that is
+/// what gets stored rather than the live state.
+#[derive(Debug, Clone)]
+pub struct SimulationRecord {
+    pub id: Uuid,
+    pub seed: u64,
+    pub map: Map,
+    pub patch_version: String,
+    pub overtime: OvertimeSettings,
+    pub teams: MatchTeams,
+    pub roster: Vec<Player>,
+    pub tick_count: u64,
+    pub team_a_score: u8,
+    pub team_b_score: u8,
+    pub completed: bool,
+    pub winner: Option<SeriesTeam>, // None until decided, and for a draw
+}
+
+impl SimulationRecord {
+    pub fn capture(sim: &ValorantSimulation) -> Self {
+        let state = &sim.state;
+        let swapped = sim.sides_swapped();
+        let (team_a_score, team_b_score) = if swapped {
+            (state.defender_score, state.attacker_score)
+        } else {
+            (state.attacker_score, state.defender_score)
+        };
+        let (completed, winner) = match &state.phase {
+            SimulationPhase::MatchEnd { winner, .. } => {
+                (true, winner.as_ref().map(|side| series_team(side, swapped)))
+            }
+            _ => (false, None),
+        };
+
+        SimulationRecord {
+            id: state.id,
+            seed: state.seed,
+            map: state.map,
+            patch_version: state.patch_version.clone(),
+            overtime: state.overtime.clone(),
+            teams: state.teams.clone(),
+            roster: starting_roster(sim),
+            tick_count: state.tick_count,
+            team_a_score,
+            team_b_score,
+            completed,
+            winner,
+        }
+    }
+}
+
+/// Team A started on attack; `swapped` says whether sides have changed since.
+fn series_team(side: &Team, swapped: bool) -> SeriesTeam {
+    match (side, swapped) {
+        (Team::Attackers, false) | (Team::Defenders, true) => SeriesTeam::TeamA,
+        _ => SeriesTeam::TeamB,
+    }
+}
+
+/// The players as they were before the first tick, on the sides they started on.
+pub fn starting_roster(sim: &ValorantSimulation) -> Vec<Player> {
+    let swapped = sim.sides_swapped();
+    let mut ids: Vec<u32> = sim.players.keys().copied().collect();
+    ids.sort();
+    ids.into_iter()
+        .map(|id| {
+            let player = &sim.players[&id];
+            let team = match (&player.team, swapped) {
+                (Team::Attackers, false) | (Team::Defenders, true) => Team::Attackers,
+                _ => Team::Defenders,
+            };
+            Player::new(
+                player.id,
+                player.name.clone(),
+                player.agent.clone(),
+                team,
+                player.skills().clone(),
+            )
+        })
+        .collect()
+}
+
+/// Replays a stored simulation up to the tick it had reached. The result is
+/// paused, since nothing is driving its playback any more; checkpoints made
+/// by hand are not kept.
+pub fn rebuild_simulation(record: &SimulationRecord) -> Result<ValorantSimulation, String> {
+    // The default patch is compiled in, so it restores wherever the server runs
+    let balance = if record.patch_version == DEFAULT_PATCH_VERSION {
+        BalanceConfig::default()
+    } else {
+        BalanceConfig::load(&record.patch_version)?
+    };
+    let mut sim = ValorantSimulation::with_balance(record.seed, balance);
+    sim.state.id = record.id;
+    sim.set_map(record.map)?;
+    sim.set_overtime_settings(record.overtime.clone())?;
+    sim.state.teams = record.teams.clone();
+    for player in &record.roster {
+        sim.add_player(player.clone());
+    }
+
+    if record.tick_count > MAX_RESTORE_TICKS {
+        return Err(format!(
+            "Simulation {} is too far along to restore ({} ticks)",
+            record.id, record.tick_count
+        ));
+    }
+    while sim.state.tick_count < record.tick_count
+        && !matches!(sim.state.phase, SimulationPhase::MatchEnd { .. })
+    {
+        sim.advance_tick()?;
+    }
+    sim.state.mode = SimulationMode::Paused;
+    Ok(sim)
+}
+
+/// Writes a simulation's row, the events from `events_from` on and, once the
+/// match is over, its final player stats. `new_events` is only that tail of
+/// the log, so `events_from + new_events.len()` is its full length.
+pub async fn save_simulation(
+    pool: &Pool,
+    record: &SimulationRecord,
+    new_events: &[GameEvent],
+    events_from: usize,
+    player_stats: &[PlayerStats],
+) -> Result<(), String> {
+    let mut client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    let transaction = client
+        .transaction()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let db_err = |e: tokio_postgres::Error| format!("Failed to save simulation: {}", e);
+
+    let id = record.id.to_string();
+    let overtime = serde_json::to_string(&record.overtime).map_err(|e| e.to_string())?;
+    let roster = serde_json::to_string(&record.roster).map_err(|e| e.to_string())?;
+    let map = format!("{:?}", record.map);
+    let status = if record.completed {
+        "completed"
+    } else {
+        "in_progress"
+    };
+    let winner = record.winner.map(|team| format!("{:?}", team));
+    transaction
+        .execute(
+            "INSERT INTO simulations (id, seed, map, patch_version, overtime, roster, team_a,
+                team_b, status, tick_count, team_a_score, team_b_score, winner, completed_at)
+             VALUES ($1, $2, $3, $4, $5::text::jsonb, $6::text::jsonb, $7, $8, $9, $10, $11,
+                $12, $13, CASE WHEN $9 = 'completed' THEN now() END)
+             ON CONFLICT (id) DO UPDATE SET
+                status = EXCLUDED.status,
+                tick_count = EXCLUDED.tick_count,
+                team_a_score = EXCLUDED.team_a_score,
+                team_b_score = EXCLUDED.team_b_score,
+                winner = EXCLUDED.winner,
+                updated_at = now(),
+                completed_at = CASE WHEN EXCLUDED.status = 'completed'
+                    THEN COALESCE(simulations.completed_at, now()) END",
+            &[
+                &id,
+                &(record.seed as i64),
+                &map,
+                &record.patch_version,
+                &overtime,
+                &roster,
+                &record.teams.starting_attackers,
+                &record.teams.starting_defenders,
+                &status,
+                &(record.tick_count as i64),
+                &(record.team_a_score as i16),
+                &(record.team_b_score as i16),
+                &winner,
+            ],
+        )
+        .await
+        .map_err(db_err)?;
+
+    // A rewind shortens the log; drop what it undid
+    transaction
+        .execute(
+            "DELETE FROM simulation_events WHERE simulation_id = $1 AND seq >= $2",
+            &[&id, &((events_from + new_events.len()) as i32)],
+        )
+        .await
+        .map_err(db_err)?;
+    let insert_event = transaction
+        .prepare(
+            "INSERT INTO simulation_events (simulation_id, seq, event_type, timestamp_ms, payload)
+             VALUES ($1, $2, $3, $4, $5::text::jsonb)
+             ON CONFLICT (simulation_id, seq) DO NOTHING",
+        )
+        .await
+        .map_err(db_err)?;
+    for (seq, event) in (events_from..).zip(new_events) {
+        let payload = serde_json::to_string(event).map_err(|e| e.to_string())?;
+        transaction
+            .execute(
+                &insert_event,
+                &[
+                    &id,
+                    &(seq as i32),
+                    &event.event_type(),
+                    &(event.timestamp() as i64),
+                    &payload,
+                ],
+            )
+            .await
+            .map_err(db_err)?;
+    }
+
+    if record.completed {
+        transaction
+            .execute(
+                "DELETE FROM simulation_player_stats WHERE simulation_id = $1",
+                &[&id],
+            )
+            .await
+            .map_err(db_err)?;
+        for stats in player_stats {
+            let Some(player) = record.roster.iter().find(|p| p.id == stats.player_id) else {
+                continue;
+            };
+            let team = format!("{:?}", series_team(&player.team, false));
+            transaction
+                .execute(
+                    "INSERT INTO simulation_player_stats (simulation_id, player_id, name, agent,
+                        team, kills, deaths, assists, damage_dealt, headshot_percentage)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+                    &[
+                        &id,
+                        &(stats.player_id as i32),
+                        &player.name,
+                        &format!("{:?}", player.agent),
+                        &team,
+                        &(stats.kills as i32),
+                        &(stats.deaths as i32),
+                        &(stats.assists as i32),
+                        &(stats.damage_dealt as i32),
+                        &stats.headshot_percentage,
+                    ],
+                )
+                .await
+                .map_err(db_err)?;
+        }
+    }
+
+    transaction
+        .commit()
+        .await
+        .map_err(|e| format!("Failed to commit simulation: {}", e))
+}
+
+/// How much of a simulation has already been written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncedState {
+    pub events: usize,
+    pub tick_count: u64,
+}
+
+/// Writes every simulation that has moved on since the last sync. Snapshots
+/// are taken under the manager lock and written after releasing it. Returns
+/// the simulations that couldn't be written; they are tried again next time.
+pub async fn sync_simulations(
+    pool: &Pool,
+    manager: &SimulationManager,
+    synced: &mut HashMap<Uuid, SyncedState>,
+) -> Result<Vec<(Uuid, String)>, String> {
+    let pending: Vec<_> = {
+        let simulations = safe_lock(manager)?;
+        simulations
+            .values()
+            .filter_map(|sim| {
+                let current = SyncedState {
+                    events: sim.events.len(),
+                    tick_count: sim.state.tick_count,
+                };
+                let previous = synced.get(&sim.state.id).copied();
+                if previous == Some(current) {
+                    return None;
+                }
+                let events_from = previous.map_or(0, |p| p.events.min(current.events));
+                Some((
+                    SimulationRecord::capture(sim),
+                    // Earlier events are already written, so only copy the tail
+                    sim.events[events_from..].to_vec(),
+                    events_from,
+                    sim.get_player_stats(),
+                    current,
+                ))
+            })
+            .collect()
+    };
+
+    let mut failed = Vec::new();
+    for (record, events, events_from, player_stats, current) in pending {
+        match save_simulation(pool, &record, &events, events_from, &player_stats).await {
+            Ok(()) => {
+                synced.insert(record.id, current);
+            }
+            Err(error) => failed.push((record.id, error)),
+        }
+    }
+    Ok(failed)
+}
+
+/// Keeps Postgres in step with the simulation manager until the server stops.
+pub async fn run_sync_loop(pool: Pool, manager: SimulationManager) {
+    let mut synced = HashMap::new();
+    let mut interval = tokio::time::interval(Duration::from_secs(SYNC_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        match sync_simulations(&pool, &manager, &mut synced).await {
+            Ok(failed) => {
+                for (id, error) in failed {
+                    eprintln!("Failed to sync simulation {}: {}", id, error);
+                }
+            }
+            Err(e) => eprintln!("Failed to sync simulations: {}", e),
+        }
+    }
+}
+
+/// A simulation that was in progress but couldn't be rebuilt. It stays in
+/// progress in the database, so the next start tries it again.
+#[derive(Debug, Clone)]
+pub struct RestoreFailure {
+    pub id: String, // as stored, since a broken record may not have a valid one
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RestoreReport {
+    pub restored: Vec<Uuid>,
+    pub failed: Vec<RestoreFailure>,
+}
+
+/// Rebuilds every simulation that was still in progress and puts it back in
+/// the manager.
+pub async fn restore_simulations(
+    pool: &Pool,
+    manager: &SimulationManager,
+) -> Result<RestoreReport, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    let rows = client
+        .query(
+            "SELECT id, seed, map, patch_version, overtime::text, roster::text, team_a, team_b,
+                tick_count
+             FROM simulations WHERE status = 'in_progress'",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("Failed to load simulations: {}", e))?;
+
+    let mut report = RestoreReport::default();
+    for row in rows {
+        // One broken record shouldn't keep the others from coming back
+        match in_progress_record(&row).and_then(|record| rebuild_simulation(&record)) {
+            Ok(sim) => {
+                let id = sim.state.id;
+                safe_lock(manager)?.insert(id, sim);
+                report.restored.push(id);
+            }
+            Err(error) => report.failed.push(RestoreFailure {
+                id: row.get(0),
+                error,
+            }),
+        }
+    }
+    Ok(report)
+}
+
+// A row from restore_simulations' query
+fn in_progress_record(row: &tokio_postgres::Row) -> Result<SimulationRecord, String> {
+    let id: String = row.get(0);
+    Ok(SimulationRecord {
+        id: Uuid::parse_str(&id).map_err(|_| format!("Invalid simulation ID: {}", id))?,
+        seed: row.get::<_, i64>(1) as u64,
+        map: parse_map(row.get(2))?,
+        patch_version: row.get(3),
+        overtime: serde_json::from_str(row.get(4))
+            .map_err(|e| format!("Unreadable overtime settings: {}", e))?,
+        roster: serde_json::from_str(row.get(5))
+            .map_err(|e| format!("Unreadable roster: {}", e))?,
+        teams: MatchTeams {
+            starting_attackers: row.get(6),
+            starting_defenders: row.get(7),
+        },
+        tick_count: row.get::<_, i64>(8) as u64,
+        team_a_score: 0,
+        team_b_score: 0,
+        completed: false,
+        winner: None,
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct MatchQuery {
+    pub player_id: Option<u32>,
+    pub player_name: Option<String>, // case-insensitive
+    pub team: Option<String>,        // roster name on either side
+    pub from: Option<String>,        // completed at or after, e.g. "2025-06-01"
+    pub to: Option<String>,          // completed before
+    pub limit: Option<i64>,          // defaults to 50, at most 500
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MatchSummary {
+    pub id: String,
+    pub map: String,
+    pub seed: u64,
+    pub patch_version: String,
+    pub team_a: Option<String>, // started on attack
+    pub team_b: Option<String>,
+    pub team_a_score: u8,
+    pub team_b_score: u8,
+    pub winner: Option<SeriesTeam>, // None for a draw
+    pub completed_at: String,       // RFC 3339, UTC
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MatchPlayerStats {
+    pub player_id: u32,
+    pub name: String,
+    pub agent: String,
+    pub team: SeriesTeam,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub damage_dealt: u32,
+    pub headshot_percentage: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MatchDetail {
+    pub summary: MatchSummary,
+    pub players: Vec<MatchPlayerStats>,
+}
+
+const MATCH_SUMMARY_COLUMNS: &str = "s.id, s.map, s.seed, s.patch_version, s.team_a, s.team_b,
+    s.team_a_score, s.team_b_score, s.winner,
+    to_char(s.completed_at AT TIME ZONE 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"')";
+
+fn parse_series_team(team: &str) -> Option<SeriesTeam> {
+    match team {
+        "TeamA" => Some(SeriesTeam::TeamA),
+        "TeamB" => Some(SeriesTeam::TeamB),
+        _ => None,
+    }
+}
+
+fn match_summary(row: &tokio_postgres::Row) -> MatchSummary {
+    MatchSummary {
+        id: row.get(0),
+        map: row.get(1),
+        seed: row.get::<_, i64>(2) as u64,
+        patch_version: row.get(3),
+        team_a: row.get(4),
+        team_b: row.get(5),
+        team_a_score: row.get::<_, i16>(6) as u8,
+        team_b_score: row.get::<_, i16>(7) as u8,
+        winner: row
+            .get::<_, Option<String>>(8)
+            .as_deref()
+            .and_then(parse_series_team),
+        completed_at: row.get(9),
+    }
+}
+
+/// Completed matches matching every filter given, most recent first.
+pub async fn find_matches(pool: &Pool, query: &MatchQuery) -> Result<Vec<MatchSummary>, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+
+    let mut conditions = vec!["s.status = 'completed'".to_string()];
+    let mut params: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> = Vec::new();
+    let player_id = query.player_id.map(|id| id as i32);
+    if let Some(ref player_id) = player_id {
+        params.push(player_id);
+        conditions.push(format!(
+            "EXISTS (SELECT 1 FROM simulation_player_stats p
+                WHERE p.simulation_id = s.id AND p.player_id = ${})",
+            params.len()
+        ));
+    }
+    if let Some(ref name) = query.player_name {
+        params.push(name);
+        conditions.push(format!(
+            "EXISTS (SELECT 1 FROM simulation_player_stats p
+                WHERE p.simulation_id = s.id AND lower(p.name) = lower(${}))",
+            params.len()
+        ));
+    }
+    if let Some(ref team) = query.team {
+        params.push(team);
+        conditions.push(format!(
+            "(s.team_a = ${0} OR s.team_b = ${0})",
+            params.len()
+        ));
+    }
+    if let Some(ref from) = query.from {
+        params.push(from);
+        conditions.push(format!(
+            "s.completed_at >= ${}::text::timestamptz",
+            params.len()
+        ));
+    }
+    if let Some(ref to) = query.to {
+        params.push(to);
+        conditions.push(format!(
+            "s.completed_at < ${}::text::timestamptz",
+            params.len()
+        ));
+    }
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    params.push(&limit);
+
+    let query_str = format!(
+        "SELECT {} FROM simulations s WHERE {} ORDER BY s.completed_at DESC LIMIT ${}",
+        MATCH_SUMMARY_COLUMNS,
+        conditions.join(" AND "),
+        params.len()
+    );
+    let rows = client
+        .query(&query_str, &params)
+        .await
+        .map_err(|e| format!("Failed to query matches: {}", e))?;
+    Ok(rows.iter().map(match_summary).collect())
+}
+
+pub async fn get_match(pool: &Pool, match_id: &str) -> Result<MatchDetail, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    let summary_row = client
+        .query_opt(
+            &format!(
+                "SELECT {} FROM simulations s WHERE s.id = $1 AND s.status = 'completed'",
+                MATCH_SUMMARY_COLUMNS
+            ),
+            &[&match_id],
+        )
+        .await
+        .map_err(|e| format!("Failed to load match: {}", e))?
+        .ok_or("Match not found")?;
+
+    let players = client
+        .query(
+            "SELECT player_id, name, agent, team, kills, deaths, assists, damage_dealt,
+                headshot_percentage
+             FROM simulation_player_stats WHERE simulation_id = $1 ORDER BY player_id",
+            &[&match_id],
+        )
+        .await
+        .map_err(|e| format!("Failed to load match stats: {}", e))?
+        .iter()
+        .map(|row| MatchPlayerStats {
+            player_id: row.get::<_, i32>(0) as u32,
+            name: row.get(1),
+            agent: row.get(2),
+            team: parse_series_team(row.get(3)).unwrap_or(SeriesTeam::TeamA),
+            kills: row.get::<_, i32>(4) as u32,
+            deaths: row.get::<_, i32>(5) as u32,
+            assists: row.get::<_, i32>(6) as u32,
+            damage_dealt: row.get::<_, i32>(7) as u32,
+            headshot_percentage: row.get(8),
+        })
+        .collect();
+
+    Ok(MatchDetail {
+        summary: match_summary(&summary_row),
+        players,
+    })
+}
+
+pub async fn get_match_events(pool: &Pool, match_id: &str) -> Result<Vec<GameEvent>, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("Failed to get DB client: {}", e))?;
+    let rows = client
+        .query(
+            "SELECT payload::text FROM simulation_events
+             WHERE simulation_id = $1 ORDER BY seq",
+            &[&match_id],
+        )
+        .await
+        .map_err(|e| format!("Failed to load match events: {}", e))?;
+    rows.iter()
+        .map(|row| serde_json::from_str(row.get(0)).map_err(|e| e.to_string()))
+        .collect()
+}
@@ -0,0 +1,402 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+use crate::models::CareerInfo;
+use crate::ranked::{RankTier, calculate_mmr_modifier};
+
+/// Where placement models are read from unless the `PLACEMENT_MODEL_DIR`
+/// environment variable says otherwise, like `DEFAULT_BALANCE_PATCH_DIR`.
+pub const DEFAULT_PLACEMENT_MODEL_DIR: &str = "src/placement_models";
+pub const DEFAULT_PLACEMENT_SEASON: &str = "2025";
+
+/// Directory holding one `<season>.json` placement model per season.
+pub fn placement_model_dir() -> String {
+    env::var("PLACEMENT_MODEL_DIR").unwrap_or_else(|_| DEFAULT_PLACEMENT_MODEL_DIR.to_string())
+}
+
+// Compiled in, like the baseline balance patch, so placement always works
+const DEFAULT_MODEL_JSON: &str = include_str!("placement_models/2025.json");
+
+// Regions without an entry of their own, or an alias to one, use this one
+const FALLBACK_REGION: &str = "other";
+
+/// Where placement starts a new career.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum StartingTier {
+    RankedPlay,
+    Tier3,
+    Tier2,
+    Tier1,
+}
+
+impl StartingTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StartingTier::RankedPlay => "Ranked Play",
+            StartingTier::Tier3 => "Tier 3 (College / Premier)",
+            StartingTier::Tier2 => "Tier 2 (College / Challengers)",
+            StartingTier::Tier1 => "Tier 1 (VCT)",
+        }
+    }
+}
+
+/// Scores a player needs to reach each tier.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TierThresholds {
+    pub tier3: f32,
+    pub tier2: f32,
+    pub tier1: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgeCurve {
+    pub peak_min: u32, // ages that get the bonus, inclusive
+    pub peak_max: u32,
+    pub peak_bonus: f32,
+    pub penalty_per_year: f32, // for each year outside the peak band
+    pub max_penalty: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionPlacement {
+    pub bonus: f32, // how much room the region's scene has for new players
+    #[serde(default)]
+    pub thresholds: Option<TierThresholds>, // overrides the season's
+}
+
+/// How a season places new careers. Every number is a contribution to one
+/// score, and the score against the thresholds decides the tier.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlacementModel {
+    pub season: String,
+    pub thresholds: TierThresholds,
+    pub spread: f32, // how soft the tier boundaries are; larger spreads the odds wider
+    pub rank_scores: HashMap<String, f32>, // by rank name, as `RankTier` parses them
+    pub rr_weight: f32, // for a full 100 RR into the rank
+    pub mmr_weight: f32, // for hidden MMR as far above the rank as it counts
+    pub experience_scores: HashMap<String, f32>,
+    pub experience_full_credit_rank: f32, // rank score below which experience counts pro rata
+    pub age: AgeCurve,
+    pub regions: HashMap<String, RegionPlacement>, // by lowercase region name
+    #[serde(default)]
+    pub region_aliases: HashMap<String, String>, // e.g. "na" -> "americas"
+}
+
+impl Default for PlacementModel {
+    fn default() -> Self {
+        Self::from_json(DEFAULT_MODEL_JSON).expect("built-in placement model is invalid")
+    }
+}
+
+impl PlacementModel {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let model: PlacementModel = serde_json::from_str(json)
+            .map_err(|e| format!("Failed to parse placement model: {}", e))?;
+        model.validate()?;
+        Ok(model)
+    }
+
+    /// Loads the placement model for a season from `placement_model_dir`.
+    pub fn load(season: &str) -> Result<Self, String> {
+        // Seasons become file names, so keep them from escaping the directory
+        if season.is_empty()
+            || !season
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+        {
+            return Err(format!("Invalid placement season: {}", season));
+        }
+
+        let path = format!("{}/{}.json", placement_model_dir(), season);
+        let json_str = fs::read_to_string(&path)
+            .map_err(|_| format!("No placement model for season {}", season))?;
+        let model = Self::from_json(&json_str)?;
+
+        if model.season != season {
+            return Err(format!(
+                "Placement model {} declares season {}",
+                path, model.season
+            ));
+        }
+        Ok(model)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let check = |thresholds: &TierThresholds, whose: &str| {
+            if thresholds.tier3 < thresholds.tier2 && thresholds.tier2 < thresholds.tier1 {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Placement model {} has {} thresholds out of order",
+                    self.season, whose
+                ))
+            }
+        };
+        check(&self.thresholds, "season")?;
+        for (name, region) in &self.regions {
+            if let Some(thresholds) = &region.thresholds {
+                check(thresholds, name)?;
+            }
+        }
+        if self.spread <= 0.0 {
+            return Err(format!(
+                "Placement model {} needs a positive spread",
+                self.season
+            ));
+        }
+        if !self.regions.contains_key(FALLBACK_REGION) {
+            return Err(format!(
+                "Placement model {} has no \"{}\" region",
+                self.season, FALLBACK_REGION
+            ));
+        }
+        if let Some((alias, target)) = self
+            .region_aliases
+            .iter()
+            .find(|(_, target)| !self.regions.contains_key(*target))
+        {
+            return Err(format!(
+                "Placement model {} aliases {} to unknown region {}",
+                self.season, alias, target
+            ));
+        }
+        Ok(())
+    }
+
+    /// The configured region `region` places in, and its settings.
+    fn region(&self, region: &str) -> (&str, &RegionPlacement) {
+        let key = region.trim().to_lowercase();
+        let key = self.region_aliases.get(&key).cloned().unwrap_or(key);
+        match self.regions.get_key_value(&key) {
+            Some((name, placement)) => (name, placement),
+            None => (FALLBACK_REGION, &self.regions[FALLBACK_REGION]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PlacementRequest {
+    pub age: u32,
+    pub current_rank: String,
+    pub past_experience: String, // "Tier 1", "Tier 2", "Tier 3" or "None"
+    pub region: String,          // e.g. "NA" or "EMEA"; unknown regions use the model's fallback
+    pub rr: Option<u32>,         // RR into the current rank, 0-100
+    pub hidden_mmr: Option<i32>, // as in the ranked RR estimate
+    pub season: Option<String>,  // Omit for the default season's model
+}
+
+impl From<&CareerInfo> for PlacementRequest {
+    fn from(info: &CareerInfo) -> Self {
+        PlacementRequest {
+            age: info.age,
+            current_rank: info.current_rank.clone(),
+            past_experience: info.past_experience.clone(),
+            region: info.division.clone(),
+            rr: None,
+            hidden_mmr: None,
+            season: None,
+        }
+    }
+}
+
+/// One input's share of the placement score.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PlacementFactor {
+    pub factor: String,
+    pub contribution: f32,
+    pub explanation: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TierProbability {
+    pub tier: StartingTier,
+    pub name: String,
+    pub probability: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PlacementResult {
+    pub tier: StartingTier,
+    pub tier_name: String,
+    pub season: String,
+    pub region: String, // the model region the player was placed in
+    pub score: f32,
+    pub thresholds: TierThresholds,
+    pub factors: Vec<PlacementFactor>,
+    pub probabilities: Vec<TierProbability>, // lowest tier first; they sum to 1
+}
+
+/// Places a player with `model`: each factor adds to a score, the score
+/// against the region's thresholds picks the tier, and how close it sits to
+/// each threshold gives the odds of every tier.
+pub fn place(
+    request: &PlacementRequest,
+    model: &PlacementModel,
+) -> Result<PlacementResult, String> {
+    let rank = RankTier::from_str(request.current_rank.trim())
+        .map_err(|_| format!("Unknown rank: {}", request.current_rank))?;
+    let (region_name, region) = model.region(&request.region);
+    let thresholds = region.thresholds.unwrap_or(model.thresholds);
+    let mut factors = Vec::new();
+
+    let rank_key = format!("{:?}", rank);
+    let rank_score = model.rank_scores.get(&rank_key).copied().unwrap_or(0.0);
+    factors.push(PlacementFactor {
+        factor: "Rank".to_string(),
+        contribution: rank_score,
+        explanation: format!("{} is worth {:.2}", rank_key, rank_score),
+    });
+
+    if let Some(rr) = request.rr {
+        let rr = rr.min(100);
+        let contribution = model.rr_weight * rr as f32 / 100.0;
+        factors.push(PlacementFactor {
+            factor: "RR".to_string(),
+            contribution,
+            explanation: format!("{} RR of the way to the next rank", rr),
+        });
+    }
+
+    if let Some(hidden_mmr) = request.hidden_mmr {
+        // The ranked estimate's modifier runs 0.8 to 1.2 around the rank's midpoint
+        let modifier = calculate_mmr_modifier(hidden_mmr, rank);
+        let contribution = model.mmr_weight * (modifier - 1.0) / 0.2;
+        factors.push(PlacementFactor {
+            factor: "Hidden MMR".to_string(),
+            contribution,
+            explanation: format!(
+                "Hidden MMR {} plays {} {}",
+                hidden_mmr,
+                if contribution >= 0.0 {
+                    "above"
+                } else {
+                    "below"
+                },
+                rank_key
+            ),
+        });
+    }
+
+    let experience_score = model
+        .experience_scores
+        .get(request.past_experience.trim())
+        .copied()
+        .unwrap_or(0.0);
+    let credit = (rank_score / model.experience_full_credit_rank).clamp(0.0, 1.0);
+    let contribution = experience_score * credit;
+    factors.push(PlacementFactor {
+        factor: "Experience".to_string(),
+        contribution,
+        explanation: if credit < 1.0 && experience_score > 0.0 {
+            format!(
+                "{} is worth {:.2}, counted at {:.0}% for a rank below {:.2}",
+                request.past_experience,
+                experience_score,
+                credit * 100.0,
+                model.experience_full_credit_rank
+            )
+        } else {
+            format!(
+                "{} is worth {:.2}",
+                request.past_experience, experience_score
+            )
+        },
+    });
+
+    let age = &model.age;
+    let years_outside = if request.age < age.peak_min {
+        age.peak_min - request.age
+    } else {
+        request.age.saturating_sub(age.peak_max)
+    };
+    let contribution = if years_outside == 0 {
+        age.peak_bonus
+    } else {
+        -(years_outside as f32 * age.penalty_per_year).min(age.max_penalty)
+    };
+    factors.push(PlacementFactor {
+        factor: "Age".to_string(),
+        contribution,
+        explanation: if years_outside == 0 {
+            format!(
+                "{} is inside the {}-{} band teams recruit from",
+                request.age, age.peak_min, age.peak_max
+            )
+        } else {
+            format!(
+                "{} is {} year(s) outside the {}-{} band",
+                request.age, years_outside, age.peak_min, age.peak_max
+            )
+        },
+    });
+
+    factors.push(PlacementFactor {
+        factor: "Region".to_string(),
+        contribution: region.bonus,
+        explanation: format!("Placed in {} from \"{}\"", region_name, request.region),
+    });
+
+    let score: f32 = factors.iter().map(|f| f.contribution).sum();
+    let tier = if score >= thresholds.tier1 {
+        StartingTier::Tier1
+    } else if score >= thresholds.tier2 {
+        StartingTier::Tier2
+    } else if score >= thresholds.tier3 {
+        StartingTier::Tier3
+    } else {
+        StartingTier::RankedPlay
+    };
+
+    Ok(PlacementResult {
+        tier,
+        tier_name: tier.as_str().to_string(),
+        season: model.season.clone(),
+        region: region_name.to_string(),
+        score,
+        thresholds,
+        factors,
+        probabilities: tier_probabilities(score, &thresholds, model.spread),
+    })
+}
+
+/// Ordered logistic odds: the chance of reaching a tier is a sigmoid of the
+/// distance past its threshold, and each tier gets what's left between it
+/// and the next one up.
+pub fn tier_probabilities(
+    score: f32,
+    thresholds: &TierThresholds,
+    spread: f32,
+) -> Vec<TierProbability> {
+    let reach = |threshold: f32| 1.0 / (1.0 + (-(score - threshold) / spread).exp());
+    let (tier3, tier2, tier1) = (
+        reach(thresholds.tier3),
+        reach(thresholds.tier2),
+        reach(thresholds.tier1),
+    );
+    [
+        (StartingTier::RankedPlay, 1.0 - tier3),
+        (StartingTier::Tier3, tier3 - tier2),
+        (StartingTier::Tier2, tier2 - tier1),
+        (StartingTier::Tier1, tier1),
+    ]
+    .into_iter()
+    .map(|(tier, probability)| TierProbability {
+        tier,
+        name: tier.as_str().to_string(),
+        probability,
+    })
+    .collect()
+}
+
+/// Places with the request's season model, or the default one.
+pub fn place_for_season(request: &PlacementRequest) -> Result<PlacementResult, String> {
+    let model = match request.season.as_deref() {
+        Some(season) => PlacementModel::load(season)?,
+        None => PlacementModel::default(),
+    };
+    place(request, &model)
+}
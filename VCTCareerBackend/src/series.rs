@@ -0,0 +1,434 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::balance::BalanceConfig;
+use crate::maps::{Map, parse_map};
+use crate::models::SimulationPlayer;
+use crate::sim::{Player, PlayerStats, SimulationPhase, Team, ValorantSimulation};
+use crate::simulation_manager::{build_player, safe_lock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum SeriesFormat {
+    Bo1,
+    Bo3,
+    Bo5,
+}
+
+impl SeriesFormat {
+    pub fn max_maps(&self) -> usize {
+        match self {
+            SeriesFormat::Bo1 => 1,
+            SeriesFormat::Bo3 => 3,
+            SeriesFormat::Bo5 => 5,
+        }
+    }
+
+    pub fn maps_to_win(&self) -> u8 {
+        (self.max_maps() / 2 + 1) as u8
+    }
+}
+
+pub fn parse_series_format(format_str: &str) -> Result<SeriesFormat, String> {
+    match format_str.to_ascii_uppercase().as_str() {
+        "BO1" => Ok(SeriesFormat::Bo1),
+        "BO3" => Ok(SeriesFormat::Bo3),
+        "BO5" => Ok(SeriesFormat::Bo5),
+        _ => Err(format!("Unknown series format: {}", format_str)),
+    }
+}
+
+/// The two rosters in a series. Team A is whoever was sent in as
+/// "Attackers"; it bans first and attacks first on the opening map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum SeriesTeam {
+    TeamA,
+    TeamB,
+}
+
+impl SeriesTeam {
+    fn other(&self) -> SeriesTeam {
+        match self {
+            SeriesTeam::TeamA => SeriesTeam::TeamB,
+            SeriesTeam::TeamB => SeriesTeam::TeamA,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum VetoAction {
+    Ban,
+    Pick,
+    Decider,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct VetoStep {
+    pub team: Option<SeriesTeam>, // None for the decider, which is what's left
+    pub action: VetoAction,
+    pub map: Map,
+}
+
+/// Runs a pick/ban veto over `pool`, alternating turns from team A. Each team
+/// bans once, the teams pick all but the last map, bans continue until one
+/// map is left and that map is the decider. The opening bans are skipped if
+/// the pool is too small for them.
+pub fn run_veto(
+    format: SeriesFormat,
+    pool: &[Map],
+    rng: &mut impl Rng,
+) -> Result<Vec<VetoStep>, String> {
+    let mut remaining: Vec<Map> = Vec::new();
+    for map in pool {
+        if !remaining.contains(map) {
+            remaining.push(*map);
+        }
+    }
+    if remaining.len() < format.max_maps() {
+        return Err(format!(
+            "A {:?} needs a map pool of at least {} maps",
+            format,
+            format.max_maps()
+        ));
+    }
+
+    let mut steps = Vec::new();
+    let mut turn = SeriesTeam::TeamA;
+    let mut take = |action: VetoAction, remaining: &mut Vec<Map>, turn: &mut SeriesTeam| {
+        let map = *remaining.choose(rng).expect("veto ran out of maps");
+        remaining.retain(|m| *m != map);
+        steps.push(VetoStep {
+            team: Some(*turn),
+            action,
+            map,
+        });
+        *turn = turn.other();
+    };
+
+    let picks = format.max_maps() - 1;
+    if picks > 0 && remaining.len() >= format.max_maps() + 2 {
+        take(VetoAction::Ban, &mut remaining, &mut turn);
+        take(VetoAction::Ban, &mut remaining, &mut turn);
+    }
+    for _ in 0..picks {
+        take(VetoAction::Pick, &mut remaining, &mut turn);
+    }
+    while remaining.len() > 1 {
+        take(VetoAction::Ban, &mut remaining, &mut turn);
+    }
+
+    steps.push(VetoStep {
+        team: None,
+        action: VetoAction::Decider,
+        map: remaining[0],
+    });
+    Ok(steps)
+}
+
+/// Maps in the order they are played: picks first, then the decider.
+pub fn veto_maps(veto: &[VetoStep]) -> Vec<(Map, Option<SeriesTeam>)> {
+    veto.iter()
+        .filter(|step| step.action != VetoAction::Ban)
+        .map(|step| (step.map, step.team))
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SeriesPlayer {
+    pub id: u32,
+    pub name: String,
+    pub team: SeriesTeam,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MapResult {
+    pub map: Map,
+    pub picked_by: Option<SeriesTeam>,
+    pub team_a_starting_side: Team,
+    pub seed: u64,
+    pub team_a_score: u8,
+    pub team_b_score: u8,
+    pub winner: SeriesTeam,
+    pub player_stats: Vec<PlayerStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SeriesResult {
+    pub id: String,
+    pub format: SeriesFormat,
+    pub seed: u64,
+    pub players: Vec<SeriesPlayer>,
+    pub veto: Vec<VetoStep>,
+    pub maps: Vec<MapResult>,
+    pub team_a_maps: u8,
+    pub team_b_maps: u8,
+    pub winner: SeriesTeam,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SeriesPlayerStats {
+    pub player_id: u32,
+    pub name: String,
+    pub team: SeriesTeam,
+    pub maps_played: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub damage_dealt: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SeriesMapScore {
+    pub map: Map,
+    pub team_a_score: u8,
+    pub team_b_score: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SeriesScoreboard {
+    pub format: SeriesFormat,
+    pub team_a_maps: u8,
+    pub team_b_maps: u8,
+    pub winner: SeriesTeam,
+    pub map_scores: Vec<SeriesMapScore>,
+    pub players: Vec<SeriesPlayerStats>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateSeriesRequest {
+    pub players: Vec<SimulationPlayer>, // "Attackers" are team A, "Defenders" team B
+    pub format: String,                 // "BO1", "BO3" or "BO5"
+    #[serde(default, deserialize_with = "crate::ranked::deserialize_maps_param")]
+    pub maps: Vec<String>, // Omit to veto from every map
+    pub seed: Option<u64>,              // Omit for a random seed
+    pub patch_version: Option<String>,  // Omit for the baseline balance patch
+}
+
+pub type SeriesManager = Arc<Mutex<HashMap<Uuid, SeriesResult>>>;
+
+pub fn create_series_manager() -> SeriesManager {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+/// Plays a whole series: the veto, then maps in veto order until one team has
+/// won enough of them. Team A starts on attack on the first map and the
+/// starting sides alternate from there.
+pub fn run_series(
+    format: SeriesFormat,
+    pool: &[Map],
+    players: &[Player],
+    seed: u64,
+    balance: &BalanceConfig,
+) -> Result<SeriesResult, String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let veto = run_veto(format, pool, &mut rng)?;
+
+    // Team A is whoever starts the series on attack
+    let series_players: Vec<SeriesPlayer> = players
+        .iter()
+        .map(|player| SeriesPlayer {
+            id: player.id,
+            name: player.name.clone(),
+            team: match player.team {
+                Team::Attackers => SeriesTeam::TeamA,
+                Team::Defenders => SeriesTeam::TeamB,
+            },
+        })
+        .collect();
+    let team_a_id = series_players
+        .iter()
+        .find(|p| p.team == SeriesTeam::TeamA)
+        .map(|p| p.id)
+        .ok_or("Series needs players on both teams")?;
+    if !series_players.iter().any(|p| p.team == SeriesTeam::TeamB) {
+        return Err("Series needs players on both teams".to_string());
+    }
+
+    let mut maps = Vec::new();
+    let (mut team_a_maps, mut team_b_maps) = (0u8, 0u8);
+    for (index, (map, picked_by)) in veto_maps(&veto).into_iter().enumerate() {
+        if team_a_maps.max(team_b_maps) >= format.maps_to_win() {
+            break;
+        }
+
+        let team_a_starting_side = if index % 2 == 0 {
+            Team::Attackers
+        } else {
+            Team::Defenders
+        };
+        let map_seed: u64 = rng.random();
+        let mut sim = ValorantSimulation::with_balance(map_seed, balance.clone());
+        sim.set_map(map)?;
+        for (player, series_player) in players.iter().zip(&series_players) {
+            let mut player = player.clone();
+            player.team = if series_player.team == SeriesTeam::TeamA {
+                team_a_starting_side.clone()
+            } else {
+                opposite(&team_a_starting_side)
+            };
+            sim.add_player(player);
+        }
+        sim.run_simulation_to_completion()?;
+
+        // Sides have swapped during the map, so find where team A ended up
+        let team_a_side = sim.players[&team_a_id].team.clone();
+        let (team_a_score, team_b_score) = match team_a_side {
+            Team::Attackers => (sim.state.attacker_score, sim.state.defender_score),
+            Team::Defenders => (sim.state.defender_score, sim.state.attacker_score),
+        };
+        let winner = match &sim.state.phase {
+            SimulationPhase::MatchEnd {
+                winner: Some(side), ..
+            } if *side == team_a_side => SeriesTeam::TeamA,
+            SimulationPhase::MatchEnd {
+                winner: Some(_), ..
+            } => SeriesTeam::TeamB,
+            _ => return Err(format!("Map {:?} ended without a winner", map)),
+        };
+        match winner {
+            SeriesTeam::TeamA => team_a_maps += 1,
+            SeriesTeam::TeamB => team_b_maps += 1,
+        }
+
+        maps.push(MapResult {
+            map,
+            picked_by,
+            team_a_starting_side,
+            seed: map_seed,
+            team_a_score,
+            team_b_score,
+            winner,
+            player_stats: sim.get_player_stats(),
+        });
+    }
+
+    let winner = if team_a_maps > team_b_maps {
+        SeriesTeam::TeamA
+    } else {
+        SeriesTeam::TeamB
+    };
+    Ok(SeriesResult {
+        id: Uuid::new_v4().to_string(),
+        format,
+        seed,
+        players: series_players,
+        veto,
+        maps,
+        team_a_maps,
+        team_b_maps,
+        winner,
+    })
+}
+
+fn opposite(team: &Team) -> Team {
+    match team {
+        Team::Attackers => Team::Defenders,
+        Team::Defenders => Team::Attackers,
+    }
+}
+
+pub fn create_series(
+    manager: &SeriesManager,
+    request: CreateSeriesRequest,
+) -> Result<SeriesResult, String> {
+    let format = parse_series_format(&request.format)?;
+    let pool = if request.maps.is_empty() {
+        Map::all().to_vec()
+    } else {
+        request
+            .maps
+            .iter()
+            .map(|name| parse_map(name))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    let balance = match request.patch_version.as_deref() {
+        Some(version) => BalanceConfig::load(version)?,
+        None => BalanceConfig::default(),
+    };
+    let players = request
+        .players
+        .into_iter()
+        .map(build_player)
+        .collect::<Result<Vec<_>, _>>()?;
+    let seed = request.seed.unwrap_or_else(|| rand::rng().random());
+
+    let result = run_series(format, &pool, &players, seed, &balance)?;
+    let series_id = Uuid::parse_str(&result.id).map_err(|_| "Invalid series ID")?;
+    safe_lock(manager)?.insert(series_id, result.clone());
+    Ok(result)
+}
+
+pub fn get_series(manager: &SeriesManager, series_id: &str) -> Result<SeriesResult, String> {
+    let series_id = Uuid::parse_str(series_id).map_err(|_| "Invalid series ID")?;
+    let series = safe_lock(manager)?;
+    series
+        .get(&series_id)
+        .cloned()
+        .ok_or_else(|| "Series not found".to_string())
+}
+
+pub fn get_series_scoreboard(
+    manager: &SeriesManager,
+    series_id: &str,
+) -> Result<SeriesScoreboard, String> {
+    let series = get_series(manager, series_id)?;
+    Ok(series_scoreboard(&series))
+}
+
+/// Sums every player's stats over the maps played, best players first.
+pub fn series_scoreboard(series: &SeriesResult) -> SeriesScoreboard {
+    let mut players: Vec<SeriesPlayerStats> = series
+        .players
+        .iter()
+        .map(|player| SeriesPlayerStats {
+            player_id: player.id,
+            name: player.name.clone(),
+            team: player.team,
+            maps_played: 0,
+            kills: 0,
+            deaths: 0,
+            assists: 0,
+            damage_dealt: 0,
+        })
+        .collect();
+
+    for map in &series.maps {
+        for stats in &map.player_stats {
+            if let Some(totals) = players.iter_mut().find(|p| p.player_id == stats.player_id) {
+                totals.maps_played += 1;
+                totals.kills += stats.kills;
+                totals.deaths += stats.deaths;
+                totals.assists += stats.assists;
+                totals.damage_dealt += stats.damage_dealt;
+            }
+        }
+    }
+    players.sort_by(|a, b| {
+        b.kills
+            .cmp(&a.kills)
+            .then(a.deaths.cmp(&b.deaths))
+            .then(a.player_id.cmp(&b.player_id))
+    });
+
+    SeriesScoreboard {
+        format: series.format,
+        team_a_maps: series.team_a_maps,
+        team_b_maps: series.team_b_maps,
+        winner: series.winner,
+        map_scores: series
+            .maps
+            .iter()
+            .map(|map| SeriesMapScore {
+                map: map.map,
+                team_a_score: map.team_a_score,
+                team_b_score: map.team_b_score,
+            })
+            .collect(),
+        players,
+    }
+}
@@ -1,9 +1,23 @@
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::agents::{Ability, AbilityEffect, UltimateEffect};
+use crate::balance::{BalanceConfig, CombatWeights};
+use crate::economy::{
+    BuyType, RoundOutcome, TeamBuyContext, WeaponDrop, add_credits, decide_team_buy, full_buy_cost,
+    next_loss_streak, round_credits,
+};
+use crate::maps::{Map, Position, Site};
+use crate::simulation_manager::EventStream;
+use crate::win_probability::{
+    self, WinProbabilityEstimate, WinProbabilitySample, match_win_probability_from,
+};
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 pub enum Agent {
     Jett,
@@ -53,6 +67,38 @@ pub enum Weapon {
     Knife,
 }
 
+impl Weapon {
+    pub fn all() -> [Weapon; 18] {
+        [
+            Weapon::Classic,
+            Weapon::Shorty,
+            Weapon::Frenzy,
+            Weapon::Ghost,
+            Weapon::Sheriff,
+            Weapon::Stinger,
+            Weapon::Spectre,
+            Weapon::Bucky,
+            Weapon::Judge,
+            Weapon::Bulldog,
+            Weapon::Guardian,
+            Weapon::Phantom,
+            Weapon::Vandal,
+            Weapon::Marshal,
+            Weapon::Operator,
+            Weapon::Ares,
+            Weapon::Odin,
+            Weapon::Knife,
+        ]
+    }
+
+    pub fn is_sidearm(&self) -> bool {
+        matches!(
+            self,
+            Weapon::Classic | Weapon::Shorty | Weapon::Frenzy | Weapon::Ghost | Weapon::Sheriff
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 pub enum Team {
     Attackers,
@@ -69,8 +115,18 @@ pub enum BodyPart {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ArmorType {
     None,
-    Light, // 25 armor, costs 400
-    Heavy, // 50 armor, costs 1000
+    Light, // 25 armor
+    Heavy, // 50 armor
+}
+
+impl ArmorType {
+    pub fn points(&self) -> u32 {
+        match self {
+            ArmorType::None => 0,
+            ArmorType::Light => 25,
+            ArmorType::Heavy => 50,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -80,18 +136,31 @@ pub enum Penetration {
     High,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeaponStats {
     pub price: u32,
     pub damage_head: (u32, u32, u32), // no armor, light armor, heavy armor
     pub damage_body: (u32, u32, u32),
     pub damage_legs: (u32, u32, u32),
-    pub fire_rate: f32, // rounds per second
+    pub range_falloff: Vec<(f32, f32)>, // (range in meters, multiplier beyond it), nearest first
+    pub fire_rate: f32,                 // rounds per second
     pub penetration: Penetration,
     pub magazine_size: u32,
     pub reload_time_ms: u32,
 }
 
+impl WeaponStats {
+    /// Damage multiplier at `range_meters`, taken from the furthest band the
+    /// target is beyond. Full damage until the first band starts.
+    pub fn falloff_multiplier(&self, range_meters: f32) -> f32 {
+        self.range_falloff
+            .iter()
+            .rev()
+            .find(|(start_range, _)| range_meters > *start_range)
+            .map_or(1.0, |(_, multiplier)| *multiplier)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PlayerLoadout {
     pub primary_weapon: Option<Weapon>,
@@ -120,6 +189,8 @@ pub struct Player {
     pub current_credits: u32,
     pub ultimate_points: u32,
     pub current_loadout: PlayerLoadout,
+    #[serde(default)]
+    pub position: Position,
 
     skills: PlayerSkills,
 }
@@ -142,6 +213,7 @@ impl Player {
                 armor: ArmorType::None,
                 abilities_purchased: Vec::new(),
             },
+            position: Position::Spawn,
             skills,
         }
     }
@@ -149,12 +221,9 @@ impl Player {
     pub fn reset_for_round(&mut self) {
         self.current_health = 100;
         // Set armor based on purchased armor type
-        self.current_armor = match self.current_loadout.armor {
-            ArmorType::None => 0,
-            ArmorType::Light => 25,
-            ArmorType::Heavy => 50,
-        };
+        self.current_armor = self.current_loadout.armor.points();
         self.is_alive = true;
+        self.position = Position::Spawn;
     }
 
     pub fn take_damage(&mut self, amount: u32) {
@@ -176,13 +245,195 @@ impl Player {
         }
     }
 
+    /// Takes a hit of `raw_damage`. While the player's armor holds, it soaks
+    /// up the difference down to `armored_damage` and wears down by as much.
+    /// Returns the health and armor actually lost.
+    pub fn absorb_hit(&mut self, raw_damage: u32, armored_damage: u32) -> u32 {
+        if !self.is_alive {
+            return 0;
+        }
+
+        let absorbed = raw_damage
+            .saturating_sub(armored_damage)
+            .min(self.current_armor);
+        self.current_armor -= absorbed;
+        let health_lost = (raw_damage - absorbed).min(self.current_health);
+        self.current_health -= health_lost;
+        if self.current_health == 0 {
+            self.is_alive = false;
+        }
+        absorbed + health_lost
+    }
+
+    pub fn skills(&self) -> &PlayerSkills {
+        &self.skills
+    }
+
     pub fn survived_round(&self) -> bool {
         self.is_alive
     }
+
+    /// Adds ultimate points, never past what the agent's ultimate costs.
+    pub fn add_ultimate_points(&mut self, points: u32) {
+        let cost = self.agent.ultimate().cost;
+        self.ultimate_points = (self.ultimate_points + points).min(cost);
+    }
+
+    pub fn has_ultimate(&self) -> bool {
+        self.ultimate_points >= self.agent.ultimate().cost
+    }
+
+    pub fn heal(&mut self, amount: u32) {
+        if self.is_alive {
+            self.current_health = (self.current_health + amount).min(100);
+        }
+    }
+
+    /// Charges of `ability_name` the player is carrying; each purchased charge
+    /// is one entry in `abilities_purchased`.
+    pub fn ability_charges(&self, ability_name: &str) -> usize {
+        self.current_loadout
+            .abilities_purchased
+            .iter()
+            .filter(|name| *name == ability_name)
+            .count()
+    }
+
+    /// First ability in the agent's kit the player holds a charge of and that
+    /// matches `usable`.
+    pub fn held_ability(&self, usable: impl Fn(&Ability) -> bool) -> Option<&'static Ability> {
+        self.agent
+            .kit()
+            .iter()
+            .find(|ability| usable(ability) && self.ability_charges(ability.name) > 0)
+    }
+
+    pub fn consume_ability(&mut self, ability_name: &str) -> bool {
+        let abilities = &mut self.current_loadout.abilities_purchased;
+        match abilities.iter().position(|name| name == ability_name) {
+            Some(index) => {
+                abilities.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drops everything but ability charges, which a player keeps through death.
+    fn lose_gear(&mut self) {
+        self.current_loadout.primary_weapon = None;
+        self.current_loadout.secondary_weapon = Weapon::Classic;
+        self.current_loadout.armor = ArmorType::None;
+    }
+
+    /// Buys the first `(weapon, armor)` loadout in `options` the player can
+    /// afford, paying only for the parts they don't already have. A sidearm
+    /// isn't needed by anyone holding a primary.
+    fn buy_first_affordable(
+        &mut self,
+        options: &[(Weapon, ArmorType)],
+        balance: &BalanceConfig,
+    ) -> bool {
+        let loadout = &self.current_loadout;
+        for (weapon, armor) in options {
+            let needs_weapon = if weapon.is_sidearm() {
+                loadout.primary_weapon.is_none() && loadout.secondary_weapon == Weapon::Classic
+            } else {
+                loadout.primary_weapon.is_none()
+            };
+            let needs_armor = armor.points() > loadout.armor.points();
+
+            let mut cost = 0;
+            if needs_weapon {
+                cost += balance.weapons[weapon].price;
+            }
+            if needs_armor {
+                cost += balance.armor_costs.cost(armor);
+            }
+            if cost > self.current_credits {
+                continue;
+            }
+
+            self.current_credits -= cost;
+            if needs_weapon {
+                if weapon.is_sidearm() {
+                    self.current_loadout.secondary_weapon = weapon.clone();
+                } else {
+                    self.current_loadout.primary_weapon = Some(weapon.clone());
+                }
+            }
+            if needs_armor {
+                self.current_loadout.armor = armor.clone();
+            }
+            return true;
+        }
+        false
+    }
+
+    /// Tops up the charges the agent gets for free each round.
+    fn refill_free_abilities(&mut self) {
+        for ability in self.agent.kit() {
+            let mut charges = self.ability_charges(ability.name);
+            while charges < ability.free_charges {
+                self.current_loadout
+                    .abilities_purchased
+                    .push(ability.name.to_string());
+                charges += 1;
+            }
+        }
+    }
+
+    /// Refills free charges and buys the rest of the kit with whatever credits
+    /// are left, in kit order.
+    fn buy_abilities(&mut self) {
+        self.refill_free_abilities();
+        for ability in self.agent.kit() {
+            let mut charges = self.ability_charges(ability.name);
+            while charges < ability.max_charges && self.current_credits >= ability.cost {
+                self.current_credits -= ability.cost;
+                self.current_loadout
+                    .abilities_purchased
+                    .push(ability.name.to_string());
+                charges += 1;
+            }
+        }
+    }
 }
 
 pub type Timestamp = u64;
 
+/// Match time covered by a single `advance_tick`.
+pub const TICK_DURATION_MS: u64 = 500;
+/// Time attackers have to plant, and the spike's fuse once planted.
+pub const ROUND_TIME_MS: i32 = 100_000;
+pub const SPIKE_TIME_MS: i32 = 45_000;
+/// Ticks the playback driver runs per step while fast-forwarding.
+pub const FAST_FORWARD_TICKS_PER_STEP: u32 = 10;
+/// Chance a player with perfect utility skill uses an ability when it matters.
+const UTILITY_USE_CHANCE: f32 = 0.5;
+/// Health restored by one heal ability.
+const HEAL_AMOUNT: u32 = 60;
+/// Reaction time before an even duel's first shot.
+const DUEL_REACTION_MS: f32 = 250.0;
+/// Longest a single duel runs before both players break off.
+const DUEL_MAX_MS: f32 = 2500.0;
+/// Chance per tick of a fight away from the site being attacked.
+const SKIRMISH_CHANCE: f32 = 0.05;
+/// How close to a site attackers are when they start contesting its choke.
+const CHOKE_CONTACT_MS: u64 = 3000;
+/// Ultimate orbs placed on the map each round.
+const ULT_ORBS_PER_ROUND: u32 = 2;
+/// Chance each orb gets picked up during a round.
+const ULT_ORB_PICKUP_CHANCE: f32 = 0.5;
+/// Rounds in each regulation half.
+const HALF_ROUNDS: u8 = 12;
+/// Rounds in regulation; anything after is overtime.
+const REGULATION_ROUNDS: u8 = 24;
+/// Chance each player votes for a draw when a ranked overtime pair ends level.
+const DRAW_VOTE_CHANCE: f32 = 0.6;
+/// Share of the lobby that has to vote for a draw for it to pass.
+const DRAW_VOTE_MAJORITY: f32 = 0.8;
+
 // Helper functions for UUID serialization in schemas
 fn serialize_uuid<S>(uuid: &Uuid, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -221,11 +472,41 @@ pub enum SimulationPhase {
         winner: Team,
     },
     MatchEnd {
-        winner: Team,
+        winner: Option<Team>, // None if the match was drawn
         final_score: (u8, u8),
     },
 }
 
+/// How a match still level after regulation is settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+pub enum TiebreakMode {
+    /// Overtime runs until a team is two rounds ahead.
+    #[default]
+    Tournament,
+    /// After every overtime pair that ends level, the players vote on
+    /// calling the match a draw.
+    Ranked,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct OvertimeSettings {
+    pub tiebreak: TiebreakMode,
+    /// If the first overtime pair ends level, the next round decides the match.
+    pub sudden_death: bool,
+    /// Credits every player starts each overtime round with.
+    pub round_credits: u32,
+}
+
+impl Default for OvertimeSettings {
+    fn default() -> Self {
+        OvertimeSettings {
+            tiebreak: TiebreakMode::Tournament,
+            sudden_death: false,
+            round_credits: 5000,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SimulationState {
     #[serde(
@@ -242,7 +523,21 @@ pub struct SimulationState {
     pub attacker_score: u8,
     pub defender_score: u8,
     pub overtime_active: bool,
+    #[serde(default)]
+    pub overtime: OvertimeSettings,
     pub tick_count: u64,
+    pub seed: u64,
+    pub patch_version: String,
+    pub map: Map,
+    #[serde(default)]
+    pub teams: MatchTeams,
+}
+
+/// Names of the two rosters, by the side each one started the match on.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MatchTeams {
+    pub starting_attackers: Option<String>,
+    pub starting_defenders: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -273,7 +568,7 @@ pub enum GameEvent {
     },
     MatchEnd {
         timestamp: Timestamp,
-        winning_team: Team,
+        winning_team: Option<Team>, // None if the match was drawn
         score_attackers: u8,
         score_defenders: u8,
     },
@@ -303,6 +598,8 @@ pub enum GameEvent {
         victim_id: u32,
         weapon: Weapon,
         is_headshot: bool,
+        #[serde(default)]
+        assister_ids: Vec<u32>,
     },
     Damage {
         timestamp: Timestamp,
@@ -315,6 +612,8 @@ pub enum GameEvent {
     SpikePlant {
         timestamp: Timestamp,
         planter_id: u32,
+        #[serde(default)]
+        site: Option<Site>,
     },
     SpikeDefuse {
         timestamp: Timestamp,
@@ -330,6 +629,21 @@ pub enum GameEvent {
         timestamp: Timestamp,
         round_number: u8,
     },
+    DrawVote {
+        timestamp: Timestamp,
+        round_number: u8,
+        votes_for: u8,
+        votes_against: u8,
+        passed: bool,
+    },
+    TeamBuy {
+        timestamp: Timestamp,
+        round_number: u8,
+        team: Team,
+        buy_type: BuyType,
+        team_credits: u32, // before buying
+        drops: Vec<WeaponDrop>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
@@ -356,138 +670,155 @@ impl GameEvent {
             GameEvent::SpikeDefuse { timestamp, .. } => *timestamp,
             GameEvent::AbilityUsed { timestamp, .. } => *timestamp,
             GameEvent::SideSwap { timestamp, .. } => *timestamp,
+            GameEvent::DrawVote { timestamp, .. } => *timestamp,
+            GameEvent::TeamBuy { timestamp, .. } => *timestamp,
+        }
+    }
+
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            GameEvent::MatchStart { .. } => "MatchStart",
+            GameEvent::MatchEnd { .. } => "MatchEnd",
+            GameEvent::BuyPhaseStart { .. } => "BuyPhaseStart",
+            GameEvent::BuyPhaseEnd { .. } => "BuyPhaseEnd",
+            GameEvent::RoundStart { .. } => "RoundStart",
+            GameEvent::RoundEnd { .. } => "RoundEnd",
+            GameEvent::Kill { .. } => "Kill",
+            GameEvent::Damage { .. } => "Damage",
+            GameEvent::SpikePlant { .. } => "SpikePlant",
+            GameEvent::SpikeDefuse { .. } => "SpikeDefuse",
+            GameEvent::AbilityUsed { .. } => "AbilityUsed",
+            GameEvent::SideSwap { .. } => "SideSwap",
+            GameEvent::DrawVote { .. } => "DrawVote",
+            GameEvent::TeamBuy { .. } => "TeamBuy",
+        }
+    }
+
+    /// Players directly involved in the event; empty for match-level events.
+    pub fn player_ids(&self) -> Vec<u32> {
+        match self {
+            GameEvent::Kill {
+                killer_id,
+                victim_id,
+                assister_ids,
+                ..
+            } => [*killer_id, *victim_id]
+                .into_iter()
+                .chain(assister_ids.iter().copied())
+                .collect(),
+            GameEvent::Damage {
+                attacker_id,
+                victim_id,
+                ..
+            } => vec![*attacker_id, *victim_id],
+            GameEvent::SpikePlant { planter_id, .. } => vec![*planter_id],
+            GameEvent::SpikeDefuse { defuser_id, .. } => vec![*defuser_id],
+            GameEvent::AbilityUsed { player_id, .. } => vec![*player_id],
+            GameEvent::TeamBuy { drops, .. } => drops
+                .iter()
+                .flat_map(|drop| [drop.buyer_id, drop.recipient_id])
+                .collect(),
+            _ => Vec::new(),
         }
     }
 }
 
+/// A player's raw gunfight skill in [0, 1], blending aim and headshot skill.
+fn gunfight_skill(skills: &PlayerSkills, combat: &CombatWeights) -> f32 {
+    let aim = skills.aim.clamp(0.0, 1.0);
+    let hs = skills.hs.clamp(0.0, 1.0);
+    (aim * combat.aim_weight + hs * combat.headshot_weight)
+        / (combat.aim_weight + combat.headshot_weight)
+}
+
+/// One side of a duel in progress.
+struct Duelist {
+    player_id: u32,
+    weapon: Weapon,
+    accuracy: f32,
+    headshot_rate: f32,
+    movement: f32,
+    next_shot_ms: f32,
+    shot_interval_ms: f32,
+    rounds_left: u32,
+}
+
 pub struct ValorantSimulation {
     pub state: SimulationState,
     pub players: HashMap<u32, Player>,
     pub events: Vec<GameEvent>,
+    pub win_probability: Vec<WinProbabilitySample>, // sampled whenever the odds move
     pub loss_streaks: HashMap<Team, u8>,
-    pub weapon_stats: HashMap<Weapon, WeaponStats>,
+    pub balance: BalanceConfig,
 
     // New fields for modular control
-    pub checkpoints: HashMap<u64, SimulationCheckpoint>,
+    pub checkpoints: HashMap<Uuid, SimulationCheckpoint>,
+    pub round_snapshots: HashMap<u8, SimulationCheckpoint>,
     pub round_timer_ms: i32,
     pub spike_timer_ms: i32,
     pub spike_planted: bool,
     pub spike_defused: bool,
     pub round_start_timestamp: Timestamp,
+    pub ultimate_boosts: HashMap<Team, f32>, // active until the round ends
+    pub assist_contributions: HashMap<u32, Vec<u32>>, // victim id -> contributor ids this round
+    pub attack_site: Option<Site>,           // where the attackers execute this round
+    pub event_stream: EventStream,
+
+    // Server-side playback: the driver only keeps running while its generation is current
+    pub autoplay: bool,
+    pub playback_generation: u64,
+
+    // Single source of randomness so a seed fully determines the match
+    rng: StdRng,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimulationCheckpoint {
+    #[serde(
+        serialize_with = "serialize_uuid",
+        deserialize_with = "deserialize_uuid"
+    )]
+    pub id: Uuid,
+    pub description: String,
     pub state: SimulationState,
     pub players: HashMap<u32, Player>,
     pub events: Vec<GameEvent>,
     pub loss_streaks: HashMap<Team, u8>,
+    pub round_timer_ms: i32,
+    pub spike_timer_ms: i32,
+    pub spike_planted: bool,
+    pub spike_defused: bool,
+    pub round_start_timestamp: Timestamp,
+    #[serde(default)]
+    pub ultimate_boosts: HashMap<Team, f32>,
+    #[serde(default)]
+    pub assist_contributions: HashMap<u32, Vec<u32>>,
+    #[serde(default)]
+    pub attack_site: Option<Site>,
+    // The timeline only grows, so a snapshot keeps its length rather than a copy
+    #[serde(default)]
+    pub win_probability_len: usize,
+
+    // Restoring the RNG makes a replay from here identical to the original run
+    #[serde(skip)]
+    rng: Option<StdRng>,
 }
 
 #[allow(clippy::new_without_default)]
 impl ValorantSimulation {
     pub fn new() -> Self {
-        let mut weapon_stats = HashMap::new();
-
-        // Initialize weapon stats based on Valorant data
-        weapon_stats.insert(
-            Weapon::Classic,
-            WeaponStats {
-                price: 0,
-                damage_head: (78, 66, 26),
-                damage_body: (26, 22, 22),
-                damage_legs: (22, 18, 18),
-                fire_rate: 6.75,
-                penetration: Penetration::Low,
-                magazine_size: 12,
-                reload_time_ms: 2250,
-            },
-        );
-
-        weapon_stats.insert(
-            Weapon::Ghost,
-            WeaponStats {
-                price: 500,
-                damage_head: (105, 87, 30),
-                damage_body: (30, 25, 25),
-                damage_legs: (25, 21, 21),
-                fire_rate: 6.75,
-                penetration: Penetration::Medium,
-                magazine_size: 15,
-                reload_time_ms: 2500,
-            },
-        );
-
-        weapon_stats.insert(
-            Weapon::Sheriff,
-            WeaponStats {
-                price: 800,
-                damage_head: (159, 145, 55),
-                damage_body: (55, 50, 46),
-                damage_legs: (46, 42, 42),
-                fire_rate: 4.0,
-                penetration: Penetration::High,
-                magazine_size: 6,
-                reload_time_ms: 3000,
-            },
-        );
-
-        weapon_stats.insert(
-            Weapon::Spectre,
-            WeaponStats {
-                price: 1600,
-                damage_head: (78, 66, 26),
-                damage_body: (26, 22, 22),
-                damage_legs: (22, 18, 18),
-                fire_rate: 13.33,
-                penetration: Penetration::Medium,
-                magazine_size: 30,
-                reload_time_ms: 2250,
-            },
-        );
-
-        weapon_stats.insert(
-            Weapon::Phantom,
-            WeaponStats {
-                price: 2900,
-                damage_head: (156, 140, 124), // Close range
-                damage_body: (39, 35, 31),
-                damage_legs: (33, 29, 26),
-                fire_rate: 11.0,
-                penetration: Penetration::Medium,
-                magazine_size: 30,
-                reload_time_ms: 2500,
-            },
-        );
-
-        weapon_stats.insert(
-            Weapon::Vandal,
-            WeaponStats {
-                price: 2900,
-                damage_head: (160, 160, 160), // Always 160 regardless of armor
-                damage_body: (40, 40, 40),
-                damage_legs: (34, 34, 34),
-                fire_rate: 9.75,
-                penetration: Penetration::Medium,
-                magazine_size: 25,
-                reload_time_ms: 2500,
-            },
-        );
+        Self::with_seed(rand::rng().random())
+    }
 
-        weapon_stats.insert(
-            Weapon::Operator,
-            WeaponStats {
-                price: 4700,
-                damage_head: (255, 255, 255),
-                damage_body: (150, 150, 150),
-                damage_legs: (120, 120, 120),
-                fire_rate: 0.75,
-                penetration: Penetration::High,
-                magazine_size: 5,
-                reload_time_ms: 3700,
-            },
-        );
+    /// Creates a simulation whose random choices are all drawn from `seed`,
+    /// so the same roster and seed always produce the same event log.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_balance(seed, BalanceConfig::default())
+    }
 
+    /// Creates a seeded simulation that prices, damages and pays out according
+    /// to `balance` instead of the built-in baseline patch.
+    pub fn with_balance(seed: u64, balance: BalanceConfig) -> Self {
         let simulation_id = Uuid::new_v4();
 
         ValorantSimulation {
@@ -501,18 +832,32 @@ impl ValorantSimulation {
                 attacker_score: 0,
                 defender_score: 0,
                 overtime_active: false,
+                overtime: OvertimeSettings::default(),
                 tick_count: 0,
+                seed,
+                patch_version: balance.version.clone(),
+                map: Map::Ascent,
+                teams: MatchTeams::default(),
             },
             players: HashMap::new(),
             events: Vec::new(),
+            win_probability: Vec::new(),
             loss_streaks: HashMap::new(),
-            weapon_stats,
+            balance,
             checkpoints: HashMap::new(),
-            round_timer_ms: 100_000,
-            spike_timer_ms: 45_000,
+            round_snapshots: HashMap::new(),
+            round_timer_ms: ROUND_TIME_MS,
+            spike_timer_ms: SPIKE_TIME_MS,
             spike_planted: false,
             spike_defused: false,
             round_start_timestamp: 0,
+            ultimate_boosts: HashMap::new(),
+            assist_contributions: HashMap::new(),
+            attack_site: None,
+            event_stream: EventStream::new(simulation_id.to_string()),
+            autoplay: false,
+            playback_generation: 0,
+            rng: StdRng::seed_from_u64(seed),
         }
     }
 
@@ -520,9 +865,30 @@ impl ValorantSimulation {
         self.players.insert(player.id, player);
     }
 
+    /// Picks the map the match is played on. Only allowed before it starts.
+    pub fn set_map(&mut self, map: Map) -> Result<(), String> {
+        if !matches!(self.state.phase, SimulationPhase::NotStarted) {
+            return Err("Map can only be changed before the simulation starts".to_string());
+        }
+        self.state.map = map;
+        Ok(())
+    }
+
+    /// Sets how a tied match is settled. Only allowed before it starts.
+    pub fn set_overtime_settings(&mut self, settings: OvertimeSettings) -> Result<(), String> {
+        if !matches!(self.state.phase, SimulationPhase::NotStarted) {
+            return Err(
+                "Overtime settings can only be changed before the simulation starts".to_string(),
+            );
+        }
+        self.state.overtime = settings;
+        Ok(())
+    }
+
+    // Match time always moves in fixed steps; playback_speed only changes how
+    // fast the playback driver feeds ticks in wall-clock time
     pub fn advance_time(&mut self, delta_ms: u64) {
-        let adjusted_delta = (delta_ms as f32 * self.state.playback_speed) as u64;
-        self.state.current_timestamp += adjusted_delta;
+        self.state.current_timestamp += delta_ms;
         self.state.tick_count += 1;
     }
 
@@ -534,8 +900,9 @@ impl ValorantSimulation {
             self.state.current_round = 1;
 
             // Initialize players with starting credits
+            let starting_credits = self.balance.economy.starting_credits;
             for player in self.players.values_mut() {
-                player.current_credits = 800;
+                player.current_credits = starting_credits;
             }
 
             // Initialize loss streaks
@@ -554,7 +921,33 @@ impl ValorantSimulation {
 
     pub fn resume_simulation(&mut self) {
         if matches!(self.state.mode, SimulationMode::Paused) {
-            self.state.mode = SimulationMode::Playing;
+            self.state.mode = if self.state.playback_speed > 1.0 {
+                SimulationMode::FastForward
+            } else {
+                SimulationMode::Playing
+            };
+        }
+    }
+
+    /// Hands ticking over to the server-side playback driver.
+    pub fn start_playback(&mut self) {
+        self.autoplay = true;
+        self.resume_simulation();
+    }
+
+    /// How many ticks the playback driver should run next and how long to wait
+    /// afterwards, or `None` once it should stop.
+    pub fn playback_step(&self) -> Option<(u32, Duration)> {
+        if !self.autoplay || matches!(self.state.phase, SimulationPhase::MatchEnd { .. }) {
+            return None;
+        }
+
+        let interval =
+            Duration::from_secs_f32(TICK_DURATION_MS as f32 / 1000.0 / self.state.playback_speed);
+        match self.state.mode {
+            SimulationMode::Paused => None,
+            SimulationMode::Playing => Some((1, interval)),
+            SimulationMode::FastForward => Some((FAST_FORWARD_TICKS_PER_STEP, interval)),
         }
     }
 
@@ -567,26 +960,143 @@ impl ValorantSimulation {
         }
     }
 
-    pub fn create_checkpoint(&mut self) {
-        let checkpoint = SimulationCheckpoint {
+    fn snapshot(&self, description: String) -> SimulationCheckpoint {
+        SimulationCheckpoint {
+            id: Uuid::new_v4(),
+            description,
             state: self.state.clone(),
             players: self.players.clone(),
             events: self.events.clone(),
             loss_streaks: self.loss_streaks.clone(),
-        };
-        self.checkpoints.insert(self.state.tick_count, checkpoint);
+            round_timer_ms: self.round_timer_ms,
+            spike_timer_ms: self.spike_timer_ms,
+            spike_planted: self.spike_planted,
+            spike_defused: self.spike_defused,
+            round_start_timestamp: self.round_start_timestamp,
+            ultimate_boosts: self.ultimate_boosts.clone(),
+            assist_contributions: self.assist_contributions.clone(),
+            attack_site: self.attack_site,
+            win_probability_len: self.win_probability.len(),
+            rng: Some(self.rng.clone()),
+        }
+    }
+
+    fn apply_snapshot(&mut self, checkpoint: SimulationCheckpoint) {
+        self.state = checkpoint.state;
+        self.players = checkpoint.players;
+        self.events = checkpoint.events;
+        self.loss_streaks = checkpoint.loss_streaks;
+        self.round_timer_ms = checkpoint.round_timer_ms;
+        self.spike_timer_ms = checkpoint.spike_timer_ms;
+        self.spike_planted = checkpoint.spike_planted;
+        self.spike_defused = checkpoint.spike_defused;
+        self.round_start_timestamp = checkpoint.round_start_timestamp;
+        self.ultimate_boosts = checkpoint.ultimate_boosts;
+        self.assist_contributions = checkpoint.assist_contributions;
+        self.attack_site = checkpoint.attack_site;
+        // Samples from a branch a rewind has since dropped are gone for good
+        self.win_probability
+            .truncate(checkpoint.win_probability_len);
+        if let Some(rng) = checkpoint.rng {
+            self.rng = rng;
+        }
+    }
+
+    pub fn create_checkpoint(&mut self, description: String) -> Uuid {
+        let checkpoint = self.snapshot(description);
+        let checkpoint_id = checkpoint.id;
+        self.checkpoints.insert(checkpoint_id, checkpoint);
+        checkpoint_id
     }
 
-    pub fn restore_checkpoint(&mut self, tick: u64) -> Result<(), String> {
-        if let Some(checkpoint) = self.checkpoints.get(&tick).cloned() {
-            self.state = checkpoint.state;
-            self.players = checkpoint.players;
-            self.events = checkpoint.events;
-            self.loss_streaks = checkpoint.loss_streaks;
+    pub fn restore_checkpoint(&mut self, checkpoint_id: Uuid) -> Result<(), String> {
+        if let Some(checkpoint) = self.checkpoints.get(&checkpoint_id).cloned() {
+            self.apply_snapshot(checkpoint);
             Ok(())
         } else {
-            Err(format!("Checkpoint not found for tick {}", tick))
+            Err(format!("Checkpoint not found: {}", checkpoint_id))
+        }
+    }
+
+    /// Rolls the match back to the start of `round_number`'s buy phase using
+    /// the snapshot taken automatically when that round began.
+    pub fn rewind_to_round(&mut self, round_number: u8) -> Result<(), String> {
+        if round_number == 0 {
+            return Err("Cannot rewind to round 0".to_string());
+        }
+        if round_number > self.state.current_round {
+            return Err(format!(
+                "Cannot rewind to round {} from round {}",
+                round_number, self.state.current_round
+            ));
+        }
+
+        let snapshot = self
+            .round_snapshots
+            .get(&round_number)
+            .cloned()
+            .ok_or_else(|| format!("No snapshot recorded for round {}", round_number))?;
+        self.apply_snapshot(snapshot);
+
+        // Later rounds get re-recorded as the match plays forward again
+        self.round_snapshots
+            .retain(|round, _| *round <= round_number);
+        Ok(())
+    }
+
+    /// Re-runs the match from the nearest round snapshot at or before `timestamp`
+    /// until the clock reaches it. The stored RNG makes this reproduce the
+    /// original run exactly.
+    pub fn replay_from(&mut self, timestamp: Timestamp) -> Result<(), String> {
+        if timestamp > self.state.current_timestamp {
+            return Err(format!(
+                "Cannot replay from {}ms, simulation is only at {}ms",
+                timestamp, self.state.current_timestamp
+            ));
+        }
+
+        let snapshot = self
+            .round_snapshots
+            .values()
+            .filter(|snapshot| snapshot.state.current_timestamp <= timestamp)
+            .max_by_key(|snapshot| snapshot.state.current_timestamp)
+            .cloned()
+            .ok_or_else(|| format!("No snapshot recorded at or before {}ms", timestamp))?;
+
+        // Playback controls belong to the viewer, not the recorded match
+        let mode = self.state.mode.clone();
+        let playback_speed = self.state.playback_speed;
+        self.apply_snapshot(snapshot);
+        self.state.mode = SimulationMode::Playing;
+
+        let mut tick_count = 0;
+        const MAX_REPLAY_TICKS: u64 = 50000;
+        while self.state.current_timestamp < timestamp
+            && !matches!(self.state.phase, SimulationPhase::MatchEnd { .. })
+        {
+            tick_count += 1;
+            if tick_count > MAX_REPLAY_TICKS {
+                return Err(format!(
+                    "Replay exceeded maximum tick limit ({}).",
+                    MAX_REPLAY_TICKS
+                ));
+            }
+            self.advance_tick()?;
         }
+
+        self.state.mode = mode;
+        self.state.playback_speed = playback_speed;
+        Ok(())
+    }
+
+    /// True while the teams are on the opposite sides from where they started.
+    pub fn sides_swapped(&self) -> bool {
+        self.events
+            .iter()
+            .filter(|e| matches!(e, GameEvent::SideSwap { .. }))
+            .count()
+            % 2
+            == 1
     }
 
     pub fn get_current_state(&self) -> &SimulationState {
@@ -597,67 +1107,21 @@ impl ValorantSimulation {
         self.events
             .iter()
             .filter(|event| {
-                if let Some(ref event_types) = filter.event_types {
-                    let event_name = match event {
-                        GameEvent::MatchStart { .. } => "MatchStart",
-                        GameEvent::MatchEnd { .. } => "MatchEnd",
-                        GameEvent::BuyPhaseStart { .. } => "BuyPhaseStart",
-                        GameEvent::BuyPhaseEnd { .. } => "BuyPhaseEnd",
-                        GameEvent::RoundStart { .. } => "RoundStart",
-                        GameEvent::RoundEnd { .. } => "RoundEnd",
-                        GameEvent::Kill { .. } => "Kill",
-                        GameEvent::Damage { .. } => "Damage",
-                        GameEvent::SpikePlant { .. } => "SpikePlant",
-                        GameEvent::SpikeDefuse { .. } => "SpikeDefuse",
-                        GameEvent::AbilityUsed { .. } => "AbilityUsed",
-                        GameEvent::SideSwap { .. } => "SideSwap",
-                    };
-                    if !event_types.contains(&event_name.to_string()) {
-                        return false;
-                    }
-                }
-
-                if let Some(ref start_time) = filter.start_timestamp {
-                    let event_time = match event {
-                        GameEvent::MatchStart { timestamp } => *timestamp,
-                        GameEvent::MatchEnd { timestamp, .. } => *timestamp,
-                        GameEvent::BuyPhaseStart { timestamp, .. } => *timestamp,
-                        GameEvent::BuyPhaseEnd { timestamp, .. } => *timestamp,
-                        GameEvent::RoundStart { timestamp, .. } => *timestamp,
-                        GameEvent::RoundEnd { timestamp, .. } => *timestamp,
-                        GameEvent::Kill { timestamp, .. } => *timestamp,
-                        GameEvent::Damage { timestamp, .. } => *timestamp,
-                        GameEvent::SpikePlant { timestamp, .. } => *timestamp,
-                        GameEvent::SpikeDefuse { timestamp, .. } => *timestamp,
-                        GameEvent::AbilityUsed { timestamp, .. } => *timestamp,
-                        GameEvent::SideSwap { timestamp, .. } => *timestamp,
-                    };
-                    if event_time < *start_time {
-                        return false;
-                    }
-                }
-
-                if let Some(ref end_time) = filter.end_timestamp {
-                    let event_time = match event {
-                        GameEvent::MatchStart { timestamp } => *timestamp,
-                        GameEvent::MatchEnd { timestamp, .. } => *timestamp,
-                        GameEvent::BuyPhaseStart { timestamp, .. } => *timestamp,
-                        GameEvent::BuyPhaseEnd { timestamp, .. } => *timestamp,
-                        GameEvent::RoundStart { timestamp, .. } => *timestamp,
-                        GameEvent::RoundEnd { timestamp, .. } => *timestamp,
-                        GameEvent::Kill { timestamp, .. } => *timestamp,
-                        GameEvent::Damage { timestamp, .. } => *timestamp,
-                        GameEvent::SpikePlant { timestamp, .. } => *timestamp,
-                        GameEvent::SpikeDefuse { timestamp, .. } => *timestamp,
-                        GameEvent::AbilityUsed { timestamp, .. } => *timestamp,
-                        GameEvent::SideSwap { timestamp, .. } => *timestamp,
-                    };
-                    if event_time > *end_time {
-                        return false;
-                    }
-                }
-
-                true
+                filter.event_types.as_ref().is_none_or(|types| {
+                    types
+                        .iter()
+                        .any(|event_type| event_type == event.event_type())
+                })
+            })
+            .filter(|event| {
+                filter
+                    .start_timestamp
+                    .is_none_or(|start| event.timestamp() >= start)
+            })
+            .filter(|event| {
+                filter
+                    .end_timestamp
+                    .is_none_or(|end| event.timestamp() <= end)
             })
             .collect()
     }
@@ -674,6 +1138,10 @@ impl ValorantSimulation {
                 matches!(e, GameEvent::Kill { victim_id, .. } if *victim_id == player.id)
             }).count() as u32;
 
+                let assists = self.events.iter().filter(|e| {
+                matches!(e, GameEvent::Kill { assister_ids, .. } if assister_ids.contains(&player.id))
+            }).count() as u32;
+
                 let headshot_kills = self
                     .events
                     .iter()
@@ -700,7 +1168,7 @@ impl ValorantSimulation {
                     player_id: player.id,
                     kills,
                     deaths,
-                    assists: 0, // TODO: Implement assist tracking
+                    assists,
                     damage_dealt,
                     headshot_percentage: if kills > 0 {
                         (headshot_kills as f32 / kills as f32) * 100.0
@@ -715,9 +1183,43 @@ impl ValorantSimulation {
     }
 
     fn record_event(&mut self, event: GameEvent) {
+        self.event_stream.broadcast_event(self.events.len(), &event);
+        self.record_win_probability(&event);
         self.events.push(event);
     }
 
+    /// Adds a timeline sample after any event that changes who is favored.
+    fn record_win_probability(&mut self, event: &GameEvent) {
+        let estimate = match event {
+            GameEvent::RoundStart { .. }
+            | GameEvent::Kill { .. }
+            | GameEvent::SpikePlant { .. } => win_probability::estimate(self),
+            // The score already counts this round
+            GameEvent::RoundEnd { winning_team, .. } => WinProbabilityEstimate {
+                attacker_round: if *winning_team == Team::Attackers {
+                    1.0
+                } else {
+                    0.0
+                },
+                attacker_match: match_win_probability_from(
+                    self.state.attacker_score,
+                    self.state.defender_score,
+                ),
+            },
+            _ => return,
+        };
+
+        self.win_probability.push(WinProbabilitySample {
+            round_number: self.state.current_round,
+            timestamp: event.timestamp(),
+            trigger: event.event_type().to_string(),
+            attackers_alive: self.get_alive_players_on_team(&Team::Attackers).len() as u8,
+            defenders_alive: self.get_alive_players_on_team(&Team::Defenders).len() as u8,
+            attacker_round: estimate.attacker_round,
+            attacker_match: estimate.attacker_match,
+        });
+    }
+
     pub fn advance_tick(&mut self) -> Result<(), String> {
         if matches!(self.state.mode, SimulationMode::Paused) {
             return Ok(());
@@ -747,17 +1249,26 @@ impl ValorantSimulation {
             }
         }
 
-        self.advance_time(500); // Each tick is 500ms
+        self.advance_time(TICK_DURATION_MS);
         Ok(())
     }
 
     fn advance_buy_phase(&mut self, round_number: u8) -> Result<(), String> {
         // Check if we need to start the buy phase (first time entering this phase)
-        let should_start_buy_phase = self.events.is_empty()
-            || !matches!(self.events.last(), Some(GameEvent::BuyPhaseStart { .. }))
-            || matches!(self.events.last(), Some(GameEvent::RoundEnd { .. }));
+        // Look back only as far as the previous round so a SideSwap logged after
+        // BuyPhaseStart doesn't restart the phase every tick
+        let should_start_buy_phase = !self
+            .events
+            .iter()
+            .rev()
+            .take_while(|e| !matches!(e, GameEvent::RoundEnd { .. }))
+            .any(|e| matches!(e, GameEvent::BuyPhaseStart { .. }));
 
         if should_start_buy_phase {
+            // Snapshot before anything in the round happens so rewinds replay it in full
+            let snapshot = self.snapshot(format!("Round {} start", round_number));
+            self.round_snapshots.insert(round_number, snapshot);
+
             self.record_event(GameEvent::BuyPhaseStart {
                 timestamp: self.state.current_timestamp,
                 round_number,
@@ -765,34 +1276,22 @@ impl ValorantSimulation {
 
             // Set the buy phase start timestamp
             self.round_start_timestamp = self.state.current_timestamp;
+            self.ultimate_boosts.clear();
+            self.assist_contributions.clear();
 
-            // Reset players for round
+            // Reset players for round. Whoever died last round loses their gear
             for player in self.players.values_mut() {
+                if !player.survived_round() {
+                    player.lose_gear();
+                }
                 player.reset_for_round();
             }
 
-            // Handle side swaps
-            if round_number == 13 {
-                for player in self.players.values_mut() {
-                    player.team = match player.team {
-                        Team::Attackers => Team::Defenders,
-                        Team::Defenders => Team::Attackers,
-                    };
-                    player.current_credits = 800;
-                    player.current_loadout = PlayerLoadout {
-                        primary_weapon: None,
-                        secondary_weapon: Weapon::Classic,
-                        armor: ArmorType::None,
-                        abilities_purchased: Vec::new(),
-                    };
-                }
-                self.loss_streaks.insert(Team::Attackers, 0);
-                self.loss_streaks.insert(Team::Defenders, 0);
-
-                self.record_event(GameEvent::SideSwap {
-                    timestamp: self.state.current_timestamp,
-                    round_number,
-                });
+            // Sides swap at half time and before every overtime round
+            if round_number == HALF_ROUNDS + 1 {
+                self.swap_sides(round_number, self.balance.economy.starting_credits);
+            } else if round_number > REGULATION_ROUNDS {
+                self.swap_sides(round_number, self.state.overtime.round_credits);
             }
         }
 
@@ -800,8 +1299,8 @@ impl ValorantSimulation {
         let elapsed_time = self.state.current_timestamp - self.round_start_timestamp;
 
         // Simulate buying logic at the start of buy phase (after 1 second to allow setup)
-        if (1000..=1500).contains(&elapsed_time) {
-            self.simulate_player_purchases();
+        if (1000..=1500).contains(&elapsed_time) && !self.team_buys_made() {
+            self.simulate_player_purchases(round_number);
         }
 
         // End buy phase after 30 seconds
@@ -818,8 +1317,8 @@ impl ValorantSimulation {
             self.spike_planted = false;
             self.spike_defused = false;
             self.round_start_timestamp = self.state.current_timestamp;
-            self.round_timer_ms = 100_000;
-            self.spike_timer_ms = 45_000;
+            self.round_timer_ms = ROUND_TIME_MS;
+            self.spike_timer_ms = SPIKE_TIME_MS;
 
             self.record_event(GameEvent::RoundStart {
                 timestamp: self.state.current_timestamp,
@@ -827,14 +1326,19 @@ impl ValorantSimulation {
                 attacker_credits_start: self
                     .players
                     .values()
-                    .find(|p| p.team == Team::Attackers)
+                    .filter(|p| p.team == Team::Attackers)
+                    .min_by_key(|p| p.id)
                     .map_or(0, |p| p.current_credits),
                 defender_credits_start: self
                     .players
                     .values()
-                    .find(|p| p.team == Team::Defenders)
+                    .filter(|p| p.team == Team::Defenders)
+                    .min_by_key(|p| p.id)
                     .map_or(0, |p| p.current_credits),
             });
+
+            self.assign_round_positions();
+            self.simulate_orb_pickups();
         }
 
         Ok(())
@@ -884,28 +1388,41 @@ impl ValorantSimulation {
             return Ok(());
         }
 
+        self.update_positions();
+        let site = self.attack_site;
+        let attackers_on_site = self.players_at_site(&alive_attackers, site);
+        let defenders_on_site = self.players_at_site(&alive_defenders, site);
+
         // Spike mechanics
         if !self.spike_planted {
-            // 15% chance per tick after 30 seconds to plant spike
-            if self.state.current_timestamp - self.round_start_timestamp > 30_000 {
-                let mut rng = rand::rng();
-                if rng.random::<f32>() < 0.15 {
-                    let planter_id = alive_attackers[rng.random_range(0..alive_attackers.len())];
-                    self.record_event(GameEvent::SpikePlant {
-                        timestamp: self.state.current_timestamp,
-                        planter_id,
-                    });
-                    self.award_spike_plant_bonus(planter_id);
-                    self.spike_planted = true;
-                    self.state.phase = SimulationPhase::RoundActive {
-                        round_number,
-                        spike_planted: true,
-                    };
-                }
-            }
-        } else {
-            self.spike_timer_ms = self.spike_timer_ms.saturating_sub(500);
-            if self.spike_timer_ms <= 0 {
+            // Only attackers standing on their site can plant, and much less
+            // often while defenders still hold it
+            let plant_chance = if defenders_on_site.is_empty() {
+                0.15
+            } else {
+                0.03
+            };
+            if !attackers_on_site.is_empty()
+                && self.rng.random::<f32>() < plant_chance
+                && !self.smoke_off_plant(&alive_defenders)
+            {
+                let planter_id =
+                    attackers_on_site[self.rng.random_range(0..attackers_on_site.len())];
+                self.spike_planted = true;
+                self.record_event(GameEvent::SpikePlant {
+                    timestamp: self.state.current_timestamp,
+                    planter_id,
+                    site,
+                });
+                self.award_spike_plant_bonus(planter_id);
+                self.state.phase = SimulationPhase::RoundActive {
+                    round_number,
+                    spike_planted: true,
+                };
+            }
+        } else {
+            self.spike_timer_ms = self.spike_timer_ms.saturating_sub(500);
+            if self.spike_timer_ms <= 0 {
                 self.end_round(
                     round_number,
                     Team::Attackers,
@@ -914,17 +1431,22 @@ impl ValorantSimulation {
                 return Ok(());
             }
 
-            // 5% chance per tick for defuse attempt
-            let mut rng = rand::rng();
-            if !alive_defenders.is_empty() && rng.random::<f32>() < 0.05 {
-                let defuser_id = alive_defenders[rng.random_range(0..alive_defenders.len())];
+            // Defusing needs a defender on the spike, and rarely works under fire
+            let defuse_chance = if attackers_on_site.is_empty() {
+                0.05
+            } else {
+                0.01
+            };
+            if !defenders_on_site.is_empty() && self.rng.random::<f32>() < defuse_chance {
+                let defuser_id =
+                    defenders_on_site[self.rng.random_range(0..defenders_on_site.len())];
                 self.record_event(GameEvent::SpikeDefuse {
                     timestamp: self.state.current_timestamp,
                     defuser_id,
                     successful: true,
                 });
                 if let Some(defuser) = self.players.get_mut(&defuser_id) {
-                    defuser.ultimate_points += 1;
+                    defuser.add_ultimate_points(1);
                 }
                 self.spike_defused = true;
                 self.end_round(round_number, Team::Defenders, RoundEndReason::SpikeDefused);
@@ -932,11 +1454,21 @@ impl ValorantSimulation {
             }
         }
 
-        // Combat simulation
-        if !alive_attackers.is_empty() && !alive_defenders.is_empty() {
-            self.simulate_combat(&alive_attackers, &alive_defenders);
+        self.simulate_ultimates(&Team::Attackers);
+        self.simulate_ultimates(&Team::Defenders);
+
+        // Combat simulation: fights happen where the two teams meet, with the
+        // odd pick elsewhere on the map
+        let attackers_in_contact = self.attackers_in_contact(&alive_attackers, site);
+        if !attackers_in_contact.is_empty() && !defenders_on_site.is_empty() {
+            self.simulate_combat(&attackers_in_contact, &defenders_on_site, site);
+        } else if self.rng.random::<f32>() < SKIRMISH_CHANCE {
+            self.simulate_combat(&alive_attackers, &alive_defenders, None);
         }
 
+        self.simulate_heals(&Team::Attackers);
+        self.simulate_heals(&Team::Defenders);
+
         // Time expiration
         if !self.spike_planted && self.round_timer_ms <= 0 {
             self.end_round(round_number, Team::Defenders, RoundEndReason::TimeExpired);
@@ -945,6 +1477,36 @@ impl ValorantSimulation {
         Ok(())
     }
 
+    /// Moves every player to the other side with a fresh loadout and `credits`.
+    /// Scores and loss streaks move with the teams.
+    fn swap_sides(&mut self, round_number: u8, credits: u32) {
+        for player in self.players.values_mut() {
+            player.team = match player.team {
+                Team::Attackers => Team::Defenders,
+                Team::Defenders => Team::Attackers,
+            };
+            player.current_credits = credits;
+            player.current_loadout = PlayerLoadout {
+                primary_weapon: None,
+                secondary_weapon: Weapon::Classic,
+                armor: ArmorType::None,
+                abilities_purchased: Vec::new(),
+            };
+            player.reset_for_round();
+        }
+        std::mem::swap(
+            &mut self.state.attacker_score,
+            &mut self.state.defender_score,
+        );
+        self.loss_streaks.insert(Team::Attackers, 0);
+        self.loss_streaks.insert(Team::Defenders, 0);
+
+        self.record_event(GameEvent::SideSwap {
+            timestamp: self.state.current_timestamp,
+            round_number,
+        });
+    }
+
     fn advance_round_end(&mut self, round_number: u8, _winner: Team) -> Result<(), String> {
         // Calculate elapsed time since the round ended
         let round_end_timestamp = match self
@@ -984,7 +1546,18 @@ impl ValorantSimulation {
 
     fn end_round(&mut self, round_number: u8, winner: Team, reason: RoundEndReason) {
         // Award round-end credits
-        self.calculate_round_rewards(&winner, &reason, self.spike_planted);
+        self.calculate_round_rewards(&RoundOutcome {
+            winner: winner.clone(),
+            reason: reason.clone(),
+            spike_planted: self.spike_planted,
+        });
+
+        // Losing a round is worth an ultimate point
+        for player in self.players.values_mut() {
+            if player.team != winner {
+                player.add_ultimate_points(1);
+            }
+        }
 
         // Update scores
         if winner == Team::Attackers {
@@ -1008,127 +1581,386 @@ impl ValorantSimulation {
 
     fn check_match_end_conditions(&mut self) -> bool {
         const WIN_SCORE_REGULAR: u8 = 13;
-        const WIN_MARGIN_OVERTIME: u8 = 2;
-
-        let current_diff =
-            (self.state.attacker_score as i16 - self.state.defender_score as i16).abs();
+        const WIN_MARGIN: u8 = 2;
+
+        let attacker_score = self.state.attacker_score;
+        let defender_score = self.state.defender_score;
+        let leader = match attacker_score.cmp(&defender_score) {
+            std::cmp::Ordering::Greater => Some(Team::Attackers),
+            std::cmp::Ordering::Less => Some(Team::Defenders),
+            std::cmp::Ordering::Equal => None,
+        };
+        let margin = attacker_score.abs_diff(defender_score);
 
-        if self.state.overtime_active {
-            if current_diff >= WIN_MARGIN_OVERTIME as i16 {
-                let winning_team = if self.state.attacker_score > self.state.defender_score {
-                    Team::Attackers
-                } else {
-                    Team::Defenders
-                };
-                self.record_event(GameEvent::MatchEnd {
-                    timestamp: self.state.current_timestamp,
-                    winning_team: winning_team.clone(),
-                    score_attackers: self.state.attacker_score,
-                    score_defenders: self.state.defender_score,
-                });
-                self.state.phase = SimulationPhase::MatchEnd {
-                    winner: winning_team,
-                    final_score: (self.state.attacker_score, self.state.defender_score),
-                };
+        if !self.state.overtime_active {
+            if attacker_score.max(defender_score) >= WIN_SCORE_REGULAR && margin >= WIN_MARGIN {
+                self.end_match(leader);
                 return true;
             }
-        } else if self.state.attacker_score >= WIN_SCORE_REGULAR
-            || self.state.defender_score >= WIN_SCORE_REGULAR
-        {
-            if current_diff >= 2 {
-                let winning_team = if self.state.attacker_score > self.state.defender_score {
-                    Team::Attackers
-                } else {
-                    Team::Defenders
-                };
-                self.record_event(GameEvent::MatchEnd {
-                    timestamp: self.state.current_timestamp,
-                    winning_team: winning_team.clone(),
-                    score_attackers: self.state.attacker_score,
-                    score_defenders: self.state.defender_score,
-                });
-                self.state.phase = SimulationPhase::MatchEnd {
-                    winner: winning_team,
-                    final_score: (self.state.attacker_score, self.state.defender_score),
-                };
-                return true;
-            } else if self.state.attacker_score == 12 && self.state.defender_score == 12 {
+            if attacker_score == HALF_ROUNDS && defender_score == HALF_ROUNDS {
                 self.state.overtime_active = true;
             }
+            return false;
+        }
+
+        if margin >= WIN_MARGIN {
+            self.end_match(leader);
+            return true;
         }
 
+        let overtime_rounds = self.state.current_round.saturating_sub(REGULATION_ROUNDS);
+        // Sudden death: past the first overtime pair, any lead wins
+        if self.state.overtime.sudden_death && overtime_rounds > 2 && leader.is_some() {
+            self.end_match(leader);
+            return true;
+        }
+
+        // A pair of overtime rounds just ended level
+        let pair_ended_level = overtime_rounds.is_multiple_of(2) && leader.is_none();
+        if pair_ended_level
+            && !self.state.overtime.sudden_death
+            && self.state.overtime.tiebreak == TiebreakMode::Ranked
+            && self.hold_draw_vote()
+        {
+            self.end_match(None);
+            return true;
+        }
         false
     }
 
+    /// Every player votes on ending a level ranked match as a draw. Returns
+    /// true if the vote passed.
+    fn hold_draw_vote(&mut self) -> bool {
+        let mut player_ids: Vec<u32> = self.players.keys().copied().collect();
+        player_ids.sort_unstable();
+
+        let votes_for = player_ids
+            .iter()
+            .filter(|_| self.rng.random::<f32>() < DRAW_VOTE_CHANCE)
+            .count();
+        let votes_against = player_ids.len() - votes_for;
+        let passed = !player_ids.is_empty()
+            && votes_for as f32 >= player_ids.len() as f32 * DRAW_VOTE_MAJORITY;
+
+        self.record_event(GameEvent::DrawVote {
+            timestamp: self.state.current_timestamp,
+            round_number: self.state.current_round,
+            votes_for: votes_for as u8,
+            votes_against: votes_against as u8,
+            passed,
+        });
+        passed
+    }
+
+    /// Ends the match with `winner`, or as a draw if there is none.
+    fn end_match(&mut self, winner: Option<Team>) {
+        self.record_event(GameEvent::MatchEnd {
+            timestamp: self.state.current_timestamp,
+            winning_team: winner.clone(),
+            score_attackers: self.state.attacker_score,
+            score_defenders: self.state.defender_score,
+        });
+        self.state.phase = SimulationPhase::MatchEnd {
+            winner,
+            final_score: (self.state.attacker_score, self.state.defender_score),
+        };
+    }
+
     pub fn get_alive_players_on_team(&self, team: &Team) -> Vec<&Player> {
-        self.players
+        let mut alive: Vec<&Player> = self
+            .players
             .values()
             .filter(|p| p.team == *team && p.is_alive)
-            .collect()
+            .collect();
+        // HashMap order is not stable between runs; sort so seeded picks are reproducible
+        alive.sort_by_key(|p| p.id);
+        alive
     }
 
-    fn calculate_loadout_cost(&self, weapon: &Weapon, armor: &ArmorType) -> u32 {
-        let weapon_cost = self.weapon_stats[weapon].price;
-        let armor_cost = match armor {
-            ArmorType::Heavy => 1000,
-            ArmorType::Light => 400,
-            ArmorType::None => 0,
+    /// True once both teams have bought this round.
+    fn team_buys_made(&self) -> bool {
+        self.events
+            .iter()
+            .rev()
+            .take_while(|e| !matches!(e, GameEvent::BuyPhaseStart { .. }))
+            .any(|e| matches!(e, GameEvent::TeamBuy { .. }))
+    }
+
+    fn team_buy_context(&self, team: &Team, round_number: u8) -> TeamBuyContext {
+        let credits = self
+            .team_player_ids(team)
+            .iter()
+            .map(|id| self.players[id].current_credits)
+            .collect();
+        // RoundEnd names the winning side, which is the other team if sides
+        // have swapped since
+        let mut swapped = false;
+        let won_last_round = self.events.iter().rev().find_map(|e| match e {
+            GameEvent::SideSwap { .. } => {
+                swapped = !swapped;
+                None
+            }
+            GameEvent::RoundEnd { winning_team, .. } => Some((winning_team == team) != swapped),
+            _ => None,
+        });
+
+        let (own_score, opponent_score) = match team {
+            Team::Attackers => (self.state.attacker_score, self.state.defender_score),
+            Team::Defenders => (self.state.defender_score, self.state.attacker_score),
+        };
+        let facing_elimination = if self.state.overtime_active {
+            opponent_score > own_score
+        } else {
+            opponent_score == 12 && own_score < 12
         };
-        weapon_cost + armor_cost
+
+        TeamBuyContext {
+            credits,
+            loss_streak: self.loss_streaks.get(team).copied().unwrap_or(0),
+            won_last_round: won_last_round.unwrap_or(false),
+            pistol_round: round_number == 1 || round_number == 13,
+            after_pistol_round: round_number == 2 || round_number == 14,
+            last_round_of_half: round_number == 12,
+            facing_elimination,
+        }
     }
 
-    fn simulate_player_purchases(&mut self) {
-        // Pre-calculate all costs to avoid borrowing conflicts
-        let operator_heavy_cost = self.calculate_loadout_cost(&Weapon::Operator, &ArmorType::Heavy);
-        let vandal_heavy_cost = self.calculate_loadout_cost(&Weapon::Vandal, &ArmorType::Heavy);
-        let spectre_cost = self.calculate_loadout_cost(&Weapon::Spectre, &ArmorType::None);
-        let spectre_light_cost = self.calculate_loadout_cost(&Weapon::Spectre, &ArmorType::Light);
-        let sheriff_cost = self.calculate_loadout_cost(&Weapon::Sheriff, &ArmorType::None);
+    fn team_player_ids(&self, team: &Team) -> Vec<u32> {
+        let mut ids: Vec<u32> = self
+            .players
+            .values()
+            .filter(|p| p.team == *team)
+            .map(|p| p.id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
 
-        for player in self.players.values_mut() {
-            // Reset loadout if they died (don't carry over equipment)
-            if !player.survived_round() {
-                player.current_loadout = PlayerLoadout {
-                    primary_weapon: None,
-                    secondary_weapon: Weapon::Classic,
-                    armor: ArmorType::None,
-                    abilities_purchased: Vec::new(),
-                };
+    /// Each team decides how to buy from its economy as a whole, then every
+    /// player buys to match that decision.
+    fn simulate_player_purchases(&mut self, round_number: u8) {
+        for team in [Team::Attackers, Team::Defenders] {
+            let context = self.team_buy_context(&team, round_number);
+            let buy_type = decide_team_buy(&context, &self.balance);
+            let team_credits = context.credits.iter().sum();
+
+            let drops = self.buy_for_team(&team, buy_type);
+
+            self.record_event(GameEvent::TeamBuy {
+                timestamp: self.state.current_timestamp,
+                round_number,
+                team,
+                buy_type,
+                team_credits,
+                drops,
+            });
+        }
+    }
+
+    fn buy_for_team(&mut self, team: &Team, buy_type: BuyType) -> Vec<WeaponDrop> {
+        let player_ids = self.team_player_ids(team);
+        let drops = if buy_type == BuyType::FullBuy {
+            self.drop_rifles(&player_ids)
+        } else {
+            Vec::new()
+        };
+
+        let options: &[(Weapon, ArmorType)] = match buy_type {
+            BuyType::Pistol => &[(Weapon::Classic, ArmorType::Light)],
+            BuyType::Eco => &[],
+            BuyType::Bonus => &[(Weapon::Sheriff, ArmorType::None)],
+            BuyType::HalfBuy => &[
+                (Weapon::Spectre, ArmorType::Light),
+                (Weapon::Stinger, ArmorType::Light),
+                (Weapon::Sheriff, ArmorType::None),
+            ],
+            BuyType::Force | BuyType::FullBuy => &[
+                (Weapon::Vandal, ArmorType::Heavy),
+                (Weapon::Spectre, ArmorType::Light),
+                (Weapon::Spectre, ArmorType::None),
+                (Weapon::Sheriff, ArmorType::None),
+            ],
+        };
+        let mut team_has_operator = player_ids
+            .iter()
+            .any(|id| self.players[id].current_loadout.primary_weapon == Some(Weapon::Operator));
+
+        for player_id in player_ids {
+            let Some(player) = self.players.get_mut(&player_id) else {
+                continue;
+            };
+            // One player on a full buy picks up the Operator if they can afford it
+            if buy_type == BuyType::FullBuy
+                && !team_has_operator
+                && player.current_loadout.primary_weapon.is_none()
+                && player
+                    .buy_first_affordable(&[(Weapon::Operator, ArmorType::Heavy)], &self.balance)
+            {
+                team_has_operator = true;
             }
+            player.buy_first_affordable(options, &self.balance);
 
-            // Dynamic buying strategy with pre-calculated costs
-            if player.current_credits >= operator_heavy_cost {
-                player.current_loadout.primary_weapon = Some(Weapon::Operator);
-                player.current_loadout.armor = ArmorType::Heavy;
-                player.current_credits -= operator_heavy_cost;
-            } else if player.current_credits >= vandal_heavy_cost {
-                player.current_loadout.primary_weapon = Some(Weapon::Vandal);
-                player.current_loadout.armor = ArmorType::Heavy;
-                player.current_credits -= vandal_heavy_cost;
-            } else if player.current_credits >= spectre_light_cost {
-                player.current_loadout.primary_weapon = Some(Weapon::Spectre);
-                player.current_loadout.armor = ArmorType::Light;
-                player.current_credits -= spectre_light_cost;
-            } else if player.current_credits >= spectre_cost {
-                player.current_loadout.primary_weapon = Some(Weapon::Spectre);
-                player.current_credits -= spectre_cost;
-            } else if player.current_credits >= sheriff_cost {
-                player.current_loadout.secondary_weapon = Weapon::Sheriff;
-                player.current_credits -= sheriff_cost;
+            // Saving rounds only take the free charges
+            if matches!(buy_type, BuyType::Eco | BuyType::Bonus) {
+                player.refill_free_abilities();
+            } else {
+                player.buy_abilities();
+            }
+            player.current_armor = player.current_loadout.armor.points();
+        }
+        drops
+    }
+
+    /// On a full buy, players who can't afford a rifle get one bought for them
+    /// by the richest teammate who can still full buy themselves afterwards.
+    fn drop_rifles(&mut self, player_ids: &[u32]) -> Vec<WeaponDrop> {
+        let full_cost = full_buy_cost(&self.balance);
+        let rifle_price = self.balance.weapons[&Weapon::Vandal].price;
+        let heavy_cost = self.balance.armor_costs.cost(&ArmorType::Heavy);
+
+        let mut drops = Vec::new();
+        for &recipient_id in player_ids {
+            let recipient = &self.players[&recipient_id];
+            if recipient.current_loadout.primary_weapon.is_some()
+                || recipient.current_credits >= full_cost
+            {
+                continue;
+            }
+
+            let buyer_id = player_ids
+                .iter()
+                .filter(|id| **id != recipient_id)
+                .map(|id| &self.players[id])
+                .filter(|buyer| {
+                    let own_cost = match buyer.current_loadout.primary_weapon {
+                        Some(_) if buyer.current_loadout.armor == ArmorType::Heavy => 0,
+                        Some(_) => heavy_cost,
+                        None => full_cost,
+                    };
+                    buyer.current_credits >= own_cost + rifle_price
+                })
+                // Richest first, lowest id on ties
+                .max_by_key(|buyer| (buyer.current_credits, std::cmp::Reverse(buyer.id)))
+                .map(|buyer| buyer.id);
+            let Some(buyer_id) = buyer_id else {
+                continue;
+            };
+
+            if let Some(buyer) = self.players.get_mut(&buyer_id) {
+                buyer.current_credits -= rifle_price;
+            }
+            if let Some(recipient) = self.players.get_mut(&recipient_id) {
+                recipient.current_loadout.primary_weapon = Some(Weapon::Vandal);
             }
+            drops.push(WeaponDrop {
+                buyer_id,
+                recipient_id,
+                weapon: Weapon::Vandal,
+            });
+        }
+        drops
+    }
+
+    fn record_ability_use(&mut self, player_id: u32, ability: &Ability) {
+        self.record_event(GameEvent::AbilityUsed {
+            timestamp: self.state.current_timestamp,
+            player_id,
+            ability_name: ability.name.to_string(),
+        });
+    }
+
+    /// Lets a player going into a duel use one of their abilities, returning
+    /// the win chance it buys them. Better utility players use theirs more
+    /// often and to better effect.
+    fn use_duel_utility(&mut self, player_id: u32, opponent_id: u32, defending: bool) -> f32 {
+        let Some(player) = self.players.get(&player_id) else {
+            return 0.0;
+        };
+        let util = player.skills.util;
+        let Some(ability) = player.held_ability(|a| a.effect.duel_bonus(defending).is_some())
+        else {
+            return 0.0;
+        };
+        if self.rng.random::<f32>() >= util * UTILITY_USE_CHANCE {
+            return 0.0;
+        }
+
+        if let Some(player) = self.players.get_mut(&player_id) {
+            player.consume_ability(ability.name);
+        }
+        self.record_ability_use(player_id, ability);
+        if ability.effect.debuffs_target() {
+            self.add_assist_contribution(player_id, opponent_id);
+        }
+        let weight = self.balance.combat.utility_weight;
+        ability.effect.duel_bonus(defending).unwrap_or(0.0) * (1.0 - weight + util * weight)
+    }
+
+    /// A healer patches up the most injured teammate still alive.
+    fn simulate_heals(&mut self, team: &Team) {
+        let alive = self.get_alive_players_on_team(team);
+        let Some(patient_id) = alive
+            .iter()
+            .filter(|p| p.current_health < 100)
+            .min_by_key(|p| p.current_health)
+            .map(|p| p.id)
+        else {
+            return;
+        };
+        let Some((healer_id, util, ability)) = alive.iter().find_map(|p| {
+            p.held_ability(|a| a.effect == AbilityEffect::Heal)
+                .map(|ability| (p.id, p.skills.util, ability))
+        }) else {
+            return;
+        };
+
+        if self.rng.random::<f32>() >= util * UTILITY_USE_CHANCE {
+            return;
+        }
+        if let Some(healer) = self.players.get_mut(&healer_id) {
+            healer.consume_ability(ability.name);
+        }
+        if let Some(patient) = self.players.get_mut(&patient_id) {
+            patient.heal(HEAL_AMOUNT);
         }
+        self.record_ability_use(healer_id, ability);
     }
 
-    fn simulate_combat(&mut self, alive_attackers: &[u32], alive_defenders: &[u32]) {
+    /// Defenders answer a plant attempt with a smoke, stalling it for this tick.
+    /// Returns true if the plant was delayed.
+    fn smoke_off_plant(&mut self, alive_defenders: &[u32]) -> bool {
+        let Some((smoker_id, util, ability)) = alive_defenders.iter().find_map(|id| {
+            let player = self.players.get(id)?;
+            player
+                .held_ability(|a| a.effect == AbilityEffect::Smoke)
+                .map(|ability| (*id, player.skills.util, ability))
+        }) else {
+            return false;
+        };
+
+        if self.rng.random::<f32>() >= util * UTILITY_USE_CHANCE {
+            return false;
+        }
+        if let Some(smoker) = self.players.get_mut(&smoker_id) {
+            smoker.consume_ability(ability.name);
+        }
+        self.record_ability_use(smoker_id, ability);
+        true
+    }
+
+    fn simulate_combat(
+        &mut self,
+        alive_attackers: &[u32],
+        alive_defenders: &[u32],
+        site: Option<Site>,
+    ) {
         // Safety check: ensure both teams have alive players
         if alive_attackers.is_empty() || alive_defenders.is_empty() {
             return;
         }
 
-        let mut rng = rand::rng();
-
-        let attacker_id = alive_attackers[rng.random_range(0..alive_attackers.len())];
-        let defender_id = alive_defenders[rng.random_range(0..alive_defenders.len())];
+        let attacker_id = alive_attackers[self.rng.random_range(0..alive_attackers.len())];
+        let defender_id = alive_defenders[self.rng.random_range(0..alive_defenders.len())];
 
         // Double-check both players are still alive
         let attacker_still_alive = self.players.get(&attacker_id).is_some_and(|p| p.is_alive);
@@ -1145,6 +1977,7 @@ impl ValorantSimulation {
         let attacker_weapon = attacker_player_data
             .current_loadout
             .primary_weapon
+            .clone()
             .unwrap_or(
                 attacker_player_data
                     .current_loadout
@@ -1154,6 +1987,7 @@ impl ValorantSimulation {
         let defender_weapon = defender_player_data
             .current_loadout
             .primary_weapon
+            .clone()
             .unwrap_or(
                 defender_player_data
                     .current_loadout
@@ -1161,147 +1995,227 @@ impl ValorantSimulation {
                     .clone(),
             );
 
-        // Calculate weapon effectiveness multipliers
-        let attacker_weapon_effectiveness = self.calculate_weapon_effectiveness(&attacker_weapon);
-        let defender_weapon_effectiveness = self.calculate_weapon_effectiveness(&defender_weapon);
-
-        // Enhanced combat calculation with weapon stats
-        let attacker_base_skill =
-            attacker_player_data.skills.aim * 0.7 + attacker_player_data.skills.hs * 0.3;
-        let defender_base_skill =
-            defender_player_data.skills.aim * 0.7 + defender_player_data.skills.hs * 0.3;
-
-        let attacker_effective_skill = attacker_base_skill * attacker_weapon_effectiveness;
-        let defender_effective_skill = defender_base_skill * defender_weapon_effectiveness;
-
-        // Fire rate advantage
-        let attacker_fire_rate = self.weapon_stats[&attacker_weapon].fire_rate;
-        let defender_fire_rate = self.weapon_stats[&defender_weapon].fire_rate;
-
-        let fire_rate_advantage = (attacker_fire_rate / defender_fire_rate).clamp(0.5, 2.0);
-
+        // Gunfight skill decides how far the duel leans one way
+        let combat = &self.balance.combat;
+        let attacker_skill = gunfight_skill(&attacker_player_data.skills, combat);
+        let defender_skill = gunfight_skill(&defender_player_data.skills, combat);
         let mut attacker_win_chance =
-            0.5 + (attacker_effective_skill - defender_effective_skill) * 0.3;
-        attacker_win_chance *= fire_rate_advantage;
+            0.5 + (attacker_skill - defender_skill) * combat.skill_gap_scale;
+
+        // Utility used going into the fight, plus any ultimate still in effect
+        attacker_win_chance += self.use_duel_utility(attacker_id, defender_id, false);
+        attacker_win_chance -= self.use_duel_utility(defender_id, attacker_id, true);
+        attacker_win_chance += self.ultimate_boosts.get(&Team::Attackers).unwrap_or(&0.0);
+        attacker_win_chance -= self.ultimate_boosts.get(&Team::Defenders).unwrap_or(&0.0);
+
+        // Fights on a site are taken at its choke, where defenders hold the angles
+        // and attackers have to peek them. Skirmishes elsewhere happen anywhere
+        // from 10 to 50 meters and either side may be the one peeking
+        let layout = site.and_then(|site| self.state.map.site(site));
+        let (min_range, max_range) = layout.map_or((10.0, 50.0), |l| l.choke_range_m);
+        attacker_win_chance -= layout.map_or(0.0, |l| l.defender_advantage);
         attacker_win_chance = attacker_win_chance.clamp(0.1f32, 0.9f32);
 
-        // Determine hit location and headshot
-        let is_attacker_headshot = rng.random::<f32>() < attacker_player_data.skills.hs;
-        let is_defender_headshot = rng.random::<f32>() < defender_player_data.skills.hs;
+        let engagement_range = self.rng.random_range(min_range..max_range);
+        let attacker_peeks = layout.is_some() || self.rng.random::<bool>();
 
-        let hit_body_part = if is_attacker_headshot || is_defender_headshot {
-            BodyPart::Head
-        } else if rng.random::<f32>() < 0.7 {
-            BodyPart::Body
-        } else {
-            BodyPart::Legs
-        };
+        let attacker = self.duelist(
+            &attacker_player_data,
+            attacker_weapon,
+            attacker_win_chance,
+            attacker_peeks,
+        );
+        let defender = self.duelist(
+            &defender_player_data,
+            defender_weapon,
+            1.0 - attacker_win_chance,
+            !attacker_peeks,
+        );
+        self.simulate_duel([attacker, defender], engagement_range);
+    }
 
-        // Simulate engagement range (10-50 meters)
-        let engagement_range = rng.random_range(10.0..50.0);
+    /// Sets up one side of a duel. `edge` is the side's overall advantage going
+    /// in (0.5 is an even fight): it buys a faster first shot and better accuracy.
+    /// A player peeking an angle also gets a head start for moving well.
+    fn duelist(&mut self, player: &Player, weapon: Weapon, edge: f32, peeking: bool) -> Duelist {
+        let stats = &self.balance.weapons[&weapon];
+        let shot_interval_ms = 1000.0 / stats.fire_rate;
+        let rounds_left = stats.magazine_size;
+
+        let aim = player.skills.aim.clamp(0.0, 1.0);
+        let movement = player.skills.movement.clamp(0.0, 1.0);
+        let mut reaction_ms = DUEL_REACTION_MS * (1.5 - edge) + self.rng.random_range(0.0..100.0);
+        if peeking {
+            reaction_ms = (reaction_ms - self.balance.combat.peek_advantage_ms * movement).max(0.0);
+        }
 
-        if rng.random::<f32>() < attacker_win_chance {
-            // Attacker wins
-            let damage = self.calculate_weapon_damage(
-                &attacker_weapon,
-                &defender_player_data.current_loadout.armor,
-                hit_body_part,
-                engagement_range,
-            );
+        Duelist {
+            player_id: player.id,
+            weapon,
+            accuracy: ((0.2 + aim * 0.6) * (0.5 + edge)).clamp(0.05, 0.95),
+            headshot_rate: player.skills.hs.clamp(0.0, 1.0),
+            movement,
+            next_shot_ms: reaction_ms,
+            shot_interval_ms,
+            rounds_left,
+        }
+    }
 
-            if let Some(victim) = self.players.get_mut(&defender_id) {
-                victim.take_damage(damage);
+    /// Plays out a duel shot by shot: whoever is due to fire next shoots, each
+    /// hit lands on a rolled body part and is logged as `Damage`, until someone
+    /// dies, both magazines run dry or the fight drags on too long.
+    fn simulate_duel(&mut self, mut sides: [Duelist; 2], range_meters: f32) {
+        loop {
+            let shooter = if sides[1].rounds_left > 0
+                && (sides[0].rounds_left == 0 || sides[1].next_shot_ms < sides[0].next_shot_ms)
+            {
+                1
+            } else {
+                0
+            };
+            let target = 1 - shooter;
+            if sides[shooter].rounds_left == 0 || sides[shooter].next_shot_ms > DUEL_MAX_MS {
+                return;
             }
 
-            // Only record kill if both killer is alive and victim actually died
-            if let (Some(killer), Some(victim)) = (
-                self.players.get(&attacker_id),
-                self.players.get(&defender_id),
-            ) {
-                if killer.is_alive && !victim.is_alive {
-                    self.record_event(GameEvent::Kill {
-                        timestamp: self.state.current_timestamp,
-                        killer_id: attacker_id,
-                        victim_id: defender_id,
-                        weapon: attacker_weapon,
-                        is_headshot: is_attacker_headshot,
-                    });
-                    self.award_kill_bonus(attacker_id);
-                }
+            sides[shooter].rounds_left -= 1;
+            sides[shooter].next_shot_ms += sides[shooter].shot_interval_ms;
+            // A target strafing well is harder to hit
+            let hit_chance = sides[shooter].accuracy
+                * (1.0 - self.balance.combat.strafe_penalty * sides[target].movement);
+            if self.rng.random::<f32>() >= hit_chance {
+                continue;
             }
-        } else {
-            // Defender wins
-            let damage = self.calculate_weapon_damage(
-                &defender_weapon,
-                &attacker_player_data.current_loadout.armor,
-                hit_body_part,
-                engagement_range,
-            );
 
-            if let Some(victim) = self.players.get_mut(&attacker_id) {
-                victim.take_damage(damage);
-            }
+            let is_headshot = self.rng.random::<f32>() < sides[shooter].headshot_rate;
+            let body_part = if is_headshot {
+                BodyPart::Head
+            } else if self.rng.random::<f32>() < 0.7 {
+                BodyPart::Body
+            } else {
+                BodyPart::Legs
+            };
 
-            // Only record kill if both killer is alive and victim actually died
-            if let (Some(killer), Some(victim)) = (
-                self.players.get(&defender_id),
-                self.players.get(&attacker_id),
-            ) {
-                if killer.is_alive && !victim.is_alive {
-                    self.record_event(GameEvent::Kill {
-                        timestamp: self.state.current_timestamp,
-                        killer_id: defender_id,
-                        victim_id: attacker_id,
-                        weapon: defender_weapon,
-                        is_headshot: is_defender_headshot,
-                    });
-                    self.award_kill_bonus(defender_id);
+            let shooter_id = sides[shooter].player_id;
+            let target_id = sides[target].player_id;
+            // Armor only counts while it has points left
+            let Some(target_armor) = self.players.get(&target_id).map(|p| {
+                if p.current_armor > 0 {
+                    p.current_loadout.armor.clone()
+                } else {
+                    ArmorType::None
                 }
+            }) else {
+                return;
+            };
+            let weapon = &sides[shooter].weapon;
+            let raw_damage = self.calculate_weapon_damage(
+                weapon,
+                &ArmorType::None,
+                body_part.clone(),
+                range_meters,
+            );
+            let armored_damage =
+                self.calculate_weapon_damage(weapon, &target_armor, body_part, range_meters);
+            self.record_damage(
+                shooter_id,
+                target_id,
+                raw_damage,
+                armored_damage,
+                &sides[shooter].weapon.clone(),
+                is_headshot,
+            );
+
+            if self.players.get(&target_id).is_some_and(|p| !p.is_alive) {
+                let weapon = sides[shooter].weapon.clone();
+                self.record_kill(shooter_id, target_id, weapon, is_headshot);
+                return;
             }
         }
     }
 
-    fn calculate_round_rewards(
+    /// Applies a hit, logs it as a `Damage` event and remembers the shooter as
+    /// a contributor towards an assist on the victim.
+    fn record_damage(
         &mut self,
-        winning_team: &Team,
-        _reason: &RoundEndReason,
-        spike_planted: bool,
+        attacker_id: u32,
+        victim_id: u32,
+        raw_damage: u32,
+        armored_damage: u32,
+        weapon: &Weapon,
+        is_headshot: bool,
     ) {
-        // Award credits based on Valorant economy system
-        for player in self.players.values_mut() {
-            let mut credits_earned = 0;
+        let Some(victim) = self.players.get_mut(&victim_id) else {
+            return;
+        };
+        let dealt = victim.absorb_hit(raw_damage, armored_damage);
+        if dealt == 0 {
+            return;
+        }
 
-            if player.team == *winning_team {
-                // Win reward
-                credits_earned += 3000;
+        self.record_event(GameEvent::Damage {
+            timestamp: self.state.current_timestamp,
+            attacker_id,
+            victim_id,
+            amount: dealt,
+            weapon: weapon.clone(),
+            is_headshot,
+        });
+        self.add_assist_contribution(attacker_id, victim_id);
+    }
 
-                // Reset loss streak for winning team
-                self.loss_streaks.insert(player.team.clone(), 0);
-            } else {
-                // Loss reward with streak bonus
-                let loss_streak = self.loss_streaks.get(&player.team).unwrap_or(&0);
-                credits_earned += match loss_streak {
-                    0 => 1900, // First loss
-                    1 => 2400, // Second consecutive loss
-                    _ => 2900, // Third+ consecutive loss
-                };
+    fn add_assist_contribution(&mut self, contributor_id: u32, victim_id: u32) {
+        let contributors = self.assist_contributions.entry(victim_id).or_default();
+        if !contributors.contains(&contributor_id) {
+            contributors.push(contributor_id);
+        }
+    }
 
-                // Update loss streak
-                self.loss_streaks
-                    .insert(player.team.clone(), loss_streak + 1);
+    /// Records the kill, crediting an assist to every teammate of the killer who
+    /// damaged or debuffed the victim this round.
+    fn record_kill(&mut self, killer_id: u32, victim_id: u32, weapon: Weapon, is_headshot: bool) {
+        let killer_team = self.players.get(&killer_id).map(|p| p.team.clone());
+        let assister_ids = self
+            .assist_contributions
+            .remove(&victim_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|id| *id != killer_id)
+            .filter(|id| self.players.get(id).map(|p| p.team.clone()) == killer_team)
+            .collect();
 
-                // Survival bonus (if they survived a lost round)
-                if player.survived_round() {
-                    credits_earned = credits_earned.min(1000); // Cap at 1000 for survival
-                }
-            }
+        self.record_event(GameEvent::Kill {
+            timestamp: self.state.current_timestamp,
+            killer_id,
+            victim_id,
+            weapon,
+            is_headshot,
+            assister_ids,
+        });
+        self.award_kill_bonus(killer_id, victim_id);
+    }
 
-            // Spike plant bonus (300 credits per team member)
-            if spike_planted && player.team == Team::Attackers {
-                credits_earned += 300;
-            }
+    fn calculate_round_rewards(&mut self, outcome: &RoundOutcome) {
+        // Pay out against the streaks going into the round, then move them on
+        let loss_streaks = self.loss_streaks.clone();
+        let economy = &self.balance.economy;
+
+        for player in self.players.values_mut() {
+            let loss_streak = loss_streaks.get(&player.team).copied().unwrap_or(0);
+            let earned = round_credits(
+                &player.team,
+                player.survived_round(),
+                loss_streak,
+                outcome,
+                economy,
+            );
+            player.current_credits = add_credits(player.current_credits, earned, economy);
+        }
 
-            player.current_credits = (player.current_credits + credits_earned).min(9000);
+        for team in [Team::Attackers, Team::Defenders] {
+            let loss_streak = loss_streaks.get(&team).copied().unwrap_or(0);
+            self.loss_streaks
+                .insert(team.clone(), next_loss_streak(&team, loss_streak, outcome));
         }
     }
 
@@ -1312,7 +2226,7 @@ impl ValorantSimulation {
         body_part: BodyPart,
         range_meters: f32,
     ) -> u32 {
-        let stats = &self.weapon_stats[weapon];
+        let stats = &self.balance.weapons[weapon];
 
         let base_damage = match body_part {
             BodyPart::Head => match armor_type {
@@ -1332,54 +2246,239 @@ impl ValorantSimulation {
             },
         };
 
-        // Apply range penalties (simplified)
-        let damage_multiplier = match weapon {
-            Weapon::Phantom => {
-                if range_meters <= 15.0 {
-                    1.0
-                } else if range_meters <= 30.0 {
-                    0.85
-                } else {
-                    0.7
-                }
+        let damage_multiplier = stats.falloff_multiplier(range_meters);
+
+        (base_damage as f32 * damage_multiplier) as u32
+    }
+
+    fn award_kill_bonus(&mut self, killer_id: u32, victim_id: u32) {
+        let economy = &self.balance.economy;
+        if let Some(killer) = self.players.get_mut(&killer_id) {
+            killer.current_credits =
+                add_credits(killer.current_credits, economy.kill_reward, economy);
+            killer.add_ultimate_points(1);
+        }
+        // Dying also earns a point, as in Valorant
+        if let Some(victim) = self.players.get_mut(&victim_id) {
+            victim.add_ultimate_points(1);
+        }
+    }
+
+    fn award_spike_plant_bonus(&mut self, planter_id: u32) {
+        if let Some(planter) = self.players.get_mut(&planter_id) {
+            planter.add_ultimate_points(1);
+        }
+    }
+
+    /// Attackers pick a site to execute on and walk there from spawn, while
+    /// defenders spread across the sites. Easier-to-hold sites get hit less.
+    fn assign_round_positions(&mut self) {
+        let map = self.state.map;
+        let sites = map.sites();
+        if sites.is_empty() {
+            return;
+        }
+
+        let weights: Vec<f32> = sites.iter().map(|l| 1.0 / l.defender_advantage).collect();
+        let mut pick = self.rng.random::<f32>() * weights.iter().sum::<f32>();
+        let mut target = sites[sites.len() - 1];
+        for (layout, weight) in sites.iter().zip(&weights) {
+            if pick < *weight {
+                target = *layout;
+                break;
             }
-            Weapon::Spectre | Weapon::Stinger => {
-                if range_meters <= 20.0 {
-                    1.0
-                } else {
-                    0.75
-                }
+            pick -= weight;
+        }
+        self.attack_site = Some(target.site);
+
+        let now = self.state.current_timestamp;
+        let attacker_ids: Vec<u32> = self
+            .get_alive_players_on_team(&Team::Attackers)
+            .iter()
+            .map(|p| p.id)
+            .collect();
+        for id in attacker_ids {
+            let arrive_at = now + target.attacker_travel_ms + self.rng.random_range(0..4000);
+            if let Some(player) = self.players.get_mut(&id) {
+                player.position = Position::Moving {
+                    site: target.site,
+                    arrive_at,
+                };
+            }
+        }
+
+        let defender_ids: Vec<u32> = self
+            .get_alive_players_on_team(&Team::Defenders)
+            .iter()
+            .map(|p| p.id)
+            .collect();
+        let offset = self.rng.random_range(0..sites.len());
+        for (index, id) in defender_ids.into_iter().enumerate() {
+            if let Some(player) = self.players.get_mut(&id) {
+                player.position = Position::AtSite(sites[(index + offset) % sites.len()].site);
+            }
+        }
+    }
+
+    /// Moves players who have reached their destination onto it, and sends
+    /// defenders to the attacked site once attackers are on it or the spike is down.
+    fn update_positions(&mut self) {
+        let now = self.state.current_timestamp;
+        for player in self.players.values_mut() {
+            if let Position::Moving { site, arrive_at } = player.position
+                && arrive_at <= now
+            {
+                player.position = Position::AtSite(site);
             }
-            _ => 1.0, // No damage falloff for most weapons
+        }
+
+        let Some(site) = self.attack_site else {
+            return;
         };
+        let site_hit = self.spike_planted
+            || self.players.values().any(|p| {
+                p.team == Team::Attackers && p.is_alive && p.position == Position::AtSite(site)
+            });
+        if !site_hit {
+            return;
+        }
 
-        (base_damage as f32 * damage_multiplier) as u32
+        let rotate_ms = self.state.map.site(site).map_or(0, |l| l.rotate_ms);
+        for player in self.players.values_mut() {
+            if player.team == Team::Defenders
+                && player.is_alive
+                && matches!(player.position, Position::AtSite(held) if held != site)
+            {
+                player.position = Position::Moving {
+                    site,
+                    arrive_at: now + rotate_ms,
+                };
+            }
+        }
     }
 
-    fn calculate_weapon_effectiveness(&self, weapon: &Weapon) -> f32 {
-        match weapon {
-            Weapon::Operator => 1.5, // Massive aim advantage
-            Weapon::Vandal => 1.2,   // High damage, good accuracy
-            Weapon::Phantom => 1.15, // Good balance
-            Weapon::Guardian => 1.1, // High damage, slower
-            Weapon::Spectre => 0.9,  // Good for close range
-            Weapon::Sheriff => 0.8,  // High damage pistol
-            Weapon::Ghost => 0.6,    // Balanced pistol
-            Weapon::Classic => 0.4,  // Basic weapon
-            _ => 0.7,                // Default effectiveness
+    fn players_at_site(&self, player_ids: &[u32], site: Option<Site>) -> Vec<u32> {
+        player_ids
+            .iter()
+            .copied()
+            .filter(|id| {
+                site.is_some_and(|site| {
+                    self.players
+                        .get(id)
+                        .is_some_and(|p| p.position == Position::AtSite(site))
+                })
+            })
+            .collect()
+    }
+
+    /// Attackers on the site or close enough to be fighting for its choke.
+    fn attackers_in_contact(&self, attacker_ids: &[u32], site: Option<Site>) -> Vec<u32> {
+        let now = self.state.current_timestamp;
+        attacker_ids
+            .iter()
+            .copied()
+            .filter(|id| {
+                self.players.get(id).is_some_and(|p| match p.position {
+                    Position::AtSite(at) => Some(at) == site,
+                    Position::Moving {
+                        site: to,
+                        arrive_at,
+                    } => Some(to) == site && arrive_at <= now + CHOKE_CONTACT_MS,
+                    Position::Spawn => false,
+                })
+            })
+            .collect()
+    }
+
+    /// Hands out the round's ultimate orbs to whoever reaches them.
+    fn simulate_orb_pickups(&mut self) {
+        let mut player_ids: Vec<u32> = self.players.keys().copied().collect();
+        player_ids.sort_unstable();
+        if player_ids.is_empty() {
+            return;
+        }
+
+        for _ in 0..ULT_ORBS_PER_ROUND {
+            if self.rng.random::<f32>() < ULT_ORB_PICKUP_CHANCE {
+                let player_id = player_ids[self.rng.random_range(0..player_ids.len())];
+                if let Some(player) = self.players.get_mut(&player_id) {
+                    player.add_ultimate_points(1);
+                }
+            }
         }
     }
 
-    fn award_kill_bonus(&mut self, killer_id: u32) {
-        if let Some(killer) = self.players.get_mut(&killer_id) {
-            killer.current_credits = (killer.current_credits + 200).min(9000);
-            killer.ultimate_points += 1; // TODO: Implement proper ult point system
+    /// Whether a player holding a full ultimate should spend it right now.
+    /// Ultimates are saved for rounds where they swing a fight: when the team
+    /// is outnumbered, for the attackers' execute onto site, or for the
+    /// defenders' retake once the spike is down.
+    fn should_cast_ultimate(&self, team: &Team, effect: UltimateEffect) -> bool {
+        let allies = self.get_alive_players_on_team(team).len();
+        let enemy_team = match team {
+            Team::Attackers => Team::Defenders,
+            Team::Defenders => Team::Attackers,
+        };
+        let enemies = self.get_alive_players_on_team(&enemy_team).len();
+
+        match effect {
+            UltimateEffect::Revive => self
+                .players
+                .values()
+                .any(|p| p.team == *team && !p.is_alive),
+            UltimateEffect::TeamBoost(_) => {
+                if self.ultimate_boosts.contains_key(team) {
+                    return false;
+                }
+                let executing = *team == Team::Attackers
+                    && !self.spike_planted
+                    && self
+                        .players
+                        .values()
+                        .any(|p| p.team == *team && p.is_alive && p.position.site().is_some());
+                let retaking = *team == Team::Defenders && self.spike_planted;
+                allies < enemies || executing || retaking
+            }
         }
     }
 
-    fn award_spike_plant_bonus(&mut self, planter_id: u32) {
-        if let Some(planter) = self.players.get_mut(&planter_id) {
-            planter.ultimate_points += 1; // TODO: Implement proper ult point system
+    /// Lets at most one player per team cast their ultimate this tick.
+    fn simulate_ultimates(&mut self, team: &Team) {
+        let caster = self
+            .get_alive_players_on_team(team)
+            .into_iter()
+            .filter(|p| p.has_ultimate())
+            .map(|p| (p.id, p.agent.ultimate()))
+            .find(|(_, ultimate)| self.should_cast_ultimate(team, ultimate.effect));
+        let Some((caster_id, ultimate)) = caster else {
+            return;
+        };
+
+        if let Some(player) = self.players.get_mut(&caster_id) {
+            player.ultimate_points = 0;
+        }
+        self.record_event(GameEvent::AbilityUsed {
+            timestamp: self.state.current_timestamp,
+            player_id: caster_id,
+            ability_name: ultimate.name.to_string(),
+        });
+
+        match ultimate.effect {
+            UltimateEffect::TeamBoost(bonus) => {
+                self.ultimate_boosts.insert(team.clone(), bonus);
+            }
+            UltimateEffect::Revive => {
+                let revived_id = self
+                    .players
+                    .values()
+                    .filter(|p| p.team == *team && !p.is_alive)
+                    .map(|p| p.id)
+                    .min();
+                if let Some(revived) = revived_id.and_then(|id| self.players.get_mut(&id)) {
+                    revived.is_alive = true;
+                    revived.current_health = 100;
+                    revived.current_armor = 0;
+                }
+            }
         }
     }
 
@@ -1438,8 +2537,8 @@ impl ValorantSimulation {
 
     // Convenience method for running entire simulation at once (legacy mode)
     pub fn run_simulation_to_completion(&mut self) -> Result<(), String> {
-        self.start_simulation();
-
+        // The first tick starts the match, like it does when stepping through
+        // one, so a match always plays out the same for a given seed and tick
         let mut tick_count = 0;
         const MAX_TICKS_PER_MATCH: u64 = 50000; // Prevent infinite loops (about 4 hours at 500ms per tick)
 
@@ -1463,6 +2562,12 @@ impl ValorantSimulation {
         let mut tick_count = 0;
         const MAX_TICKS_PER_ROUND: u64 = 2000; // Prevent infinite loops (10 minutes at 500ms per tick)
 
+        // When called during the post-round pause, play through to the end of the next round
+        let finished_round = match self.state.phase {
+            SimulationPhase::RoundEnd { round_number, .. } => Some(round_number),
+            _ => None,
+        };
+
         loop {
             tick_count += 1;
             if tick_count > MAX_TICKS_PER_ROUND {
@@ -1473,11 +2578,14 @@ impl ValorantSimulation {
             }
 
             self.advance_tick()?;
-            if matches!(
-                self.state.phase,
-                SimulationPhase::RoundEnd { .. } | SimulationPhase::MatchEnd { .. }
-            ) {
-                break;
+            match self.state.phase {
+                SimulationPhase::RoundEnd { round_number, .. }
+                    if Some(round_number) != finished_round =>
+                {
+                    break;
+                }
+                SimulationPhase::MatchEnd { .. } => break,
+                _ => {}
             }
         }
         Ok(())
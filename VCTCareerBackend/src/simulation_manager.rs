@@ -1,15 +1,23 @@
 use super::sim::{
-    Agent, EventFilter, GameEvent, Player, PlayerStats, SimulationState, Team, ValorantSimulation,
+    Agent, EventFilter, GameEvent, MatchTeams, OvertimeSettings, Player, PlayerStats,
+    SimulationState, Team, ValorantSimulation,
 };
+use crate::balance::BalanceConfig;
+use crate::economy::BuyType;
+use crate::maps::Map;
 use crate::models::{EventFilterRequest, SimulationId, SimulationPlayer};
+use crate::win_probability::{self, RoundWinProbability, WinProbabilityEstimate};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::mpsc;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum SimulationCommand {
+    Play,
     Pause,
     Resume,
     SetSpeed(f32),
@@ -25,6 +33,7 @@ pub enum AdvanceMode {
 impl SimulationCommand {
     pub fn from_string(action: &str, speed: Option<f32>) -> Result<Self, String> {
         match action {
+            "play" => Ok(SimulationCommand::Play),
             "pause" => Ok(SimulationCommand::Pause),
             "resume" => Ok(SimulationCommand::Resume),
             "set_speed" => {
@@ -51,7 +60,7 @@ impl AdvanceMode {
 }
 
 // Helper function for safe mutex access
-fn safe_lock<T>(mutex: &Arc<Mutex<T>>) -> Result<MutexGuard<T>, String> {
+pub(crate) fn safe_lock<T>(mutex: &Arc<Mutex<T>>) -> Result<MutexGuard<'_, T>, String> {
     mutex
         .lock()
         .map_err(|e| format!("Failed to acquire lock: {}", e))
@@ -67,28 +76,77 @@ pub fn create_simulation(
     manager: &SimulationManager,
     players: Vec<SimulationPlayer>,
 ) -> Result<SimulationId, String> {
-    let mut sim = ValorantSimulation::new();
+    create_simulation_with_seed(manager, players, None)
+}
+
+pub fn create_simulation_with_seed(
+    manager: &SimulationManager,
+    players: Vec<SimulationPlayer>,
+    seed: Option<u64>,
+) -> Result<SimulationId, String> {
+    create_simulation_with_options(
+        manager,
+        players,
+        SimulationOptions {
+            seed,
+            ..SimulationOptions::default()
+        },
+    )
+}
+
+/// Optional settings for a new simulation; anything left as `None` falls back
+/// to a random seed, the baseline balance patch, Ascent and tournament overtime.
+#[derive(Debug, Clone, Default)]
+pub struct SimulationOptions {
+    pub seed: Option<u64>,
+    pub patch_version: Option<String>,
+    pub map: Option<Map>,
+    pub overtime: Option<OvertimeSettings>,
+    pub teams: MatchTeams,
+}
+
+/// Turns an API player into a simulation player on the side it names.
+pub fn build_player(player_data: SimulationPlayer) -> Result<Player, String> {
+    let agent = parse_agent(&player_data.agent)?;
+    let team = parse_team(&player_data.team)?;
+
+    Ok(Player::new(
+        player_data.id,
+        player_data.name,
+        agent,
+        team,
+        crate::sim::PlayerSkills {
+            aim: player_data.aim_skill,
+            hs: player_data.hs_skill,
+            movement: player_data.movement_skill,
+            util: player_data.util_skill,
+        },
+    ))
+}
+
+pub fn create_simulation_with_options(
+    manager: &SimulationManager,
+    players: Vec<SimulationPlayer>,
+    options: SimulationOptions,
+) -> Result<SimulationId, String> {
+    let balance = match options.patch_version.as_deref() {
+        Some(version) => BalanceConfig::load(version)?,
+        None => BalanceConfig::default(),
+    };
+    let seed = options.seed.unwrap_or_else(|| rand::rng().random());
+    let mut sim = ValorantSimulation::with_balance(seed, balance);
+    if let Some(map) = options.map {
+        sim.set_map(map)?;
+    }
+    if let Some(overtime) = options.overtime {
+        sim.set_overtime_settings(overtime)?;
+    }
+    sim.state.teams = options.teams;
     let simulation_id = sim.state.id;
 
     // Convert and add players to simulation
     for player_data in players {
-        let agent = parse_agent(&player_data.agent)?;
-        let team = parse_team(&player_data.team)?;
-
-        let player = Player::new(
-            player_data.id,
-            player_data.name,
-            agent,
-            team,
-            crate::sim::PlayerSkills {
-                aim: player_data.aim_skill,
-                hs: player_data.hs_skill,
-                movement: player_data.movement_skill,
-                util: player_data.util_skill,
-            },
-        );
-
-        sim.add_player(player);
+        sim.add_player(build_player(player_data)?);
     }
 
     // Store simulation in manager with safe locking
@@ -161,6 +219,7 @@ pub fn control_simulation(
         .ok_or("Simulation not found")?;
 
     match command {
+        SimulationCommand::Play => sim.start_playback(),
         SimulationCommand::Pause => sim.pause_simulation(),
         SimulationCommand::Resume => sim.resume_simulation(),
         SimulationCommand::SetSpeed(speed_value) => sim.set_playback_speed(speed_value),
@@ -180,6 +239,54 @@ pub fn control_simulation_legacy(
     control_simulation(manager, &simulation_id_str, command)
 }
 
+/// Claims the playback driver for a simulation that should be auto-playing.
+/// Returns the generation to pass to `drive_playback`; any driver already
+/// running for the simulation stops at its next step.
+pub fn claim_playback_driver(
+    manager: &SimulationManager,
+    simulation_id_str: &str,
+) -> Result<Option<u64>, String> {
+    let simulation_id = Uuid::parse_str(simulation_id_str)
+        .map_err(|_| "Invalid simulation ID format".to_string())?;
+    let mut simulations = safe_lock(manager)?;
+    let sim = simulations
+        .get_mut(&simulation_id)
+        .ok_or("Simulation not found")?;
+
+    if sim.playback_step().is_none() {
+        return Ok(None);
+    }
+    sim.playback_generation += 1;
+    Ok(Some(sim.playback_generation))
+}
+
+/// Ticks a simulation in wall-clock time until it is paused, finishes, is
+/// removed, or another driver claims it.
+pub async fn drive_playback(manager: SimulationManager, simulation_id: Uuid, generation: u64) {
+    loop {
+        let delay = {
+            let Ok(mut simulations) = safe_lock(&manager) else {
+                return;
+            };
+            let Some(sim) = simulations.get_mut(&simulation_id) else {
+                return;
+            };
+            if sim.playback_generation != generation {
+                return;
+            }
+            let Some((ticks, delay)) = sim.playback_step() else {
+                return;
+            };
+            if let Err(e) = sim.advance_multiple_ticks(ticks) {
+                log::warn!("Playback of simulation {} stopped: {}", simulation_id, e);
+                return;
+            }
+            delay
+        };
+        tokio::time::sleep(delay).await;
+    }
+}
+
 pub fn get_simulation_events(
     manager: &SimulationManager,
     simulation_id_str: &str,
@@ -408,6 +515,7 @@ pub struct LiveStats {
     pub top_fraggers: Vec<PlayerPerformance>,
     pub economy_status: EconomyStatus,
     pub match_phase: String,
+    pub win_probability: WinProbabilityEstimate,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -460,7 +568,7 @@ pub struct PlayerRanking {
 pub struct EconomyStatus {
     pub attacker_average_credits: u32,
     pub defender_average_credits: u32,
-    pub attacker_buy_strength: String, // "Full Buy", "Force Buy", "Eco", "Save"
+    pub attacker_buy_strength: String, // a BuyType label, e.g. "Full Buy" or "Eco"
     pub defender_buy_strength: String,
     pub loss_streaks: HashMap<Team, u8>,
 }
@@ -517,7 +625,7 @@ pub fn get_live_stats(
         .collect();
 
     // Sort by kills descending
-    performers.sort_by(|a, b| b.kills.cmp(&a.kills));
+    performers.sort_by_key(|p| std::cmp::Reverse(p.kills));
     performers.truncate(5); // Top 5 fraggers
 
     // Calculate economy status
@@ -532,9 +640,24 @@ pub fn get_live_stats(
         top_fraggers: performers,
         economy_status,
         match_phase: format!("{:?}", state.phase),
+        win_probability: win_probability::estimate(sim),
     })
 }
 
+pub fn get_win_probability_timeline(
+    manager: &SimulationManager,
+    simulation_id_str: &str,
+) -> Result<Vec<RoundWinProbability>, String> {
+    let simulation_id = Uuid::parse_str(simulation_id_str)
+        .map_err(|_| "Invalid simulation ID format".to_string())?;
+    let simulations = safe_lock(manager)?;
+    let sim = simulations
+        .get(&simulation_id)
+        .ok_or("Simulation not found")?;
+
+    Ok(win_probability::timeline(sim))
+}
+
 pub fn get_scoreboard(
     manager: &SimulationManager,
     simulation_id_str: &str,
@@ -671,8 +794,14 @@ fn calculate_economy_status(sim: &ValorantSimulation) -> EconomyStatus {
         0
     };
 
-    let attacker_buy_strength = determine_buy_strength(attacker_avg);
-    let defender_buy_strength = determine_buy_strength(defender_avg);
+    // Report what each team decided to buy; before the first buy phase there
+    // is no decision yet, so fall back to judging from credits
+    let attacker_buy_strength = latest_buy_type(sim, &Team::Attackers)
+        .map(|buy_type| buy_type.label().to_string())
+        .unwrap_or_else(|| determine_buy_strength(attacker_avg));
+    let defender_buy_strength = latest_buy_type(sim, &Team::Defenders)
+        .map(|buy_type| buy_type.label().to_string())
+        .unwrap_or_else(|| determine_buy_strength(defender_avg));
 
     EconomyStatus {
         attacker_average_credits: attacker_avg,
@@ -683,6 +812,17 @@ fn calculate_economy_status(sim: &ValorantSimulation) -> EconomyStatus {
     }
 }
 
+fn latest_buy_type(sim: &ValorantSimulation, team: &Team) -> Option<BuyType> {
+    sim.events.iter().rev().find_map(|event| match event {
+        GameEvent::TeamBuy {
+            team: buy_team,
+            buy_type,
+            ..
+        } if buy_team == team => Some(*buy_type),
+        _ => None,
+    })
+}
+
 fn determine_buy_strength(avg_credits: u32) -> String {
     match avg_credits {
         0..=2000 => "Save".to_string(),
@@ -725,11 +865,42 @@ pub struct SimulationCheckpoint {
     pub description: String,
 }
 
+impl From<&super::sim::SimulationCheckpoint> for SimulationCheckpoint {
+    fn from(checkpoint: &super::sim::SimulationCheckpoint) -> Self {
+        SimulationCheckpoint {
+            checkpoint_id: checkpoint.id.to_string(),
+            timestamp: checkpoint.state.current_timestamp,
+            round_number: checkpoint.state.current_round,
+            state_snapshot: checkpoint.state.clone(),
+            event_count: checkpoint.events.len(),
+            description: checkpoint.description.clone(),
+        }
+    }
+}
+
 pub fn create_checkpoint(
     manager: &SimulationManager,
     simulation_id_str: &str,
-    _description: Option<String>,
+    description: Option<String>,
 ) -> Result<String, String> {
+    let simulation_id = Uuid::parse_str(simulation_id_str)
+        .map_err(|_| "Invalid simulation ID format".to_string())?;
+    let mut simulations = safe_lock(manager)?;
+    let sim = simulations
+        .get_mut(&simulation_id)
+        .ok_or("Simulation not found")?;
+
+    let description =
+        description.unwrap_or_else(|| format!("Round {} checkpoint", sim.state.current_round));
+    let checkpoint_id = sim.create_checkpoint(description);
+
+    Ok(checkpoint_id.to_string())
+}
+
+pub fn list_checkpoints(
+    manager: &SimulationManager,
+    simulation_id_str: &str,
+) -> Result<Vec<SimulationCheckpoint>, String> {
     let simulation_id = Uuid::parse_str(simulation_id_str)
         .map_err(|_| "Invalid simulation ID format".to_string())?;
     let simulations = safe_lock(manager)?;
@@ -737,12 +908,15 @@ pub fn create_checkpoint(
         .get(&simulation_id)
         .ok_or("Simulation not found")?;
 
-    let _state = sim.get_current_state();
-    let checkpoint_id = Uuid::new_v4().to_string();
+    let mut checkpoints: Vec<SimulationCheckpoint> = sim
+        .checkpoints
+        .values()
+        .map(SimulationCheckpoint::from)
+        .collect();
+    // Oldest first, matching the order they were taken in
+    checkpoints.sort_by_key(|c| (c.state_snapshot.tick_count, c.event_count));
 
-    // In a real implementation, you'd store this checkpoint in a database or cache
-    // For now, we'll just return the checkpoint info
-    Ok(checkpoint_id)
+    Ok(checkpoints)
 }
 
 pub fn restore_checkpoint(
@@ -752,10 +926,7 @@ pub fn restore_checkpoint(
 ) -> Result<(), String> {
     let simulation_id = Uuid::parse_str(simulation_id_str)
         .map_err(|_| "Invalid simulation ID format".to_string())?;
-
-    // Parse checkpoint ID as a tick number for now
-    let tick = checkpoint_id
-        .parse::<u64>()
+    let checkpoint_uuid = Uuid::parse_str(checkpoint_id)
         .map_err(|_| format!("Invalid checkpoint ID format: {}", checkpoint_id))?;
 
     let mut simulations = safe_lock(manager)?;
@@ -763,8 +934,7 @@ pub fn restore_checkpoint(
         .get_mut(&simulation_id)
         .ok_or("Simulation not found")?;
 
-    // Use the simulation's built-in checkpoint restoration
-    sim.restore_checkpoint(tick)
+    sim.restore_checkpoint(checkpoint_uuid)
         .map_err(|e| format!("Failed to restore checkpoint {}: {}", checkpoint_id, e))
 }
 
@@ -776,27 +946,26 @@ pub fn rewind_to_round(
     let simulation_id = Uuid::parse_str(simulation_id_str)
         .map_err(|_| "Invalid simulation ID format".to_string())?;
     let mut simulations = safe_lock(manager)?;
-    let _sim = simulations
+    let sim = simulations
         .get_mut(&simulation_id)
         .ok_or("Simulation not found")?;
 
-    // Simple rewind implementation - reset to beginning and replay to target round
-    if target_round == 0 {
-        return Err("Cannot rewind to round 0".to_string());
-    }
-
-    // In a real implementation, you'd use checkpoints or event replay
-    // For now, this is a placeholder
-    Err("Rewind functionality requires checkpoint system implementation".to_string())
+    sim.rewind_to_round(target_round)
 }
 
 pub fn replay_from(
-    _manager: &SimulationManager,
-    _simulation_id_str: &str,
-    _timestamp: u64,
+    manager: &SimulationManager,
+    simulation_id_str: &str,
+    timestamp: u64,
 ) -> Result<(), String> {
-    // Placeholder for replay functionality
-    Err("Replay functionality requires event sourcing implementation".to_string())
+    let simulation_id = Uuid::parse_str(simulation_id_str)
+        .map_err(|_| "Invalid simulation ID format".to_string())?;
+    let mut simulations = safe_lock(manager)?;
+    let sim = simulations
+        .get_mut(&simulation_id)
+        .ok_or("Simulation not found")?;
+
+    sim.replay_from(timestamp)
 }
 
 // Event streaming functionality
@@ -805,13 +974,57 @@ pub trait EventListener: Send + Sync {
     fn get_listener_id(&self) -> String;
 }
 
+/// An event as delivered to a live subscriber. `index` is the event's position
+/// in the simulation's log, so a client can reconnect and resume after it.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StreamedEvent {
+    pub index: usize,
+    pub event: GameEvent,
+}
+
+impl StreamedEvent {
+    /// Formats the event as a Server-Sent Events frame.
+    pub fn to_sse_frame(&self) -> String {
+        let data = serde_json::to_string(&self.event).unwrap_or_default();
+        format!(
+            "id: {}\nevent: {}\ndata: {}\n\n",
+            self.index,
+            self.event.event_type(),
+            data
+        )
+    }
+}
+
+pub type EventReceiver = mpsc::UnboundedReceiver<StreamedEvent>;
+
 #[derive(Debug)]
 pub struct EventSubscription {
     pub listener_id: String,
     pub event_types: Option<Vec<String>>,
     pub player_filter: Option<Vec<u32>>,
+    pub sender: mpsc::UnboundedSender<StreamedEvent>,
 }
 
+impl EventSubscription {
+    pub fn matches(&self, event: &GameEvent) -> bool {
+        if let Some(ref event_types) = self.event_types
+            && !event_types.iter().any(|t| t == event.event_type())
+        {
+            return false;
+        }
+        if let Some(ref player_filter) = self.player_filter
+            && !event
+                .player_ids()
+                .iter()
+                .any(|id| player_filter.contains(id))
+        {
+            return false;
+        }
+        true
+    }
+}
+
+#[derive(Debug)]
 pub struct EventStream {
     pub simulation_id: String,
     pub active: bool,
@@ -836,30 +1049,81 @@ impl EventStream {
             .retain(|sub| sub.listener_id != listener_id);
     }
 
-    pub fn broadcast_event(&self, _event: &GameEvent) {
-        // In a real implementation, this would broadcast to all subscribers
-        // This is a placeholder for the streaming functionality
+    pub fn broadcast_event(&mut self, index: usize, event: &GameEvent) {
+        if !self.active {
+            return;
+        }
+        // A closed channel means the client hung up, so drop the subscription
+        self.subscribers.retain(|sub| {
+            if sub.sender.is_closed() {
+                return false;
+            }
+            !sub.matches(event)
+                || sub
+                    .sender
+                    .send(StreamedEvent {
+                        index,
+                        event: event.clone(),
+                    })
+                    .is_ok()
+        });
     }
 }
 
+/// Subscribes to a simulation's live events. Events already in the log from
+/// `from_index` onward are queued first, so a reconnecting client sees no gap.
 pub fn subscribe_to_events(
-    _manager: &SimulationManager,
-    _simulation_id_str: &str,
-    _listener_id: String,
-    _event_types: Option<Vec<String>>,
-    _player_filter: Option<Vec<u32>>,
-) -> Result<(), String> {
-    // Placeholder for event subscription
-    // In a real implementation, you'd manage a registry of event streams
-    Ok(())
+    manager: &SimulationManager,
+    simulation_id_str: &str,
+    listener_id: String,
+    event_types: Option<Vec<String>>,
+    player_filter: Option<Vec<u32>>,
+    from_index: Option<usize>,
+) -> Result<EventReceiver, String> {
+    let simulation_id = Uuid::parse_str(simulation_id_str)
+        .map_err(|_| "Invalid simulation ID format".to_string())?;
+    let mut simulations = safe_lock(manager)?;
+    let sim = simulations
+        .get_mut(&simulation_id)
+        .ok_or("Simulation not found")?;
+
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let subscription = EventSubscription {
+        listener_id,
+        event_types,
+        player_filter,
+        sender,
+    };
+
+    if let Some(from_index) = from_index {
+        for (index, event) in sim.events.iter().enumerate().skip(from_index) {
+            if subscription.matches(event) {
+                let _ = subscription.sender.send(StreamedEvent {
+                    index,
+                    event: event.clone(),
+                });
+            }
+        }
+    }
+
+    sim.event_stream.subscribe(subscription);
+    Ok(receiver)
 }
 
-pub fn get_event_stream(
-    _manager: &SimulationManager,
+pub fn unsubscribe_from_events(
+    manager: &SimulationManager,
     simulation_id_str: &str,
-) -> Result<EventStream, String> {
-    // Placeholder for getting event stream
-    Ok(EventStream::new(simulation_id_str.to_string()))
+    listener_id: &str,
+) -> Result<(), String> {
+    let simulation_id = Uuid::parse_str(simulation_id_str)
+        .map_err(|_| "Invalid simulation ID format".to_string())?;
+    let mut simulations = safe_lock(manager)?;
+    let sim = simulations
+        .get_mut(&simulation_id)
+        .ok_or("Simulation not found")?;
+
+    sim.event_stream.unsubscribe(listener_id);
+    Ok(())
 }
 
 // Enhanced event querying with time-travel capabilities
@@ -905,6 +1169,13 @@ pub fn create_checkpoint_legacy(
     create_checkpoint(manager, &simulation_id_str, description)
 }
 
+pub fn list_checkpoints_legacy(
+    manager: &SimulationManager,
+    simulation_id_str: String,
+) -> Result<Vec<SimulationCheckpoint>, String> {
+    list_checkpoints(manager, &simulation_id_str)
+}
+
 pub fn restore_checkpoint_legacy(
     manager: &SimulationManager,
     simulation_id_str: String,
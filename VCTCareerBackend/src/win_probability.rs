@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::sim::{
+    ArmorType, ROUND_TIME_MS, SPIKE_TIME_MS, SimulationPhase, Team, Timestamp, ValorantSimulation,
+};
+
+// Logistic model of the attackers winning the round they are playing, tuned
+// against seeded baseline-patch matches; the calibration test in
+// tests/win_probability_tests.rs checks the estimates still match outcomes.
+// Equipment only counts living players, so it carries most of what the alive
+// counts would. Retune if the round or combat rules change.
+const ATTACKER_BIAS: f32 = 0.03;
+const ALIVE_WEIGHT: f32 = 0.10; // per player up
+const EQUIPMENT_WEIGHT: f32 = 0.18; // per 1000 credits of equipment up
+const PLANT_WEIGHT: f32 = 0.10;
+const DETONATION_WEIGHT: f32 = 0.20; // as the spike timer runs down
+const CLOCK_WEIGHT: f32 = 0.12; // as the round timer runs down with no plant
+
+// Defusing takes 7 seconds; once less than that is left the spike will blow
+const DEFUSE_TIME_MS: i32 = 7_000;
+
+/// What the round model looks at: the state of the round right now.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundSituation {
+    pub attackers_alive: u8,
+    pub defenders_alive: u8,
+    pub attacker_equipment: u32, // credits' worth of guns and armor still alive
+    pub defender_equipment: u32,
+    pub spike_planted: bool,
+    pub round_timer_ms: i32,
+    pub spike_timer_ms: i32,
+}
+
+impl RoundSituation {
+    pub fn from_simulation(sim: &ValorantSimulation) -> Self {
+        let alive = |team: Team| sim.get_alive_players_on_team(&team);
+        let equipment = |team: Team| {
+            alive(team)
+                .iter()
+                .map(|player| {
+                    let loadout = &player.current_loadout;
+                    let weapon = loadout
+                        .primary_weapon
+                        .as_ref()
+                        .unwrap_or(&loadout.secondary_weapon);
+                    let armor = if player.current_armor > 0 {
+                        &loadout.armor
+                    } else {
+                        &ArmorType::None
+                    };
+                    sim.balance.weapons[weapon].price + sim.balance.armor_costs.cost(armor)
+                })
+                .sum()
+        };
+
+        RoundSituation {
+            attackers_alive: alive(Team::Attackers).len() as u8,
+            defenders_alive: alive(Team::Defenders).len() as u8,
+            attacker_equipment: equipment(Team::Attackers),
+            defender_equipment: equipment(Team::Defenders),
+            spike_planted: sim.spike_planted,
+            round_timer_ms: sim.round_timer_ms,
+            spike_timer_ms: sim.spike_timer_ms,
+        }
+    }
+}
+
+/// The attackers' chance of winning the round from `situation`.
+pub fn round_win_probability(situation: &RoundSituation) -> f32 {
+    // Rounds that are already decided
+    if situation.defenders_alive == 0 {
+        return 1.0;
+    }
+    if situation.spike_planted && situation.spike_timer_ms < DEFUSE_TIME_MS {
+        return 1.0;
+    }
+    if situation.attackers_alive == 0 && !situation.spike_planted {
+        return 0.0;
+    }
+
+    let alive_gap = situation.attackers_alive as f32 - situation.defenders_alive as f32;
+    let equipment_gap =
+        (situation.attacker_equipment as f32 - situation.defender_equipment as f32) / 1000.0;
+    let mut logit = ATTACKER_BIAS + ALIVE_WEIGHT * alive_gap + EQUIPMENT_WEIGHT * equipment_gap;
+    if situation.spike_planted {
+        let spike_elapsed = 1.0 - situation.spike_timer_ms as f32 / SPIKE_TIME_MS as f32;
+        logit += PLANT_WEIGHT + DETONATION_WEIGHT * spike_elapsed;
+    } else {
+        let round_elapsed = 1.0 - situation.round_timer_ms as f32 / ROUND_TIME_MS as f32;
+        logit -= CLOCK_WEIGHT * round_elapsed;
+    }
+    1.0 / (1.0 + (-logit).exp())
+}
+
+/// The attackers' chance of winning the match from `attacker_score` to
+/// `defender_score`, given their chance of winning the round in progress.
+/// Later rounds are treated as coin flips, since who is favored in them
+/// depends on buys that haven't happened yet. Overtime needs a two-round
+/// lead; sudden death and draw votes aren't modelled.
+pub fn match_win_probability(
+    attacker_score: u8,
+    defender_score: u8,
+    attacker_round_probability: f32,
+) -> f32 {
+    attacker_round_probability * match_win_probability_from(attacker_score + 1, defender_score)
+        + (1.0 - attacker_round_probability)
+            * match_win_probability_from(attacker_score, defender_score + 1)
+}
+
+/// The attackers' chance of winning the match from a score between rounds.
+pub fn match_win_probability_from(attacker_score: u8, defender_score: u8) -> f32 {
+    const WIN_SCORE: usize = 13;
+    let (a, d) = (attacker_score as usize, defender_score as usize);
+    if a >= WIN_SCORE && a >= d + 2 {
+        return 1.0;
+    }
+    if d >= WIN_SCORE && d >= a + 2 {
+        return 0.0;
+    }
+    // In overtime only the lead matters: level is even, a round up needs one
+    // more to close it out
+    if a >= WIN_SCORE - 1 && d >= WIN_SCORE - 1 {
+        return match a.cmp(&d) {
+            std::cmp::Ordering::Equal => 0.5,
+            std::cmp::Ordering::Greater => 0.75,
+            std::cmp::Ordering::Less => 0.25,
+        };
+    }
+
+    // Fill in regulation from the final scores back, with 12-12 as the overtime coin flip
+    let mut chances = [[0.0f32; WIN_SCORE + 1]; WIN_SCORE + 1];
+    for attacker in (0..=WIN_SCORE).rev() {
+        for defender in (0..=WIN_SCORE).rev() {
+            chances[attacker][defender] = if attacker == WIN_SCORE - 1 && defender == WIN_SCORE - 1
+            {
+                0.5
+            } else if attacker == WIN_SCORE {
+                1.0
+            } else if defender == WIN_SCORE {
+                0.0
+            } else {
+                0.5 * chances[attacker + 1][defender] + 0.5 * chances[attacker][defender + 1]
+            };
+        }
+    }
+    chances[a][d]
+}
+
+/// Both chances for the current moment, from the attackers' side.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct WinProbabilityEstimate {
+    pub attacker_round: f32,
+    pub attacker_match: f32,
+}
+
+/// Estimates who is favored right now. Between rounds the round estimate is
+/// for the next round on what the teams have in hand.
+pub fn estimate(sim: &ValorantSimulation) -> WinProbabilityEstimate {
+    let state = &sim.state;
+    let (attacker_score, defender_score) = (state.attacker_score, state.defender_score);
+    match &state.phase {
+        SimulationPhase::MatchEnd { winner, .. } => {
+            let attacker_match = match winner {
+                Some(Team::Attackers) => 1.0,
+                Some(Team::Defenders) => 0.0,
+                None => 0.5,
+            };
+            WinProbabilityEstimate {
+                attacker_round: attacker_match,
+                attacker_match,
+            }
+        }
+        SimulationPhase::RoundEnd { winner, .. } => WinProbabilityEstimate {
+            attacker_round: if *winner == Team::Attackers { 1.0 } else { 0.0 },
+            attacker_match: match_win_probability_from(attacker_score, defender_score),
+        },
+        _ => {
+            let attacker_round = round_win_probability(&RoundSituation::from_simulation(sim));
+            WinProbabilityEstimate {
+                attacker_round,
+                attacker_match: match_win_probability(
+                    attacker_score,
+                    defender_score,
+                    attacker_round,
+                ),
+            }
+        }
+    }
+}
+
+/// One point on the win probability timeline, taken right after `trigger`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WinProbabilitySample {
+    pub round_number: u8,
+    pub timestamp: Timestamp,
+    pub trigger: String, // the event type that moved the estimate
+    pub attackers_alive: u8,
+    pub defenders_alive: u8,
+    pub attacker_round: f32,
+    pub attacker_match: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RoundWinProbability {
+    pub round_number: u8,
+    pub samples: Vec<WinProbabilitySample>,
+}
+
+/// The recorded samples grouped by round, oldest first.
+pub fn timeline(sim: &ValorantSimulation) -> Vec<RoundWinProbability> {
+    let mut rounds: Vec<RoundWinProbability> = Vec::new();
+    for sample in &sim.win_probability {
+        match rounds.last_mut() {
+            Some(round) if round.round_number == sample.round_number => {
+                round.samples.push(sample.clone())
+            }
+            _ => rounds.push(RoundWinProbability {
+                round_number: sample.round_number,
+                samples: vec![sample.clone()],
+            }),
+        }
+    }
+    rounds
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use vctcareer_backend::agents::AgentRole;
+use vctcareer_backend::maps::Map;
+use vctcareer_backend::sim::{
+    Agent, GameEvent, Player, PlayerSkills, SimulationPhase, Team, ValorantSimulation,
+};
+
+// Everyone plays the same apart from how good they are with utility
+fn utility(util: f32) -> impl Fn(u32) -> PlayerSkills {
+    move |_| common::skills(0.7, 0.4, 0.7, util)
+}
+
+fn mixed_agents() -> [Agent; 10] {
+    [
+        Agent::Jett,
+        Agent::Sova,
+        Agent::Omen,
+        Agent::Killjoy,
+        Agent::Skye,
+        Agent::Raze,
+        Agent::Breach,
+        Agent::Brimstone,
+        Agent::Cypher,
+        Agent::Sage,
+    ]
+}
+
+fn is_ultimate(name: &str) -> bool {
+    Agent::all().iter().any(|a| a.ultimate().name == name)
+}
+
+fn ability_uses(sim: &ValorantSimulation) -> usize {
+    sim.events
+        .iter()
+        .filter(|e| matches!(e, GameEvent::AbilityUsed { ability_name, .. } if !is_ultimate(ability_name)))
+        .count()
+}
+
+fn ultimate_uses(sim: &ValorantSimulation) -> Vec<(u32, String)> {
+    sim.events
+        .iter()
+        .filter_map(|e| match e {
+            GameEvent::AbilityUsed {
+                player_id,
+                ability_name,
+                ..
+            } if is_ultimate(ability_name) => Some((*player_id, ability_name.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_every_agent_has_a_kit_with_a_free_ability() {
+    for agent in Agent::all() {
+        let kit = agent.kit();
+        assert!(!kit.is_empty(), "{:?} has no abilities", agent);
+        assert!(
+            kit.iter().any(|a| a.free_charges > 0 && a.cost == 0),
+            "{:?} has no signature ability",
+            agent
+        );
+        assert!(kit.iter().all(|a| a.free_charges <= a.max_charges));
+    }
+    assert_eq!(Agent::Omen.role(), AgentRole::Controller);
+    assert_eq!(Agent::Killjoy.role(), AgentRole::Sentinel);
+}
+
+#[test]
+fn test_players_buy_abilities_in_buy_phase() {
+    let mut sim = common::seeded_match(5, Map::Ascent, &mixed_agents(), utility(0.8));
+    sim.start_simulation();
+
+    while matches!(sim.state.phase, SimulationPhase::BuyPhase { .. }) {
+        sim.advance_tick().unwrap();
+    }
+
+    for player in sim.players.values() {
+        for ability in player.agent.kit() {
+            assert!(player.ability_charges(ability.name) >= ability.free_charges);
+        }
+        assert!(!player.current_loadout.abilities_purchased.is_empty());
+    }
+}
+
+#[test]
+fn test_abilities_are_used_during_a_match() {
+    let sim = common::run_seeded_match(9, Map::Ascent, &mixed_agents(), utility(0.8));
+
+    assert!(ability_uses(&sim) > 0);
+}
+
+#[test]
+fn test_players_without_utility_skill_never_use_abilities() {
+    let sim = common::run_seeded_match(9, Map::Ascent, &mixed_agents(), utility(0.0));
+
+    assert_eq!(ability_uses(&sim), 0);
+}
+
+#[test]
+fn test_agent_choice_changes_outcomes() {
+    let duelists = common::run_seeded_match(21, Map::Ascent, &[Agent::Jett], utility(0.8));
+
+    let initiators = common::run_seeded_match(21, Map::Ascent, &[Agent::Breach], utility(0.8));
+
+    assert_ne!(
+        serde_json::to_string(&duelists.events).unwrap(),
+        serde_json::to_string(&initiators.events).unwrap()
+    );
+}
+
+#[test]
+fn test_ultimate_points_are_capped_at_ult_cost() {
+    let mut player = Player::new(
+        1,
+        "Player1".to_string(),
+        Agent::Phoenix,
+        Team::Attackers,
+        PlayerSkills {
+            aim: 0.5,
+            hs: 0.5,
+            movement: 0.5,
+            util: 0.5,
+        },
+    );
+
+    player.add_ultimate_points(5);
+    assert!(!player.has_ultimate());
+    player.add_ultimate_points(5);
+    assert_eq!(player.ultimate_points, Agent::Phoenix.ultimate().cost);
+    assert!(player.has_ultimate());
+}
+
+#[test]
+fn test_ultimates_are_cast_during_a_match() {
+    let sim = common::run_seeded_match(9, Map::Ascent, &mixed_agents(), utility(0.8));
+
+    let casts = ultimate_uses(&sim);
+    assert!(!casts.is_empty());
+    for (player_id, ability_name) in casts {
+        assert_eq!(sim.players[&player_id].agent.ultimate().name, ability_name);
+    }
+}
+
+#[test]
+fn test_kills_give_ultimate_points_to_killer_and_victim() {
+    let mut sim = common::seeded_match(4, Map::Ascent, &mixed_agents(), utility(0.0));
+    sim.start_simulation();
+
+    let (killer_id, victim_id) = loop {
+        sim.advance_tick().unwrap();
+        let kill = sim.events.iter().find_map(|e| match e {
+            GameEvent::Kill {
+                killer_id,
+                victim_id,
+                ..
+            } => Some((*killer_id, *victim_id)),
+            _ => None,
+        });
+        if let Some(kill) = kill {
+            break kill;
+        }
+    };
+    assert!(sim.players[&killer_id].ultimate_points >= 1);
+    assert!(sim.players[&victim_id].ultimate_points >= 1);
+}
@@ -0,0 +1,191 @@
+use vctcareer_backend::attributes::{
+    Attribute, Development, PlayerAttributes, PlayerCondition, TrainingFocus, WeekPerformance,
+    develop_week, growth_rate, parse_training_focus, weekly_decline,
+};
+use vctcareer_backend::sim::PlayerSkills;
+
+fn flat_attributes(value: u8) -> PlayerAttributes {
+    PlayerAttributes {
+        headshot: value,
+        aim: value,
+        igl: value,
+        movement: value,
+        game_sense: value,
+        clutch: value,
+        entry: value,
+        support: value,
+        utility: value,
+    }
+}
+
+fn training_week(sessions: u32) -> WeekPerformance {
+    WeekPerformance {
+        training_sessions: sessions,
+        ..Default::default()
+    }
+}
+
+fn match_week(won: bool, kills: u32, deaths: u32) -> WeekPerformance {
+    WeekPerformance {
+        series_played: 1,
+        series_won: won as u32,
+        maps_played: 2,
+        kills,
+        deaths,
+        assists: 6,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_attributes_map_onto_simulation_skills() {
+    let skills = flat_attributes(50).skills();
+    for skill in [skills.aim, skills.hs, skills.movement, skills.util] {
+        assert!((skill - 0.5).abs() < 1e-5);
+    }
+
+    let mut sharpshooter = flat_attributes(50);
+    sharpshooter.headshot = 90;
+    let skills = sharpshooter.skills();
+    assert!(skills.hs > 0.8);
+    assert!((skills.aim - 0.5).abs() < 1e-5);
+}
+
+#[test]
+fn test_form_and_morale_scale_skills() {
+    let base = PlayerSkills {
+        aim: 0.6,
+        hs: 0.4,
+        movement: 0.6,
+        util: 0.6,
+    };
+    let neutral = PlayerCondition::default().apply(base.clone());
+    assert!((neutral.aim - 0.6).abs() < 1e-5);
+
+    let hot = PlayerCondition {
+        form: 1.0,
+        morale: 1.0,
+    }
+    .apply(base.clone());
+    let cold = PlayerCondition {
+        form: -1.0,
+        morale: 0.0,
+    }
+    .apply(base);
+    assert!(hot.aim > 0.6 && hot.aim <= 0.6 * 1.1);
+    assert!(cold.aim < 0.6);
+
+    let capped = PlayerCondition {
+        form: 1.0,
+        morale: 1.0,
+    }
+    .apply(flat_attributes(100).skills());
+    assert_eq!(capped.aim, 1.0);
+}
+
+#[test]
+fn test_age_curves() {
+    assert!(growth_rate(18) > growth_rate(22));
+    assert!(growth_rate(22) > growth_rate(27));
+    assert!(growth_rate(27) > growth_rate(33));
+
+    assert_eq!(weekly_decline(24, Attribute::Aim), 0.0);
+    assert!(weekly_decline(30, Attribute::Aim) > 0.0);
+    assert_eq!(weekly_decline(30, Attribute::GameSense), 0.0);
+    assert!(weekly_decline(35, Attribute::Aim) > weekly_decline(35, Attribute::GameSense));
+}
+
+#[test]
+fn test_training_goes_into_the_focus() {
+    let mut attributes = flat_attributes(40);
+    let mut development = Development {
+        focus: TrainingFocus::Aim,
+        ..Default::default()
+    };
+
+    // Under a point is banked rather than lost
+    let changes = develop_week(&mut attributes, &mut development, 19, &training_week(1));
+    assert!(changes.is_empty());
+    assert!(development.progress.aim > 0.0);
+
+    for _ in 0..20 {
+        develop_week(&mut attributes, &mut development, 19, &training_week(4));
+    }
+    assert!(attributes.aim > 40);
+    assert!(attributes.headshot > 40);
+    assert_eq!(attributes.movement, 40);
+    assert_eq!(attributes.igl, 40);
+}
+
+#[test]
+fn test_young_players_grow_faster() {
+    let mut young = flat_attributes(40);
+    let mut veteran = flat_attributes(40);
+    let (mut young_dev, mut veteran_dev) = (Development::default(), Development::default());
+    for _ in 0..30 {
+        develop_week(&mut young, &mut young_dev, 19, &training_week(4));
+        develop_week(&mut veteran, &mut veteran_dev, 27, &training_week(4));
+    }
+    let total = |a: &PlayerAttributes| {
+        Attribute::ALL
+            .iter()
+            .map(|attr| a.get(*attr) as u32)
+            .sum::<u32>()
+    };
+    assert!(total(&young) > total(&veteran));
+    assert!(total(&veteran) >= total(&flat_attributes(40)));
+}
+
+#[test]
+fn test_age_wears_down_mechanics_first() {
+    let mut attributes = flat_attributes(70);
+    let mut development = Development::default();
+    for _ in 0..40 {
+        develop_week(&mut attributes, &mut development, 33, &training_week(0));
+    }
+    assert!(attributes.aim < 70);
+    assert!(attributes.movement < 70);
+    assert!(attributes.game_sense > attributes.aim);
+}
+
+#[test]
+fn test_match_performance_moves_form_and_morale() {
+    let mut attributes = flat_attributes(60);
+    let mut starring = Development::default();
+    let mut struggling = Development::default();
+
+    develop_week(
+        &mut attributes.clone(),
+        &mut starring,
+        22,
+        &match_week(true, 50, 20),
+    );
+    develop_week(
+        &mut attributes,
+        &mut struggling,
+        22,
+        &match_week(false, 10, 35),
+    );
+
+    assert!(starring.condition.form > 0.0);
+    assert!(starring.condition.morale > PlayerCondition::default().morale);
+    assert!(struggling.condition.form < 0.0);
+    assert!(struggling.condition.morale < PlayerCondition::default().morale);
+    // Playing well teaches more on the gunfight side
+    assert!(starring.progress.aim > struggling.progress.aim);
+
+    // Form fades without matches
+    let form = starring.condition.form;
+    develop_week(&mut attributes, &mut starring, 22, &training_week(3));
+    assert!(starring.condition.form.abs() < form.abs());
+}
+
+#[test]
+fn test_parse_training_focus() {
+    assert_eq!(parse_training_focus("aim").unwrap(), TrainingFocus::Aim);
+    assert_eq!(
+        parse_training_focus("Teamplay").unwrap(),
+        TrainingFocus::Teamplay
+    );
+    assert!(parse_training_focus("Cooking").is_err());
+}
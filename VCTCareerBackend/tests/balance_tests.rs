@@ -0,0 +1,81 @@
+mod common;
+
+use vctcareer_backend::balance::{BalanceConfig, DEFAULT_PATCH_VERSION};
+use vctcareer_backend::sim::{Agent, ValorantSimulation, Weapon};
+
+#[test]
+fn test_default_balance_matches_baseline_file() {
+    let default = BalanceConfig::default();
+    let loaded = BalanceConfig::load(DEFAULT_PATCH_VERSION).unwrap();
+
+    assert_eq!(default.version, DEFAULT_PATCH_VERSION);
+    assert_eq!(
+        serde_json::to_value(&default.economy).unwrap(),
+        serde_json::to_value(&loaded.economy).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_value(&default.combat).unwrap(),
+        serde_json::to_value(&loaded.combat).unwrap()
+    );
+    assert_eq!(default.weapons.len(), loaded.weapons.len());
+    assert_eq!(default.weapons[&Weapon::Vandal].price, 2900);
+    assert_eq!(default.armor_costs.heavy, 1000);
+}
+
+#[test]
+fn test_available_versions_include_baseline() {
+    let versions = BalanceConfig::available_versions().unwrap();
+    assert!(versions.contains(&DEFAULT_PATCH_VERSION.to_string()));
+}
+
+#[test]
+fn test_unknown_or_unsafe_patch_versions_are_rejected() {
+    assert!(BalanceConfig::load("does-not-exist").is_err());
+    assert!(BalanceConfig::load("../Cargo").is_err());
+    assert!(BalanceConfig::load("").is_err());
+}
+
+#[test]
+fn test_balance_missing_a_weapon_is_rejected() {
+    let mut value = serde_json::to_value(BalanceConfig::default()).unwrap();
+    value["weapons"].as_object_mut().unwrap().remove("Odin");
+
+    let result = BalanceConfig::from_json(&value.to_string());
+    assert!(result.unwrap_err().contains("Odin"));
+}
+
+#[test]
+fn test_balance_with_out_of_range_combat_weights_is_rejected() {
+    let mut value = serde_json::to_value(BalanceConfig::default()).unwrap();
+    value["combat"]["strafe_penalty"] = serde_json::json!(1.5);
+
+    let result = BalanceConfig::from_json(&value.to_string());
+    assert!(result.unwrap_err().contains("strafe penalty"));
+}
+
+#[test]
+fn test_loss_bonus_ladder_repeats_last_step() {
+    let economy = BalanceConfig::default().economy;
+    assert_eq!(economy.loss_bonus_for_streak(0), 1900);
+    assert_eq!(economy.loss_bonus_for_streak(1), 2400);
+    assert_eq!(economy.loss_bonus_for_streak(2), 2900);
+    assert_eq!(economy.loss_bonus_for_streak(7), 2900);
+}
+
+#[test]
+fn test_simulation_uses_supplied_balance() {
+    let mut balance = BalanceConfig {
+        version: "test-patch".to_string(),
+        ..BalanceConfig::default()
+    };
+    balance.economy.starting_credits = 4000;
+
+    let mut sim = ValorantSimulation::with_balance(3, balance);
+    common::add_roster(&mut sim, &[Agent::Jett], |_| {
+        common::skills(0.7, 0.5, 0.7, 0.7)
+    });
+    sim.start_simulation();
+
+    assert_eq!(sim.state.patch_version, "test-patch");
+    assert!(sim.players.values().all(|p| p.current_credits == 4000));
+}
@@ -0,0 +1,154 @@
+use vctcareer_backend::balance::BalanceConfig;
+use vctcareer_backend::batch::{
+    BatchSimulationRequest, MAX_BATCH_ITERATIONS, distribution, run_batch, simulate_batch,
+    wilson_interval,
+};
+use vctcareer_backend::maps::Map;
+use vctcareer_backend::models::SimulationPlayer;
+use vctcareer_backend::series::SeriesTeam;
+use vctcareer_backend::sim::{Agent, Player, PlayerSkills, Team};
+
+fn roster(team_a_aim: f32, team_b_aim: f32) -> Vec<Player> {
+    (1..=10u32)
+        .map(|id| {
+            let (team, aim) = if id <= 5 {
+                (Team::Attackers, team_a_aim)
+            } else {
+                (Team::Defenders, team_b_aim)
+            };
+            Player::new(
+                id,
+                format!("Player{}", id),
+                Agent::Jett,
+                team,
+                PlayerSkills {
+                    aim,
+                    hs: aim - 0.2,
+                    movement: 0.7,
+                    util: 0.7,
+                },
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_batch_is_reproducible_from_its_seed() {
+    let players = roster(0.7, 0.7);
+    let balance = BalanceConfig::default();
+    let first = run_batch(&players, 8, 11, &[Map::Ascent, Map::Bind], &balance).unwrap();
+    let second = run_batch(&players, 8, 11, &[Map::Ascent, Map::Bind], &balance).unwrap();
+
+    assert_eq!(
+        serde_json::to_string(&first).unwrap(),
+        serde_json::to_string(&second).unwrap()
+    );
+}
+
+#[test]
+fn test_batch_summarizes_every_match() {
+    let players = roster(0.7, 0.7);
+    let result = run_batch(&players, 10, 5, &[Map::Haven], &BalanceConfig::default()).unwrap();
+
+    assert_eq!(result.iterations, 10);
+    assert_eq!(result.team_a_wins + result.team_b_wins + result.draws, 10);
+    assert_eq!(
+        result
+            .score_distribution
+            .iter()
+            .map(|s| s.count)
+            .sum::<u32>(),
+        10
+    );
+    assert!(result.average_rounds >= 13.0);
+
+    let probability = &result.team_a_win_probability;
+    assert!(probability.lower <= probability.estimate && probability.estimate <= probability.upper);
+
+    assert_eq!(result.players.len(), 10);
+    let first = &result.players[0];
+    assert_eq!(first.player_id, 1);
+    assert_eq!(first.team, SeriesTeam::TeamA);
+    assert!(
+        first.kills.min as f64 <= first.kills.mean && first.kills.mean <= first.kills.max as f64
+    );
+}
+
+#[test]
+fn test_stronger_roster_is_favoured() {
+    let players = roster(0.9, 0.5);
+    let result = run_batch(&players, 12, 3, &[Map::Ascent], &BalanceConfig::default()).unwrap();
+
+    assert!(result.team_a_win_probability.estimate > 0.75);
+    assert!(result.team_a_win_probability.lower > 0.5);
+}
+
+#[test]
+fn test_batch_size_is_capped() {
+    let players = roster(0.7, 0.7);
+    let balance = BalanceConfig::default();
+
+    assert!(run_batch(&players, 0, 1, &[Map::Ascent], &balance).is_err());
+    let error = run_batch(
+        &players,
+        MAX_BATCH_ITERATIONS + 1,
+        1,
+        &[Map::Ascent],
+        &balance,
+    )
+    .unwrap_err();
+    assert!(error.contains("limited"));
+}
+
+#[test]
+fn test_batch_needs_both_teams() {
+    let players: Vec<Player> = roster(0.7, 0.7)
+        .into_iter()
+        .filter(|p| p.team == Team::Attackers)
+        .collect();
+    assert!(run_batch(&players, 2, 1, &[Map::Ascent], &BalanceConfig::default()).is_err());
+}
+
+#[test]
+fn test_simulate_batch_rejects_unknown_maps() {
+    let request = BatchSimulationRequest {
+        players: (1..=2u32)
+            .map(|id| SimulationPlayer {
+                id,
+                name: format!("Player{}", id),
+                agent: "Jett".to_string(),
+                team: if id == 1 { "Attackers" } else { "Defenders" }.to_string(),
+                aim_skill: 0.7,
+                hs_skill: 0.5,
+                movement_skill: 0.7,
+                util_skill: 0.7,
+            })
+            .collect(),
+        iterations: 2,
+        maps: vec!["Atlantis".to_string()],
+        seed: Some(1),
+        patch_version: None,
+    };
+    assert!(simulate_batch(request).unwrap_err().contains("Atlantis"));
+}
+
+#[test]
+fn test_wilson_interval_stays_in_bounds() {
+    let all_wins = wilson_interval(20, 20);
+    assert_eq!(all_wins.estimate, 1.0);
+    assert!(all_wins.lower > 0.8 && all_wins.upper <= 1.0);
+
+    let even = wilson_interval(50, 100);
+    assert!((even.lower - 0.404).abs() < 0.01);
+    assert!((even.upper - 0.596).abs() < 0.01);
+}
+
+#[test]
+fn test_distribution_summary() {
+    let summary = distribution(vec![4, 1, 3, 2]);
+    assert_eq!(summary.min, 1);
+    assert_eq!(summary.max, 4);
+    assert_eq!(summary.mean, 2.5);
+    assert_eq!(summary.median, 2.5);
+    assert!((summary.std_dev - 1.118).abs() < 0.001);
+}
@@ -0,0 +1,242 @@
+mod common;
+
+use uuid::Uuid;
+use vctcareer_backend::balance::BalanceConfig;
+use vctcareer_backend::calendar::{
+    Circuit, Record, StageKind, advance_week, season_schedule, season_weeks, stage_for_week,
+};
+use vctcareer_backend::career::{Career, Contract, FIRST_SEASON};
+use vctcareer_backend::contracts::{ContractOffer, sign_offer};
+use vctcareer_backend::offers::Offer;
+
+fn sign(career: &mut Career, team: &str, months: u32) {
+    career.current_team = Some(team.to_string());
+    career.contract = Some(Contract {
+        team: team.to_string(),
+        region: "NA".to_string(),
+        yearly_salary: 50_000,
+        contract_length_months: months,
+        start_season: career.season,
+        start_week: career.calendar.week,
+    });
+}
+
+#[test]
+fn test_schedules_cover_the_season_without_gaps() {
+    for circuit in [
+        Circuit::Vct,
+        Circuit::Challengers,
+        Circuit::Premier,
+        Circuit::Ranked,
+    ] {
+        let stages = season_schedule(circuit);
+        assert_eq!(stages[0].start_week, 1);
+        for pair in stages.windows(2) {
+            assert_eq!(pair[1].start_week, pair[0].end_week + 1);
+        }
+        assert_eq!(stages.last().unwrap().end_week, season_weeks(circuit));
+        assert!(stage_for_week(circuit, season_weeks(circuit) + 1).is_err());
+    }
+
+    let vct: Vec<String> = season_schedule(Circuit::Vct)
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    for name in [
+        "Kickoff",
+        "Masters 1",
+        "Stage 1",
+        "Masters 2",
+        "Stage 2",
+        "Champions",
+    ] {
+        assert!(vct.contains(&name.to_string()), "VCT is missing {}", name);
+    }
+    assert!(
+        season_schedule(Circuit::Challengers)
+            .iter()
+            .any(|s| s.name.contains("Split"))
+    );
+    assert!(
+        season_schedule(Circuit::Premier)
+            .iter()
+            .all(|s| s.kind == StageKind::Offseason || s.name.starts_with("Premier"))
+    );
+}
+
+#[test]
+fn test_placement_picks_the_circuit() {
+    assert_eq!(
+        common::create_career("Radiant", "Tier 1").calendar.circuit,
+        Circuit::Vct
+    );
+    assert_eq!(
+        common::create_career("Iron", "None").calendar.circuit,
+        Circuit::Ranked
+    );
+}
+
+#[test]
+fn test_free_agent_week_is_training_only() {
+    let mut career = common::create_career("Immortal", "Tier 2");
+    let report = advance_week(&mut career, &[], &BalanceConfig::default()).unwrap();
+
+    assert_eq!(report.week, 1);
+    assert!(report.matches.is_empty());
+    assert_eq!(report.scrims_played, 0);
+    assert!(report.training_sessions > 0);
+    assert_eq!(career.calendar.week, 2);
+    // The stage opener lands on top of the welcome message
+    assert!(
+        report
+            .messages
+            .iter()
+            .any(|m| m.subject.ends_with("begins"))
+    );
+    assert_eq!(career.inbox[0].id, 2);
+}
+
+#[test]
+fn test_team_plays_a_league_series_each_week() {
+    let mut career = common::create_career("Immortal", "Tier 2");
+    sign(&mut career, "M80", 24);
+    let opponents = vec!["M80".to_string(), "Shopify Rebellion".to_string()];
+
+    let mut replay = career.clone();
+    let report = advance_week(&mut career, &opponents, &BalanceConfig::default()).unwrap();
+    let replayed = advance_week(&mut replay, &opponents, &BalanceConfig::default()).unwrap();
+
+    assert_eq!(report.stage.kind, StageKind::League);
+    assert_eq!(report.matches.len(), 1);
+    let series = &report.matches[0];
+    assert_eq!(series.opponent, "Shopify Rebellion");
+    assert_eq!(series.won, series.maps_won > series.maps_lost);
+    assert_eq!(report.scrims_played, 4);
+    let record = career.calendar.stage_record;
+    assert_eq!(record.wins + record.losses, 1);
+
+    // The same week from the same save plays out the same way
+    assert_eq!(replayed.matches[0].seed, series.seed);
+    assert_eq!(replayed.matches[0].kills, series.kills);
+    assert_eq!(replay, career);
+}
+
+#[test]
+fn test_playoffs_need_a_winning_league_record() {
+    let playoffs = season_schedule(Circuit::Premier)
+        .into_iter()
+        .find(|s| s.kind == StageKind::Playoffs)
+        .unwrap();
+
+    let mut qualified = common::create_career("Ascendant", "Tier 3");
+    assert_eq!(qualified.calendar.circuit, Circuit::Premier);
+    sign(&mut qualified, "Premier Stack", 24);
+    qualified.calendar.week = playoffs.start_week;
+    qualified.calendar.last_league_record = Record { wins: 5, losses: 3 };
+    let mut missed = qualified.clone();
+    missed.calendar.last_league_record = Record { wins: 2, losses: 6 };
+
+    let report = advance_week(&mut qualified, &[], &BalanceConfig::default()).unwrap();
+    assert_eq!(report.matches.len(), 1);
+    assert_eq!(qualified.calendar.in_contention, report.matches[0].won);
+
+    let report = advance_week(&mut missed, &[], &BalanceConfig::default()).unwrap();
+    assert!(report.matches.is_empty());
+    assert!(!missed.calendar.in_contention);
+}
+
+#[test]
+fn test_season_rolls_over_and_contracts_expire() {
+    let mut career = common::create_career("Gold", "None");
+    assert_eq!(career.calendar.circuit, Circuit::Ranked);
+    sign(&mut career, "Ranked Stack", 12);
+    let attributes_before = career.attributes.clone();
+
+    for _ in 0..season_weeks(Circuit::Ranked) {
+        advance_week(&mut career, &[], &BalanceConfig::default()).unwrap();
+    }
+
+    assert_eq!(career.season, FIRST_SEASON + 1);
+    assert_eq!(career.profile.age, 22);
+    assert_eq!(career.calendar.week, 1);
+    assert_eq!(career.contract, None);
+    assert_eq!(career.current_team, None);
+    assert!(
+        career
+            .history
+            .iter()
+            .any(|entry| entry.description.contains("Ranked Stack"))
+    );
+    assert!(career.inbox[0].subject.contains("wrap-up"));
+    // A season of training leaves its mark
+    assert!(career.attributes != attributes_before);
+}
+
+#[test]
+fn test_signing_into_another_circuit_moves_the_career_at_season_end() {
+    let mut career = common::create_career("Gold", "None");
+    // A year with a team the player joins next season, as sign_offer makes it
+    sign(&mut career, "Premier Stack", 12);
+    if let Some(contract) = career.contract.as_mut() {
+        contract.start_season += 1;
+    }
+    career.current_team = None;
+    career.calendar.next_circuit = Some(Circuit::Premier);
+
+    for _ in 0..season_weeks(Circuit::Ranked) {
+        advance_week(&mut career, &[], &BalanceConfig::default()).unwrap();
+    }
+    assert_eq!(career.calendar.circuit, Circuit::Premier);
+    assert_eq!(career.calendar.next_circuit, None);
+    assert_eq!(career.current_team.as_deref(), Some("Premier Stack"));
+
+    // The year is played out in the new circuit
+    for _ in 0..season_weeks(Circuit::Premier) {
+        advance_week(&mut career, &[], &BalanceConfig::default()).unwrap();
+    }
+    assert_eq!(career.season, FIRST_SEASON + 2);
+    assert_eq!(career.contract, None);
+    assert_eq!(career.calendar.circuit, Circuit::Premier);
+}
+
+#[test]
+fn test_team_in_another_circuit_plays_no_official_matches_before_the_move() {
+    let mut career = common::create_career("Immortal", "Tier 2");
+    let circuit = career.calendar.circuit;
+    let offer = Offer {
+        team: "Sentinels".to_string(),
+        contract_length_months: 12,
+        yearlysalary: 100_000,
+        region: "Americas".to_string(),
+    };
+    let mut offer = ContractOffer::new(Uuid::from_u128(1), &career, offer, Some(1), 0.5);
+    sign_offer(&mut career, &mut offer, 500_000).unwrap();
+    assert_eq!(career.calendar.next_circuit, Some(Circuit::Vct));
+    assert_eq!(career.current_team, None);
+
+    let opponents = vec!["Sentinels".to_string(), "M80".to_string()];
+    let report = advance_week(&mut career, &opponents, &BalanceConfig::default()).unwrap();
+    assert_eq!(report.stage.kind, StageKind::League);
+    assert!(report.matches.is_empty());
+    assert_eq!(career.calendar.circuit, circuit);
+    assert_eq!(career.current_team, None);
+}
+
+#[test]
+fn test_contract_signed_midseason_runs_from_the_week_joined() {
+    let mut career = common::create_career("Gold", "None");
+    career.calendar.week = season_weeks(Circuit::Ranked) / 2;
+    sign(&mut career, "Ranked Stack", 12);
+
+    let weeks_left = season_weeks(Circuit::Ranked) - career.calendar.week + 1;
+    for _ in 0..weeks_left {
+        advance_week(&mut career, &[], &BalanceConfig::default()).unwrap();
+    }
+    assert_eq!(career.season, FIRST_SEASON + 1);
+    assert!(career.contract.is_some());
+
+    for _ in 0..season_weeks(Circuit::Ranked) {
+        advance_week(&mut career, &[], &BalanceConfig::default()).unwrap();
+    }
+    assert_eq!(career.contract, None);
+}
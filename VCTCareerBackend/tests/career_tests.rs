@@ -0,0 +1,134 @@
+mod common;
+
+use uuid::Uuid;
+use vctcareer_backend::attributes::PlayerAttributes;
+use vctcareer_backend::career::{
+    Career, Contract, FIRST_SEASON, InboxReadFlag, UpdateCareerRequest,
+};
+use vctcareer_backend::placement::place_for_season;
+
+#[test]
+fn test_new_career_is_a_placed_free_agent() {
+    let id = Uuid::new_v4();
+    let career = Career::new(id, &common::career_request("Radiant", "Tier 1")).unwrap();
+
+    assert_eq!(career.id, id.to_string());
+    assert_eq!(career.profile.name, "Demon1");
+    assert_eq!(career.profile.starting_tier, "Tier 1 (VCT)");
+    assert_eq!(career.current_team, None);
+    assert_eq!(career.contract, None);
+    assert_eq!(career.season, FIRST_SEASON);
+    assert_eq!(career.history.len(), 1);
+    assert_eq!(career.inbox.len(), 1);
+    assert!(career.inbox[0].content.contains("Tier 1 (VCT)"));
+}
+
+#[test]
+fn test_career_keeps_the_placement_it_was_given() {
+    let create = common::career_request("Iron", "None");
+    let mut placement = place_for_season(&create.placement_request()).unwrap();
+    placement.tier_name = "Tier 1 (VCT)".to_string();
+    let career = Career::with_placement(Uuid::new_v4(), &create, &placement).unwrap();
+    assert_eq!(career.profile.starting_tier, "Tier 1 (VCT)");
+}
+
+#[test]
+fn test_blank_name_defaults_to_player() {
+    let mut create = common::career_request("Gold", "None");
+    create.name = Some("   ".to_string());
+    let career = Career::new(Uuid::new_v4(), &create).unwrap();
+    assert_eq!(career.profile.name, "Player");
+}
+
+#[test]
+fn test_starting_attributes_follow_rank_and_experience() {
+    let gold = PlayerAttributes::starting("Gold", "None");
+    let radiant = PlayerAttributes::starting("Radiant", "None");
+    let veteran = PlayerAttributes::starting("Gold", "Tier 1");
+
+    assert!(radiant.aim > gold.aim);
+    assert!(radiant.movement > gold.movement);
+    assert_eq!(radiant.igl, gold.igl);
+    assert!(veteran.igl > gold.igl);
+    assert_eq!(veteran.aim, gold.aim);
+}
+
+#[test]
+fn test_new_career_rejects_out_of_range_age() {
+    let mut create = common::career_request("Gold", "None");
+    create.age = 9;
+    assert!(Career::new(Uuid::new_v4(), &create).is_err());
+}
+
+#[test]
+fn test_update_changes_only_editable_fields() {
+    let mut career = Career::new(
+        Uuid::new_v4(),
+        &common::career_request("Immortal", "Tier 2"),
+    )
+    .unwrap();
+    let before = career.clone();
+
+    let update = UpdateCareerRequest {
+        name: Some("  Demon2 ".to_string()),
+        role: Some("Sentinel".to_string()),
+        inbox: vec![InboxReadFlag {
+            id: career.inbox[0].id,
+            read: true,
+        }],
+    };
+    career.apply_update(update).unwrap();
+
+    assert_eq!(career.profile.name, "Demon2");
+    assert_eq!(career.profile.role.as_deref(), Some("Sentinel"));
+    assert!(career.inbox[0].read);
+    assert_eq!(career.id, before.id);
+    assert_eq!(career.attributes, before.attributes);
+    assert_eq!(career.contract, before.contract);
+
+    // Omitted fields are left alone
+    career.apply_update(UpdateCareerRequest::default()).unwrap();
+    assert_eq!(career.profile.name, "Demon2");
+}
+
+#[test]
+fn test_invalid_update_leaves_career_unchanged() {
+    let mut career =
+        Career::new(Uuid::new_v4(), &common::career_request("Diamond", "None")).unwrap();
+    let before = career.clone();
+
+    let blank_name = UpdateCareerRequest {
+        name: Some("   ".to_string()),
+        ..Default::default()
+    };
+    assert!(career.apply_update(blank_name).is_err());
+
+    let unknown_message = UpdateCareerRequest {
+        role: Some("Controller".to_string()),
+        inbox: vec![InboxReadFlag {
+            id: 999,
+            read: true,
+        }],
+        ..Default::default()
+    };
+    let error = career.apply_update(unknown_message).unwrap_err();
+    assert!(error.contains("999"));
+
+    assert_eq!(career, before);
+}
+
+#[test]
+fn test_contract_must_match_the_current_team() {
+    let mut career =
+        Career::new(Uuid::new_v4(), &common::career_request("Diamond", "None")).unwrap();
+    career.contract = Some(Contract {
+        team: "Fnatic".to_string(),
+        region: "EU".to_string(),
+        yearly_salary: 80_000,
+        contract_length_months: 12,
+        start_season: FIRST_SEASON,
+        start_week: 1,
+    });
+    let error = career.validate().unwrap_err();
+    assert!(error.contains("Fnatic"));
+}
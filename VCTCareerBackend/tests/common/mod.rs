@@ -0,0 +1,82 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use uuid::Uuid;
+use vctcareer_backend::career::{Career, CreateCareerRequest};
+use vctcareer_backend::maps::Map;
+use vctcareer_backend::sim::{Agent, Player, PlayerSkills, Team, ValorantSimulation};
+
+/// Adds ten players, 1-5 attacking and 6-10 defending, cycling through
+/// `agents` and asking `skills` for each player id.
+pub fn add_roster(
+    sim: &mut ValorantSimulation,
+    agents: &[Agent],
+    skills: impl Fn(u32) -> PlayerSkills,
+) {
+    for id in 1..=10u32 {
+        let team = if id <= 5 {
+            Team::Attackers
+        } else {
+            Team::Defenders
+        };
+        sim.add_player(Player::new(
+            id,
+            format!("Player{}", id),
+            agents[(id as usize - 1) % agents.len()].clone(),
+            team,
+            skills(id),
+        ));
+    }
+}
+
+/// A seeded match on `map` with `add_roster`'s players, not yet started.
+pub fn seeded_match(
+    seed: u64,
+    map: Map,
+    agents: &[Agent],
+    skills: impl Fn(u32) -> PlayerSkills,
+) -> ValorantSimulation {
+    let mut sim = ValorantSimulation::with_seed(seed);
+    sim.set_map(map).unwrap();
+    add_roster(&mut sim, agents, skills);
+    sim
+}
+
+/// `seeded_match` played to the end.
+pub fn run_seeded_match(
+    seed: u64,
+    map: Map,
+    agents: &[Agent],
+    skills: impl Fn(u32) -> PlayerSkills,
+) -> ValorantSimulation {
+    let mut sim = seeded_match(seed, map, agents, skills);
+    sim.run_simulation_to_completion().unwrap();
+    sim
+}
+
+pub fn skills(aim: f32, hs: f32, movement: f32, util: f32) -> PlayerSkills {
+    PlayerSkills {
+        aim,
+        hs,
+        movement,
+        util,
+    }
+}
+
+pub fn career_request(rank: &str, experience: &str) -> CreateCareerRequest {
+    CreateCareerRequest {
+        name: Some("Demon1".to_string()),
+        age: 21,
+        current_rank: rank.to_string(),
+        past_experience: experience.to_string(),
+        division: "NA".to_string(),
+        role: Some("Duelist".to_string()),
+        rr: None,
+        hidden_mmr: None,
+        placement_season: None,
+    }
+}
+
+pub fn create_career(rank: &str, experience: &str) -> Career {
+    Career::new(Uuid::from_u128(7), &career_request(rank, experience)).unwrap()
+}
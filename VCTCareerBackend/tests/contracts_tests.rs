@@ -0,0 +1,181 @@
+mod common;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use uuid::Uuid;
+use vctcareer_backend::calendar::{Circuit, season_weeks};
+use vctcareer_backend::career::Career;
+use vctcareer_backend::contracts::{
+    ContractOffer, CounterOfferRequest, MAX_COUNTERS, OFFER_VALID_WEEKS, OfferStatus, TeamResponse,
+    respond_to_counter, sign_offer, team_interest, team_limit,
+};
+use vctcareer_backend::offers::Offer;
+
+fn create_career() -> Career {
+    common::create_career("Ascendant", "Tier 3")
+}
+
+fn create_offer(career: &Career, tier: i16, interest: f32) -> ContractOffer {
+    let offer = Offer {
+        team: "Evil Geniuses".to_string(),
+        contract_length_months: 24,
+        yearlysalary: 100_000,
+        region: "Americas".to_string(),
+    };
+    ContractOffer::new(Uuid::from_u128(1), career, offer, Some(tier), interest)
+}
+
+fn counter(yearly_salary: u32, contract_length_months: u32) -> CounterOfferRequest {
+    CounterOfferRequest {
+        yearly_salary,
+        contract_length_months,
+    }
+}
+
+#[test]
+fn test_new_offer_is_open_and_expires_in_game_weeks() {
+    let mut career = create_career();
+    let offer = create_offer(&career, 3, 0.5);
+    assert_eq!(offer.status, OfferStatus::Open);
+    assert_eq!(offer.career_id, career.id);
+    assert_eq!(offer.negotiation.len(), 1);
+    assert_eq!(offer.expires_season, career.season);
+    assert_eq!(offer.expires_week, career.calendar.week + OFFER_VALID_WEEKS);
+
+    career.calendar.week += OFFER_VALID_WEEKS;
+    assert!(!offer.is_expired(&career));
+    career.calendar.week += 1;
+    assert!(offer.is_expired(&career));
+
+    // Offers made at the end of a season run into the next one
+    career.calendar.week = season_weeks(career.calendar.circuit);
+    let late = create_offer(&career, 3, 0.5);
+    assert_eq!(late.expires_season, career.season + 1);
+    assert_eq!(late.expires_week, OFFER_VALID_WEEKS);
+}
+
+#[test]
+fn test_interest_follows_overall_against_tier() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..20 {
+        let tier1 = team_interest(50, Some(1), &mut rng);
+        let tier3 = team_interest(50, Some(3), &mut rng);
+        assert!(tier3 > tier1);
+        assert!((0.05..=1.0).contains(&tier1));
+    }
+}
+
+#[test]
+fn test_team_agrees_within_its_limit() {
+    let career = create_career();
+    let mut offer = create_offer(&career, 3, 0.5);
+    let limit = team_limit(&offer, career.attributes.overall(), 24);
+    assert!(limit > offer.opening_salary);
+
+    let outcome = respond_to_counter(&mut offer, &counter(limit, 24), &career, 1_000_000).unwrap();
+    assert_eq!(outcome.response, TeamResponse::Agreed);
+    assert_eq!(offer.yearly_salary, limit);
+    assert_eq!(offer.status, OfferStatus::Open);
+    assert_eq!(offer.negotiation.len(), 3);
+}
+
+#[test]
+fn test_team_meets_partway_then_walks_away() {
+    let career = create_career();
+    let mut offer = create_offer(&career, 3, 0.5);
+    let greedy = counter(1_000_000, 24);
+
+    let outcome = respond_to_counter(&mut offer, &greedy, &career, 10_000_000).unwrap();
+    assert_eq!(outcome.response, TeamResponse::Countered);
+    assert!(offer.yearly_salary > offer.opening_salary);
+    assert!(offer.interest < 0.5);
+
+    for _ in 1..MAX_COUNTERS {
+        respond_to_counter(&mut offer, &greedy, &career, 10_000_000).unwrap();
+    }
+    assert_eq!(offer.status, OfferStatus::Withdrawn);
+    assert!(respond_to_counter(&mut offer, &greedy, &career, 10_000_000).is_err());
+}
+
+#[test]
+fn test_budget_caps_what_the_team_agrees_to() {
+    let career = create_career();
+    let mut offer = create_offer(&career, 3, 1.0);
+    let outcome = respond_to_counter(&mut offer, &counter(110_000, 24), &career, 105_000).unwrap();
+    assert_eq!(outcome.response, TeamResponse::Declined);
+    assert_eq!(offer.yearly_salary, 100_000);
+    assert_eq!(offer.status, OfferStatus::Open);
+}
+
+#[test]
+fn test_unwanted_lengths_lower_the_limit() {
+    let career = create_career();
+    let overall = career.attributes.overall();
+    assert!(overall < 60, "starting players are short-term signings");
+    let offer = create_offer(&career, 3, 0.5);
+    assert!(team_limit(&offer, overall, 48) < team_limit(&offer, overall, 24));
+    assert_eq!(
+        team_limit(&offer, overall, 12),
+        team_limit(&offer, overall, 24)
+    );
+
+    // Better players are worth more, and worth keeping longer
+    assert!(team_limit(&offer, overall + 20, 24) > team_limit(&offer, overall, 24));
+    assert!(team_limit(&offer, 80, 12) < team_limit(&offer, 80, 24));
+}
+
+#[test]
+fn test_invalid_counters_are_rejected() {
+    let career = create_career();
+    let mut offer = create_offer(&career, 3, 0.5);
+    assert!(respond_to_counter(&mut offer, &counter(0, 24), &career, 1_000_000).is_err());
+    assert!(respond_to_counter(&mut offer, &counter(90_000, 120), &career, 1_000_000).is_err());
+    assert_eq!(offer.counters, 0);
+}
+
+#[test]
+fn test_signing_creates_the_contract() {
+    let mut career = create_career();
+    let mut offer = create_offer(&career, 3, 0.5);
+    let contract = sign_offer(&mut career, &mut offer, 500_000).unwrap();
+
+    assert_eq!(offer.status, OfferStatus::Accepted);
+    assert_eq!(career.current_team.as_deref(), Some("Evil Geniuses"));
+    assert_eq!(career.contract.as_ref(), Some(&contract));
+    assert_eq!(contract.yearly_salary, 100_000);
+    assert_eq!(contract.start_season, career.season);
+    assert_eq!(contract.start_week, career.calendar.week);
+    assert_eq!(career.inbox[0].subject, "Signed with Evil Geniuses");
+    assert!(career.validate().is_ok());
+
+    // Already signed, so the next offer can't be taken
+    let mut another = create_offer(&career, 3, 0.5);
+    assert!(sign_offer(&mut career, &mut another, 500_000).is_err());
+}
+
+#[test]
+fn test_signing_needs_budget_and_an_open_offer() {
+    let mut career = create_career();
+    let mut offer = create_offer(&career, 3, 0.5);
+    assert!(sign_offer(&mut career, &mut offer, 50_000).is_err());
+    assert!(career.contract.is_none());
+
+    career.calendar.week += OFFER_VALID_WEEKS + 1;
+    assert!(sign_offer(&mut career, &mut offer, 500_000).is_err());
+}
+
+#[test]
+fn test_signing_in_another_circuit_moves_the_career_next_season() {
+    let mut career = create_career();
+    let circuit = career.calendar.circuit;
+    let mut offer = create_offer(&career, 1, 0.5);
+    let contract = sign_offer(&mut career, &mut offer, 500_000).unwrap();
+    assert_eq!(career.calendar.circuit, circuit);
+    assert_eq!(career.calendar.next_circuit, Some(Circuit::Vct));
+    // Rosters are locked, so they aren't the player's team until then
+    assert_eq!(career.current_team, None);
+    assert!(career.validate().is_ok());
+    // The term starts when the player joins, not when they sign
+    assert_eq!(contract.start_season, career.season + 1);
+    assert_eq!(contract.start_week, 1);
+}
@@ -0,0 +1,275 @@
+use vctcareer_backend::balance::BalanceConfig;
+use vctcareer_backend::economy::{
+    BuyType, RoundOutcome, TeamBuyContext, add_credits, decide_team_buy, full_buy_cost,
+    next_loss_streak, round_credits,
+};
+use vctcareer_backend::sim::{
+    Agent, GameEvent, Player, PlayerSkills, RoundEndReason, Team, ValorantSimulation,
+};
+
+fn context(credits_each: u32) -> TeamBuyContext {
+    TeamBuyContext {
+        credits: vec![credits_each; 5],
+        ..TeamBuyContext::default()
+    }
+}
+
+fn outcome(winner: Team, reason: RoundEndReason, spike_planted: bool) -> RoundOutcome {
+    RoundOutcome {
+        winner,
+        reason,
+        spike_planted,
+    }
+}
+
+#[test]
+fn test_winners_get_the_win_reward_only() {
+    let economy = BalanceConfig::default().economy;
+    let detonated = outcome(Team::Attackers, RoundEndReason::SpikeDetonated, true);
+
+    // No plant bonus on top of a won round
+    assert_eq!(
+        round_credits(&Team::Attackers, true, 2, &detonated, &economy),
+        economy.round_win_reward
+    );
+    assert_eq!(
+        round_credits(&Team::Attackers, false, 0, &detonated, &economy),
+        economy.round_win_reward
+    );
+}
+
+#[test]
+fn test_losers_get_the_loss_bonus_for_their_streak() {
+    let economy = BalanceConfig::default().economy;
+    let eliminated = outcome(
+        Team::Defenders,
+        RoundEndReason::AllAttackersEliminated,
+        false,
+    );
+
+    for streak in 0..5 {
+        assert_eq!(
+            round_credits(&Team::Attackers, false, streak, &eliminated, &economy),
+            economy.loss_bonus_for_streak(streak)
+        );
+    }
+}
+
+#[test]
+fn test_defenders_saving_through_detonation_get_no_loss_bonus() {
+    let economy = BalanceConfig::default().economy;
+    let detonated = outcome(Team::Attackers, RoundEndReason::SpikeDetonated, true);
+
+    assert_eq!(
+        round_credits(&Team::Defenders, true, 1, &detonated, &economy),
+        0
+    );
+    // Defenders who died trying to retake still get it
+    assert_eq!(
+        round_credits(&Team::Defenders, false, 1, &detonated, &economy),
+        economy.loss_bonus_for_streak(1)
+    );
+}
+
+#[test]
+fn test_attackers_surviving_a_defuse_keep_loss_and_plant_bonus() {
+    let economy = BalanceConfig::default().economy;
+    let defused = outcome(Team::Defenders, RoundEndReason::SpikeDefused, true);
+
+    assert_eq!(
+        round_credits(&Team::Attackers, true, 0, &defused, &economy),
+        economy.loss_bonus_for_streak(0) + economy.spike_plant_reward
+    );
+}
+
+#[test]
+fn test_plant_bonus_needs_a_plant() {
+    let economy = BalanceConfig::default().economy;
+    let planted = outcome(
+        Team::Defenders,
+        RoundEndReason::AllAttackersEliminated,
+        true,
+    );
+    let not_planted = outcome(Team::Defenders, RoundEndReason::TimeExpired, false);
+
+    assert_eq!(
+        round_credits(&Team::Attackers, false, 0, &planted, &economy),
+        economy.loss_bonus_for_streak(0) + economy.spike_plant_reward
+    );
+    assert_eq!(
+        round_credits(&Team::Attackers, false, 0, &not_planted, &economy),
+        economy.loss_bonus_for_streak(0)
+    );
+    // Defenders never get it
+    let lost_defending = outcome(
+        Team::Attackers,
+        RoundEndReason::AllDefendersEliminated,
+        true,
+    );
+    assert_eq!(
+        round_credits(&Team::Defenders, false, 0, &lost_defending, &economy),
+        economy.loss_bonus_for_streak(0)
+    );
+}
+
+#[test]
+fn test_loss_streak_resets_on_win_and_grows_on_loss() {
+    let won = outcome(Team::Attackers, RoundEndReason::SpikeDetonated, true);
+
+    assert_eq!(next_loss_streak(&Team::Attackers, 3, &won), 0);
+    assert_eq!(next_loss_streak(&Team::Defenders, 1, &won), 2);
+    assert_eq!(next_loss_streak(&Team::Defenders, u8::MAX, &won), u8::MAX);
+}
+
+#[test]
+fn test_credits_are_capped() {
+    let economy = BalanceConfig::default().economy;
+    assert_eq!(
+        add_credits(economy.credit_cap - 100, 3000, &economy),
+        economy.credit_cap
+    );
+    assert_eq!(add_credits(1000, 1900, &economy), 2900);
+}
+
+#[test]
+fn test_pistol_rounds_are_pistol_buys() {
+    let balance = BalanceConfig::default();
+    let context = TeamBuyContext {
+        pistol_round: true,
+        ..context(800)
+    };
+    assert_eq!(decide_team_buy(&context, &balance), BuyType::Pistol);
+}
+
+#[test]
+fn test_full_buy_when_team_average_covers_rifles() {
+    let balance = BalanceConfig::default();
+    let full_cost = full_buy_cost(&balance);
+    assert_eq!(
+        decide_team_buy(&context(full_cost), &balance),
+        BuyType::FullBuy
+    );
+
+    // One rich player can cover a poor teammate through drops
+    let context = TeamBuyContext {
+        credits: vec![full_cost * 2, 0, full_cost, full_cost, full_cost],
+        ..TeamBuyContext::default()
+    };
+    assert_eq!(decide_team_buy(&context, &balance), BuyType::FullBuy);
+}
+
+#[test]
+fn test_team_saves_when_it_cannot_afford_a_buy() {
+    let balance = BalanceConfig::default();
+    assert_eq!(decide_team_buy(&context(1000), &balance), BuyType::Eco);
+}
+
+#[test]
+fn test_team_forces_when_saving_is_pointless() {
+    let balance = BalanceConfig::default();
+    let last_of_half = TeamBuyContext {
+        last_round_of_half: true,
+        ..context(1000)
+    };
+    assert_eq!(decide_team_buy(&last_of_half, &balance), BuyType::Force);
+
+    let match_point = TeamBuyContext {
+        facing_elimination: true,
+        ..context(1000)
+    };
+    assert_eq!(decide_team_buy(&match_point, &balance), BuyType::Force);
+}
+
+#[test]
+fn test_winners_half_buy_after_pistol_and_play_bonus_later() {
+    let balance = BalanceConfig::default();
+    let after_pistol = TeamBuyContext {
+        won_last_round: true,
+        after_pistol_round: true,
+        ..context(3000)
+    };
+    assert_eq!(decide_team_buy(&after_pistol, &balance), BuyType::HalfBuy);
+
+    let bonus = TeamBuyContext {
+        won_last_round: true,
+        ..context(3000)
+    };
+    assert_eq!(decide_team_buy(&bonus, &balance), BuyType::Bonus);
+}
+
+#[test]
+fn test_long_loss_streak_allows_half_buy() {
+    let balance = BalanceConfig::default();
+    // The top loss bonus still funds a full buy next round after spending now
+    let streak = TeamBuyContext {
+        loss_streak: 2,
+        ..context(3100)
+    };
+    assert_eq!(decide_team_buy(&streak, &balance), BuyType::HalfBuy);
+
+    let no_streak = TeamBuyContext {
+        loss_streak: 0,
+        ..context(3100)
+    };
+    assert_eq!(decide_team_buy(&no_streak, &balance), BuyType::Eco);
+}
+
+#[test]
+fn test_each_team_logs_one_buy_decision_per_round() {
+    let mut sim = ValorantSimulation::with_seed(3);
+    for id in 1..=10u32 {
+        let team = if id <= 5 {
+            Team::Attackers
+        } else {
+            Team::Defenders
+        };
+        sim.add_player(Player::new(
+            id,
+            format!("Player{}", id),
+            Agent::Sova,
+            team,
+            PlayerSkills {
+                aim: 0.7,
+                hs: 0.3,
+                movement: 0.6,
+                util: 0.6,
+            },
+        ));
+    }
+    sim.run_simulation_to_completion().unwrap();
+
+    let rounds_played = sim
+        .events
+        .iter()
+        .filter(|e| matches!(e, GameEvent::RoundEnd { .. }))
+        .count();
+    let buys: Vec<_> = sim
+        .events
+        .iter()
+        .filter_map(|e| match e {
+            GameEvent::TeamBuy {
+                round_number,
+                buy_type,
+                drops,
+                ..
+            } => Some((*round_number, *buy_type, drops.clone())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(buys.len(), rounds_played * 2);
+
+    for (round_number, buy_type, drops) in &buys {
+        if *round_number == 1 || *round_number == 13 {
+            assert_eq!(*buy_type, BuyType::Pistol);
+        }
+        for drop in drops {
+            assert_eq!(*buy_type, BuyType::FullBuy);
+            // Sides swap at half time, so compare the two players with each other
+            assert_eq!(
+                sim.players[&drop.buyer_id].team,
+                sim.players[&drop.recipient_id].team
+            );
+            assert_ne!(drop.buyer_id, drop.recipient_id);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use vctcareer_backend::maps::{Map, parse_map};
+use vctcareer_backend::sim::{Agent, GameEvent, ValorantSimulation};
+
+const AGENTS: [Agent; 5] = [
+    Agent::Jett,
+    Agent::Sova,
+    Agent::Omen,
+    Agent::Killjoy,
+    Agent::Breach,
+];
+
+#[test]
+fn test_every_map_has_sites() {
+    for map in Map::all() {
+        let sites = map.sites();
+        assert!(sites.len() >= 2, "{:?}", map);
+        for layout in sites {
+            assert!(layout.attacker_travel_ms > 0);
+            assert!(layout.choke_range_m.0 < layout.choke_range_m.1);
+        }
+    }
+}
+
+#[test]
+fn test_parse_map() {
+    assert_eq!(parse_map("Haven").unwrap(), Map::Haven);
+    assert_eq!(parse_map("lotus").unwrap(), Map::Lotus);
+    assert!(parse_map("Dust2").is_err());
+}
+
+#[test]
+fn test_map_cannot_change_after_start() {
+    let mut sim = ValorantSimulation::with_seed(1);
+    sim.set_map(Map::Bind).unwrap();
+    sim.start_simulation();
+    assert!(sim.set_map(Map::Split).is_err());
+    assert_eq!(sim.state.map, Map::Bind);
+}
+
+#[test]
+fn test_spike_is_planted_on_a_site_of_the_map() {
+    let sim = common::run_seeded_match(8, Map::Haven, &AGENTS, |_| {
+        common::skills(0.7, 0.3, 0.6, 0.6)
+    });
+
+    let planted_sites: Vec<_> = sim
+        .events
+        .iter()
+        .filter_map(|e| match e {
+            GameEvent::SpikePlant { site, .. } => Some(site.expect("plant without a site")),
+            _ => None,
+        })
+        .collect();
+    assert!(!planted_sites.is_empty());
+    assert!(
+        planted_sites
+            .iter()
+            .all(|site| Map::Haven.site(*site).is_some())
+    );
+}
+
+#[test]
+fn test_map_choice_changes_outcomes() {
+    let ascent = common::run_seeded_match(12, Map::Ascent, &AGENTS, |_| {
+        common::skills(0.7, 0.3, 0.6, 0.6)
+    });
+    let split = common::run_seeded_match(12, Map::Split, &AGENTS, |_| {
+        common::skills(0.7, 0.3, 0.6, 0.6)
+    });
+
+    assert_ne!(
+        serde_json::to_string(&ascent.events).unwrap(),
+        serde_json::to_string(&split.events).unwrap()
+    );
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use vctcareer_backend::maps::Map;
+use vctcareer_backend::persistence::{
+    MIGRATIONS, SimulationRecord, rebuild_simulation, starting_roster,
+};
+use vctcareer_backend::series::SeriesTeam;
+use vctcareer_backend::sim::{Agent, MatchTeams, SimulationMode, Team, ValorantSimulation};
+
+fn create_simulation(seed: u64) -> ValorantSimulation {
+    let mut sim = common::seeded_match(seed, Map::Ascent, &[Agent::Omen], |id| {
+        common::skills(0.6 + (id % 4) as f32 * 0.05, 0.4, 0.7, 0.6)
+    });
+    sim.state.teams = MatchTeams {
+        starting_attackers: Some("Sentinels".to_string()),
+        starting_defenders: Some("Fnatic".to_string()),
+    };
+    sim
+}
+
+#[test]
+fn test_migrations_are_ordered_and_unique() {
+    let versions: Vec<i32> = MIGRATIONS.iter().map(|(version, _, _)| *version).collect();
+    let mut sorted = versions.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(versions, sorted);
+    assert!(MIGRATIONS.iter().all(|(_, _, sql)| !sql.trim().is_empty()));
+}
+
+#[test]
+fn test_stepping_and_running_a_match_agree() {
+    let mut stepped = create_simulation(12);
+    while !matches!(
+        stepped.state.phase,
+        vctcareer_backend::sim::SimulationPhase::MatchEnd { .. }
+    ) {
+        stepped.advance_round().unwrap();
+    }
+    let mut run = create_simulation(12);
+    run.run_simulation_to_completion().unwrap();
+
+    assert_eq!(
+        serde_json::to_string(&stepped.events).unwrap(),
+        serde_json::to_string(&run.events).unwrap()
+    );
+}
+
+#[test]
+fn test_rebuild_resumes_where_the_simulation_was() {
+    let mut sim = create_simulation(31);
+    for _ in 0..3 {
+        sim.advance_round().unwrap();
+    }
+    sim.advance_multiple_ticks(17).unwrap();
+
+    let record = SimulationRecord::capture(&sim);
+    assert!(!record.completed);
+    let mut rebuilt = rebuild_simulation(&record).unwrap();
+
+    assert_eq!(rebuilt.state.id, sim.state.id);
+    assert_eq!(rebuilt.state.tick_count, sim.state.tick_count);
+    assert_eq!(rebuilt.state.teams, sim.state.teams);
+    assert!(matches!(rebuilt.state.mode, SimulationMode::Paused));
+    assert_eq!(
+        serde_json::to_string(&rebuilt.events).unwrap(),
+        serde_json::to_string(&sim.events).unwrap()
+    );
+
+    // And carries on exactly as the original would have
+    rebuilt.state.mode = SimulationMode::Playing;
+    sim.run_simulation_to_completion().unwrap();
+    rebuilt.run_simulation_to_completion().unwrap();
+    assert_eq!(
+        serde_json::to_string(&rebuilt.events).unwrap(),
+        serde_json::to_string(&sim.events).unwrap()
+    );
+}
+
+#[test]
+fn test_starting_roster_undoes_side_swaps() {
+    let mut sim = create_simulation(5);
+    for _ in 0..13 {
+        sim.advance_round().unwrap();
+    }
+    sim.advance_multiple_ticks(2).unwrap();
+    assert!(sim.sides_swapped());
+    assert_eq!(sim.players[&1].team, Team::Defenders);
+
+    let roster = starting_roster(&sim);
+    assert_eq!(roster.len(), 10);
+    assert!(
+        roster
+            .iter()
+            .all(|p| (p.team == Team::Attackers) == (p.id <= 5))
+    );
+    assert!(roster.iter().all(|p| p.current_credits == 0 && p.is_alive));
+}
+
+#[test]
+fn test_completed_record_scores_by_roster() {
+    let mut sim = create_simulation(8);
+    sim.run_simulation_to_completion().unwrap();
+    let record = SimulationRecord::capture(&sim);
+
+    assert!(record.completed);
+    let team_a_side = sim.players[&1].team.clone();
+    let (team_a_score, team_b_score) = match team_a_side {
+        Team::Attackers => (sim.state.attacker_score, sim.state.defender_score),
+        Team::Defenders => (sim.state.defender_score, sim.state.attacker_score),
+    };
+    assert_eq!(record.team_a_score, team_a_score);
+    assert_eq!(record.team_b_score, team_b_score);
+    let expected_winner = if team_a_score > team_b_score {
+        SeriesTeam::TeamA
+    } else {
+        SeriesTeam::TeamB
+    };
+    assert_eq!(record.winner, Some(expected_winner));
+}
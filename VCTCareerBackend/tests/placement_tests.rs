@@ -0,0 +1,124 @@
+use vctcareer_backend::placement::{
+    DEFAULT_PLACEMENT_SEASON, PlacementModel, PlacementRequest, StartingTier, place,
+    place_for_season,
+};
+
+fn request(rank: &str, experience: &str, region: &str) -> PlacementRequest {
+    PlacementRequest {
+        age: 20,
+        current_rank: rank.to_string(),
+        past_experience: experience.to_string(),
+        region: region.to_string(),
+        rr: None,
+        hidden_mmr: None,
+        season: None,
+    }
+}
+
+#[test]
+fn test_default_model_is_the_default_season_file() {
+    let loaded = PlacementModel::load(DEFAULT_PLACEMENT_SEASON).unwrap();
+    let built_in = PlacementModel::default();
+    assert_eq!(loaded.season, built_in.season);
+    assert_eq!(loaded.thresholds, built_in.thresholds);
+    assert_eq!(loaded.rank_scores, built_in.rank_scores);
+}
+
+#[test]
+fn test_factors_add_up_to_the_score_and_odds_to_one() {
+    let model = PlacementModel::default();
+    for rank in ["Iron", "Gold", "Diamond", "Immortal", "Radiant"] {
+        for experience in ["None", "Tier 3", "Tier 1"] {
+            let result = place(&request(rank, experience, "EU"), &model).unwrap();
+            let sum: f32 = result.factors.iter().map(|f| f.contribution).sum();
+            assert!((sum - result.score).abs() < 1e-4);
+
+            let total: f32 = result.probabilities.iter().map(|p| p.probability).sum();
+            assert!((total - 1.0).abs() < 1e-4, "odds sum to {}", total);
+            assert!(result.probabilities.iter().all(|p| p.probability >= 0.0));
+        }
+    }
+}
+
+#[test]
+fn test_placed_tier_is_the_most_likely_one_well_inside_a_band() {
+    let model = PlacementModel::default();
+    let result = place(&request("Radiant", "Tier 1", "NA"), &model).unwrap();
+    assert_eq!(result.tier, StartingTier::Tier1);
+    let likeliest = result
+        .probabilities
+        .iter()
+        .max_by(|a, b| a.probability.total_cmp(&b.probability))
+        .unwrap();
+    assert_eq!(likeliest.tier, StartingTier::Tier1);
+}
+
+#[test]
+fn test_ranks_below_platinum_now_count() {
+    let model = PlacementModel::default();
+    let iron = place(&request("Iron", "None", "NA"), &model).unwrap();
+    let gold = place(&request("Gold", "None", "NA"), &model).unwrap();
+    assert!(gold.score > iron.score);
+}
+
+#[test]
+fn test_region_aliases_and_fallback() {
+    let model = PlacementModel::default();
+    let result = place(&request("Diamond", "None", " na "), &model).unwrap();
+    assert_eq!(result.region, "americas");
+
+    let result = place(&request("Diamond", "None", "KR"), &model).unwrap();
+    assert_eq!(result.region, "pacific");
+
+    let result = place(&request("Diamond", "None", "Atlantis"), &model).unwrap();
+    assert_eq!(result.region, "other");
+}
+
+#[test]
+fn test_region_thresholds_override_the_season() {
+    let model = PlacementModel::default();
+    let result = place(&request("Diamond", "None", "CN"), &model).unwrap();
+    assert_eq!(result.region, "china");
+    assert_eq!(
+        result.thresholds,
+        model.regions["china"].thresholds.unwrap()
+    );
+}
+
+#[test]
+fn test_ranked_data_moves_the_score() {
+    let model = PlacementModel::default();
+    let base = place(&request("Immortal", "None", "EU"), &model).unwrap();
+
+    let mut with_rr = request("Immortal", "None", "EU");
+    with_rr.rr = Some(90);
+    let with_rr = place(&with_rr, &model).unwrap();
+    assert!(with_rr.score > base.score);
+    assert!(with_rr.factors.iter().any(|f| f.factor == "RR"));
+
+    let mut high_mmr = request("Immortal", "None", "EU");
+    high_mmr.hidden_mmr = Some(3000);
+    let mut low_mmr = request("Immortal", "None", "EU");
+    low_mmr.hidden_mmr = Some(0);
+    let high = place(&high_mmr, &model).unwrap();
+    let low = place(&low_mmr, &model).unwrap();
+    assert!(high.score > base.score);
+    assert!(low.score < base.score);
+}
+
+#[test]
+fn test_unknown_rank_is_an_error() {
+    let result = place_for_season(&request("Grandmaster", "None", "NA"));
+    assert!(result.unwrap_err().contains("Unknown rank"));
+}
+
+#[test]
+fn test_invalid_seasons_are_rejected() {
+    assert!(PlacementModel::load("../2025").is_err());
+    assert!(PlacementModel::load("").is_err());
+    assert!(PlacementModel::load("1999").is_err());
+
+    let mut req = request("Gold", "None", "NA");
+    req.season = Some("1999".to_string());
+    assert!(place_for_season(&req).is_err());
+}
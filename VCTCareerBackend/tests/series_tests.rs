@@ -0,0 +1,208 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use vctcareer_backend::balance::BalanceConfig;
+use vctcareer_backend::maps::Map;
+use vctcareer_backend::models::SimulationPlayer;
+use vctcareer_backend::series::{
+    CreateSeriesRequest, SeriesFormat, SeriesTeam, VetoAction, create_series,
+    create_series_manager, get_series, get_series_scoreboard, parse_series_format, run_series,
+    run_veto,
+};
+use vctcareer_backend::sim::{Agent, Player, PlayerSkills, Team};
+
+const POOL: [Map; 7] = [
+    Map::Ascent,
+    Map::Bind,
+    Map::Haven,
+    Map::Split,
+    Map::Lotus,
+    Map::Sunset,
+    Map::Abyss,
+];
+
+fn roster() -> Vec<Player> {
+    (1..=10u32)
+        .map(|id| {
+            let team = if id <= 5 {
+                Team::Attackers
+            } else {
+                Team::Defenders
+            };
+            Player::new(
+                id,
+                format!("Player{}", id),
+                Agent::Sova,
+                team,
+                PlayerSkills {
+                    aim: 0.7,
+                    hs: 0.3,
+                    movement: 0.6,
+                    util: 0.6,
+                },
+            )
+        })
+        .collect()
+}
+
+fn veto_actions(format: SeriesFormat) -> Vec<VetoAction> {
+    let mut rng = StdRng::seed_from_u64(1);
+    run_veto(format, &POOL, &mut rng)
+        .unwrap()
+        .iter()
+        .map(|step| step.action)
+        .collect()
+}
+
+#[test]
+fn test_parse_series_format() {
+    assert_eq!(parse_series_format("BO3").unwrap(), SeriesFormat::Bo3);
+    assert_eq!(parse_series_format("bo5").unwrap(), SeriesFormat::Bo5);
+    assert!(parse_series_format("BO2").is_err());
+}
+
+#[test]
+fn test_veto_follows_the_standard_order() {
+    use VetoAction::{Ban, Decider, Pick};
+
+    assert_eq!(
+        veto_actions(SeriesFormat::Bo1),
+        vec![Ban, Ban, Ban, Ban, Ban, Ban, Decider]
+    );
+    assert_eq!(
+        veto_actions(SeriesFormat::Bo3),
+        vec![Ban, Ban, Pick, Pick, Ban, Ban, Decider]
+    );
+    assert_eq!(
+        veto_actions(SeriesFormat::Bo5),
+        vec![Ban, Ban, Pick, Pick, Pick, Pick, Decider]
+    );
+}
+
+#[test]
+fn test_veto_uses_every_map_once_and_alternates_turns() {
+    let mut rng = StdRng::seed_from_u64(9);
+    let veto = run_veto(SeriesFormat::Bo3, &POOL, &mut rng).unwrap();
+
+    let mut maps: Vec<Map> = veto.iter().map(|step| step.map).collect();
+    maps.sort_by_key(|map| format!("{:?}", map));
+    maps.dedup();
+    assert_eq!(maps.len(), POOL.len());
+
+    let turns: Vec<_> = veto.iter().filter_map(|step| step.team).collect();
+    assert!(turns.windows(2).all(|pair| pair[0] != pair[1]));
+    assert_eq!(turns[0], SeriesTeam::TeamA);
+    assert_eq!(veto.last().unwrap().team, None);
+}
+
+#[test]
+fn test_veto_rejects_a_pool_too_small_for_the_format() {
+    let mut rng = StdRng::seed_from_u64(1);
+    assert!(run_veto(SeriesFormat::Bo5, &POOL[..4], &mut rng).is_err());
+    // Too small for the opening bans, but enough maps to play
+    let veto = run_veto(SeriesFormat::Bo3, &POOL[..3], &mut rng).unwrap();
+    assert!(veto.iter().all(|step| step.action != VetoAction::Ban));
+}
+
+#[test]
+fn test_series_stops_once_a_team_has_won_enough_maps() {
+    let balance = BalanceConfig::default();
+    let series = run_series(SeriesFormat::Bo3, &POOL, &roster(), 5, &balance).unwrap();
+
+    assert!((2..=3).contains(&series.maps.len()));
+    let winner_maps = series
+        .maps
+        .iter()
+        .filter(|map| map.winner == series.winner)
+        .count();
+    assert_eq!(winner_maps, 2);
+    assert_eq!(
+        series.team_a_maps + series.team_b_maps,
+        series.maps.len() as u8
+    );
+
+    // Maps are played in veto order with team A's starting side alternating
+    let veto_maps: Vec<Map> = series
+        .veto
+        .iter()
+        .filter(|step| step.action != VetoAction::Ban)
+        .map(|step| step.map)
+        .collect();
+    for (index, map) in series.maps.iter().enumerate() {
+        assert_eq!(map.map, veto_maps[index]);
+        let expected_side = if index % 2 == 0 {
+            Team::Attackers
+        } else {
+            Team::Defenders
+        };
+        assert_eq!(map.team_a_starting_side, expected_side);
+        let winner_score = map.team_a_score.max(map.team_b_score);
+        assert!(winner_score >= 13);
+        assert_eq!(
+            map.winner == SeriesTeam::TeamA,
+            map.team_a_score > map.team_b_score
+        );
+    }
+}
+
+#[test]
+fn test_series_is_reproducible_from_its_seed() {
+    let balance = BalanceConfig::default();
+    let first = run_series(SeriesFormat::Bo3, &POOL, &roster(), 77, &balance).unwrap();
+    let second = run_series(SeriesFormat::Bo3, &POOL, &roster(), 77, &balance).unwrap();
+
+    assert_eq!(first.veto, second.veto);
+    let scores = |series: &vctcareer_backend::series::SeriesResult| {
+        series
+            .maps
+            .iter()
+            .map(|map| (map.map, map.team_a_score, map.team_b_score))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(scores(&first), scores(&second));
+}
+
+#[test]
+fn test_series_scoreboard_sums_every_map() {
+    let manager = create_series_manager();
+    let players = (1..=10u32)
+        .map(|id| SimulationPlayer {
+            id,
+            name: format!("Player{}", id),
+            agent: "Jett".to_string(),
+            team: if id <= 5 { "Attackers" } else { "Defenders" }.to_string(),
+            aim_skill: 0.7,
+            hs_skill: 0.3,
+            movement_skill: 0.6,
+            util_skill: 0.6,
+        })
+        .collect();
+    let series = create_series(
+        &manager,
+        CreateSeriesRequest {
+            players,
+            format: "BO3".to_string(),
+            maps: POOL.iter().map(|map| format!("{:?}", map)).collect(),
+            seed: Some(3),
+            patch_version: None,
+        },
+    )
+    .unwrap();
+
+    let stored = get_series(&manager, &series.id).unwrap();
+    assert_eq!(stored.winner, series.winner);
+
+    let scoreboard = get_series_scoreboard(&manager, &series.id).unwrap();
+    assert_eq!(scoreboard.map_scores.len(), series.maps.len());
+    for player in &scoreboard.players {
+        assert_eq!(player.maps_played as usize, series.maps.len());
+        let kills: u32 = series
+            .maps
+            .iter()
+            .flat_map(|map| &map.player_stats)
+            .filter(|stats| stats.player_id == player.player_id)
+            .map(|stats| stats.kills)
+            .sum();
+        assert_eq!(player.kills, kills);
+    }
+    assert!(get_series(&manager, "not-a-uuid").is_err());
+}
@@ -0,0 +1,451 @@
+mod common;
+
+use vctcareer_backend::balance::BalanceConfig;
+use vctcareer_backend::maps::Map;
+use vctcareer_backend::sim::{
+    Agent, EventFilter, GameEvent, OvertimeSettings, Player, PlayerSkills, SimulationPhase, Team,
+    TiebreakMode, ValorantSimulation, Weapon,
+};
+
+const AGENTS: [Agent; 5] = [
+    Agent::Jett,
+    Agent::Sova,
+    Agent::Omen,
+    Agent::Killjoy,
+    Agent::Breach,
+];
+
+// Spread out so the players don't all duel evenly
+fn spread_skills(id: u32) -> PlayerSkills {
+    let skill = 0.6 + (id % 5) as f32 * 0.05;
+    common::skills(skill, skill - 0.2, skill, skill)
+}
+
+#[test]
+fn test_same_seed_produces_identical_event_log() {
+    let first = common::run_seeded_match(42, Map::Ascent, &AGENTS, spread_skills);
+    let second = common::run_seeded_match(42, Map::Ascent, &AGENTS, spread_skills);
+
+    assert_eq!(first.state.seed, 42);
+    assert_eq!(
+        serde_json::to_string(&first.events).unwrap(),
+        serde_json::to_string(&second.events).unwrap()
+    );
+    assert_eq!(first.state.attacker_score, second.state.attacker_score);
+    assert_eq!(first.state.defender_score, second.state.defender_score);
+}
+
+#[test]
+fn test_different_seeds_diverge() {
+    let first = common::run_seeded_match(1, Map::Ascent, &AGENTS, spread_skills);
+    let second = common::run_seeded_match(2, Map::Ascent, &AGENTS, spread_skills);
+
+    assert_ne!(
+        serde_json::to_string(&first.events).unwrap(),
+        serde_json::to_string(&second.events).unwrap()
+    );
+}
+
+#[test]
+fn test_unseeded_simulation_exposes_its_seed() {
+    let sim = ValorantSimulation::new();
+    let replay = ValorantSimulation::with_seed(sim.get_current_state().seed);
+    assert_eq!(sim.state.seed, replay.state.seed);
+}
+
+#[test]
+fn test_every_weapon_has_stats() {
+    let stats = BalanceConfig::default().weapons;
+    assert_eq!(stats.len(), Weapon::all().len());
+
+    for weapon in Weapon::all() {
+        let weapon_stats = stats
+            .get(&weapon)
+            .unwrap_or_else(|| panic!("missing stats for {:?}", weapon));
+        assert!(weapon_stats.fire_rate > 0.0, "{:?}", weapon);
+        assert!(weapon_stats.magazine_size > 0, "{:?}", weapon);
+        assert!(
+            weapon_stats.damage_head.0 >= weapon_stats.damage_body.0,
+            "{:?}",
+            weapon
+        );
+
+        let ranges: Vec<f32> = weapon_stats.range_falloff.iter().map(|b| b.0).collect();
+        assert!(ranges.windows(2).all(|w| w[0] < w[1]), "{:?}", weapon);
+        let multipliers: Vec<f32> = weapon_stats.range_falloff.iter().map(|b| b.1).collect();
+        assert!(multipliers.windows(2).all(|w| w[0] >= w[1]), "{:?}", weapon);
+    }
+}
+
+#[test]
+fn test_falloff_bands_reduce_damage_with_range() {
+    let stats = BalanceConfig::default().weapons;
+
+    let phantom = &stats[&Weapon::Phantom];
+    assert_eq!(phantom.falloff_multiplier(10.0), 1.0);
+    assert_eq!(phantom.falloff_multiplier(25.0), 0.85);
+    assert_eq!(phantom.falloff_multiplier(60.0), 0.7);
+
+    let vandal = &stats[&Weapon::Vandal];
+    assert_eq!(vandal.falloff_multiplier(60.0), 1.0);
+
+    let judge = &stats[&Weapon::Judge];
+    assert!(judge.falloff_multiplier(20.0) < judge.falloff_multiplier(5.0));
+}
+
+#[test]
+fn test_assists_go_to_teammates_of_the_killer() {
+    let sim = common::run_seeded_match(42, Map::Ascent, &AGENTS, spread_skills);
+
+    let mut total_assists = 0;
+    for event in &sim.events {
+        if let GameEvent::Kill {
+            killer_id,
+            assister_ids,
+            ..
+        } = event
+        {
+            let killer_team = &sim.players[killer_id].team;
+            for assister_id in assister_ids {
+                assert_ne!(assister_id, killer_id);
+                assert_eq!(&sim.players[assister_id].team, killer_team);
+            }
+            total_assists += assister_ids.len() as u32;
+        }
+    }
+
+    let stats = sim.get_player_stats();
+    assert!(total_assists > 0);
+    assert_eq!(stats.iter().map(|s| s.assists).sum::<u32>(), total_assists);
+}
+
+#[test]
+fn test_damage_events_feed_damage_dealt() {
+    let sim = common::run_seeded_match(42, Map::Ascent, &AGENTS, spread_skills);
+
+    for stats in sim.get_player_stats() {
+        let dealt: u32 = sim
+            .events
+            .iter()
+            .filter_map(|e| match e {
+                GameEvent::Damage {
+                    attacker_id,
+                    amount,
+                    ..
+                } if *attacker_id == stats.player_id => Some(*amount),
+                _ => None,
+            })
+            .sum();
+        assert_eq!(stats.damage_dealt, dealt);
+    }
+    assert!(
+        sim.events
+            .iter()
+            .any(|e| matches!(e, GameEvent::Damage { .. }))
+    );
+}
+
+#[test]
+fn test_duels_trade_several_shots_before_a_kill() {
+    let sim = common::run_seeded_match(42, Map::Ascent, &AGENTS, spread_skills);
+
+    // Count hits landed on each victim in the duel that killed them
+    let mut multi_hit_kills = 0;
+    for (index, event) in sim.events.iter().enumerate() {
+        if let GameEvent::Kill {
+            timestamp,
+            victim_id,
+            ..
+        } = event
+        {
+            let hits_on_victim = sim.events[..index]
+                .iter()
+                .rev()
+                .take_while(|e| e.timestamp() == *timestamp)
+                .filter(|e| matches!(e, GameEvent::Damage { victim_id: v, .. } if v == victim_id))
+                .count();
+            assert!(hits_on_victim >= 1);
+            if hits_on_victim > 1 {
+                multi_hit_kills += 1;
+            }
+        }
+    }
+    assert!(multi_hit_kills > 0);
+
+    // Return fire: some duels have both players landing hits on each other
+    let exchanges = sim.events.windows(2).any(|pair| match pair {
+        [
+            GameEvent::Damage {
+                attacker_id: a1,
+                victim_id: v1,
+                ..
+            },
+            GameEvent::Damage {
+                attacker_id: a2,
+                victim_id: v2,
+                ..
+            },
+        ] => a1 == v2 && v1 == a2,
+        _ => false,
+    });
+    assert!(exchanges);
+}
+
+/// Plays seeded matches with `settings` until one matching `keep` comes up.
+fn find_match(
+    settings: OvertimeSettings,
+    keep: impl Fn(&ValorantSimulation) -> bool,
+) -> ValorantSimulation {
+    for seed in 0..300 {
+        let mut sim = ValorantSimulation::with_seed(seed);
+        sim.set_overtime_settings(settings.clone()).unwrap();
+        common::add_roster(&mut sim, &AGENTS, spread_skills);
+        sim.run_simulation_to_completion().unwrap();
+        if keep(&sim) {
+            return sim;
+        }
+    }
+    panic!("no seed produced the wanted match");
+}
+
+#[test]
+fn test_overtime_resets_credits_and_swaps_sides_every_round() {
+    let settings = OvertimeSettings::default();
+    let round_credits = settings.round_credits;
+    let sim = find_match(settings, |sim| sim.state.overtime_active);
+
+    let last_round = sim.state.current_round;
+    assert!(last_round > 24);
+    for round in 25..=last_round {
+        assert!(sim.events.iter().any(|e| matches!(
+            e,
+            GameEvent::SideSwap { round_number, .. } if *round_number == round
+        )));
+    }
+    for event in &sim.events {
+        if let GameEvent::TeamBuy {
+            round_number,
+            team_credits,
+            ..
+        } = event
+            && *round_number > 24
+        {
+            assert_eq!(*team_credits, round_credits * 5);
+        }
+    }
+
+    // Tournament overtime only ends on a two round lead
+    assert_eq!(
+        sim.state.attacker_score.abs_diff(sim.state.defender_score),
+        2
+    );
+    let rounds_played = sim
+        .events
+        .iter()
+        .filter(|e| matches!(e, GameEvent::RoundEnd { .. }))
+        .count();
+    assert_eq!(
+        (sim.state.attacker_score + sim.state.defender_score) as usize,
+        rounds_played
+    );
+}
+
+#[test]
+fn test_sudden_death_decides_after_the_first_overtime_pair() {
+    let settings = OvertimeSettings {
+        sudden_death: true,
+        ..OvertimeSettings::default()
+    };
+    let sim = find_match(settings, |sim| sim.state.current_round == 27);
+
+    assert_eq!(
+        sim.state.attacker_score.abs_diff(sim.state.defender_score),
+        1
+    );
+    assert!(matches!(
+        sim.state.phase,
+        SimulationPhase::MatchEnd {
+            winner: Some(_),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_ranked_overtime_can_end_in_a_draw_vote() {
+    let settings = OvertimeSettings {
+        tiebreak: TiebreakMode::Ranked,
+        ..OvertimeSettings::default()
+    };
+    let sim = find_match(settings, |sim| {
+        matches!(
+            sim.state.phase,
+            SimulationPhase::MatchEnd { winner: None, .. }
+        )
+    });
+
+    assert_eq!(sim.state.attacker_score, sim.state.defender_score);
+    let votes: Vec<_> = sim
+        .events
+        .iter()
+        .filter_map(|e| match e {
+            GameEvent::DrawVote {
+                round_number,
+                passed,
+                ..
+            } => Some((*round_number, *passed)),
+            _ => None,
+        })
+        .collect();
+    // Votes follow each level overtime pair, and only the last one passed
+    assert!(votes.iter().all(|(round, _)| *round > 24 && round % 2 == 0));
+    assert_eq!(votes.iter().filter(|(_, passed)| *passed).count(), 1);
+    assert!(votes.last().unwrap().1);
+    assert!(matches!(
+        sim.events.last(),
+        Some(GameEvent::MatchEnd {
+            winning_team: None,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_overtime_settings_are_fixed_once_started() {
+    let mut sim = ValorantSimulation::with_seed(1);
+    sim.start_simulation();
+    assert!(
+        sim.set_overtime_settings(OvertimeSettings::default())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_armor_absorbs_damage_until_broken() {
+    let mut player = Player::new(
+        1,
+        "Player1".to_string(),
+        Agent::Jett,
+        Team::Attackers,
+        PlayerSkills {
+            aim: 0.5,
+            hs: 0.5,
+            movement: 0.5,
+            util: 0.5,
+        },
+    );
+    player.current_armor = 25;
+
+    // Armor takes the difference while it lasts
+    assert_eq!(player.absorb_hit(40, 20), 40);
+    assert_eq!(player.current_armor, 5);
+    assert_eq!(player.current_health, 80);
+
+    // Then only what is left of it
+    assert_eq!(player.absorb_hit(40, 20), 40);
+    assert_eq!(player.current_armor, 0);
+    assert_eq!(player.current_health, 45);
+
+    // Broken armor stops nothing
+    assert_eq!(player.absorb_hit(40, 20), 40);
+    assert_eq!(player.current_health, 5);
+}
+
+/// Share of rounds the first roster wins over `seeds` matches against the second.
+fn round_win_rate(first: PlayerSkills, second: PlayerSkills, seeds: u64) -> f32 {
+    let (mut won, mut played) = (0u32, 0u32);
+    for seed in 0..seeds {
+        let mut sim = ValorantSimulation::with_seed(seed);
+        for id in 1..=10u32 {
+            let (team, skills) = if id <= 5 {
+                (Team::Attackers, first.clone())
+            } else {
+                (Team::Defenders, second.clone())
+            };
+            sim.add_player(Player::new(
+                id,
+                format!("Player{}", id),
+                Agent::Jett,
+                team,
+                skills,
+            ));
+        }
+        sim.run_simulation_to_completion().unwrap();
+
+        // Scores follow the teams across side swaps
+        let state = &sim.state;
+        won += if sim.players[&1].team == Team::Attackers {
+            state.attacker_score
+        } else {
+            state.defender_score
+        } as u32;
+        played += (state.attacker_score + state.defender_score) as u32;
+    }
+    won as f32 / played as f32
+}
+
+fn skills(aim: f32, hs: f32, movement: f32) -> PlayerSkills {
+    PlayerSkills {
+        aim,
+        hs,
+        movement,
+        util: 0.7,
+    }
+}
+
+#[test]
+fn test_calibration_even_teams_split_rounds() {
+    let rate = round_win_rate(skills(0.7, 0.5, 0.7), skills(0.7, 0.5, 0.7), 20);
+    assert!((0.4..=0.6).contains(&rate), "even teams won {}", rate);
+}
+
+#[test]
+fn test_calibration_skill_gaps_scale_win_rates() {
+    let small_gap = round_win_rate(skills(0.75, 0.55, 0.7), skills(0.65, 0.45, 0.7), 20);
+    let large_gap = round_win_rate(skills(0.9, 0.7, 0.7), skills(0.5, 0.3, 0.7), 20);
+
+    assert!(small_gap > 0.6, "small gap won {}", small_gap);
+    assert!(large_gap > small_gap);
+    // Better players win most rounds, not all of them
+    assert!(large_gap < 1.0);
+}
+
+#[test]
+fn test_calibration_movement_gives_an_edge() {
+    let rate = round_win_rate(skills(0.7, 0.5, 0.95), skills(0.7, 0.5, 0.3), 20);
+    assert!(rate > 0.55, "better movement won {}", rate);
+}
+
+#[test]
+fn test_filtered_events_cover_every_event_type() {
+    let sim = common::run_seeded_match(42, Map::Ascent, &AGENTS, spread_skills);
+    for event_type in ["Damage", "TeamBuy", "AbilityUsed", "SideSwap", "Kill"] {
+        let filter = EventFilter {
+            event_types: Some(vec![event_type.to_string()]),
+            player_ids: None,
+            round_numbers: None,
+            start_timestamp: None,
+            end_timestamp: None,
+        };
+        let filtered = sim.get_filtered_events(&filter);
+        let expected = sim
+            .events
+            .iter()
+            .filter(|e| e.event_type() == event_type)
+            .count();
+        assert!(expected > 0, "no {} events", event_type);
+        assert_eq!(filtered.len(), expected);
+    }
+
+    let middle = sim.events[sim.events.len() / 2].timestamp();
+    let filter = EventFilter {
+        event_types: None,
+        player_ids: None,
+        round_numbers: None,
+        start_timestamp: Some(middle),
+        end_timestamp: Some(middle),
+    };
+    let filtered = sim.get_filtered_events(&filter);
+    assert!(!filtered.is_empty());
+    assert!(filtered.iter().all(|e| e.timestamp() == middle));
+}
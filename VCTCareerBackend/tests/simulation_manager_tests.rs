@@ -1,11 +1,17 @@
+use std::time::Duration;
 use uuid::Uuid;
+use vctcareer_backend::maps::Map;
 use vctcareer_backend::models::{EventFilterRequest, SimulationPlayer};
-use vctcareer_backend::sim::GameEvent;
+use vctcareer_backend::sim::{FAST_FORWARD_TICKS_PER_STEP, GameEvent};
 use vctcareer_backend::simulation_manager::{
-    advance_simulation_legacy, control_simulation_legacy, create_simulation,
-    create_simulation_manager, get_events_by_player_legacy, get_events_by_round_legacy,
-    get_events_by_type_legacy, get_events_since_legacy, get_simulation_events_legacy,
-    get_simulation_state, get_simulation_stats_legacy,
+    SimulationOptions, advance_simulation_legacy, claim_playback_driver, control_simulation_legacy,
+    create_checkpoint_legacy, create_simulation, create_simulation_manager,
+    create_simulation_with_options, create_simulation_with_seed, drive_playback,
+    get_events_by_player_legacy, get_events_by_round_legacy, get_events_by_type_legacy,
+    get_events_since_legacy, get_live_stats, get_scoreboard, get_simulation_events_legacy,
+    get_simulation_state, get_simulation_stats_legacy, get_win_probability_timeline,
+    list_checkpoints_legacy, replay_from_legacy, restore_checkpoint_legacy, rewind_to_round_legacy,
+    subscribe_to_events,
 };
 
 fn create_mock_players() -> Vec<SimulationPlayer> {
@@ -543,3 +549,463 @@ fn test_simulation_integration_flow() {
     let events = get_simulation_events_legacy(&manager, simulation_id, filter).unwrap();
     assert!(!events.is_empty());
 }
+
+#[test]
+fn test_checkpoint_create_list_and_restore() {
+    let manager = create_simulation_manager();
+    let players = create_mock_players();
+    let simulation_id = create_simulation(&manager, players).unwrap();
+
+    advance_simulation_legacy(
+        &manager,
+        simulation_id.clone(),
+        None,
+        Some("round".to_string()),
+    )
+    .unwrap();
+    let state_at_checkpoint = get_simulation_state(&manager, &simulation_id).unwrap();
+    let events_at_checkpoint = get_simulation_events_legacy(
+        &manager,
+        simulation_id.clone(),
+        EventFilterRequest {
+            event_types: None,
+            player_ids: None,
+            round_numbers: None,
+            start_timestamp: None,
+            end_timestamp: None,
+        },
+    )
+    .unwrap();
+
+    let checkpoint_id = create_checkpoint_legacy(
+        &manager,
+        simulation_id.clone(),
+        Some("After round 1".to_string()),
+    )
+    .unwrap();
+
+    let checkpoints = list_checkpoints_legacy(&manager, simulation_id.clone()).unwrap();
+    assert_eq!(checkpoints.len(), 1);
+    assert_eq!(checkpoints[0].checkpoint_id, checkpoint_id);
+    assert_eq!(checkpoints[0].description, "After round 1");
+    assert_eq!(
+        checkpoints[0].round_number,
+        state_at_checkpoint.current_round
+    );
+    assert_eq!(checkpoints[0].event_count, events_at_checkpoint.len());
+
+    advance_simulation_legacy(
+        &manager,
+        simulation_id.clone(),
+        None,
+        Some("round".to_string()),
+    )
+    .unwrap();
+    let advanced_state = get_simulation_state(&manager, &simulation_id).unwrap();
+    assert!(advanced_state.tick_count > state_at_checkpoint.tick_count);
+
+    restore_checkpoint_legacy(&manager, simulation_id.clone(), checkpoint_id).unwrap();
+    let restored_state = get_simulation_state(&manager, &simulation_id).unwrap();
+    assert_eq!(restored_state.tick_count, state_at_checkpoint.tick_count);
+    assert_eq!(
+        restored_state.current_round,
+        state_at_checkpoint.current_round
+    );
+}
+
+#[test]
+fn test_restore_unknown_checkpoint() {
+    let manager = create_simulation_manager();
+    let players = create_mock_players();
+    let simulation_id = create_simulation(&manager, players).unwrap();
+
+    let result =
+        restore_checkpoint_legacy(&manager, simulation_id.clone(), Uuid::new_v4().to_string());
+    assert!(result.is_err());
+    assert!(result.unwrap_err().contains("Checkpoint not found"));
+
+    let result = restore_checkpoint_legacy(&manager, simulation_id, "not-a-uuid".to_string());
+    assert!(result.is_err());
+    assert!(result.unwrap_err().contains("Invalid checkpoint ID format"));
+}
+
+fn all_events(
+    manager: &vctcareer_backend::simulation_manager::SimulationManager,
+    simulation_id: &str,
+) -> Vec<GameEvent> {
+    get_simulation_events_legacy(
+        manager,
+        simulation_id.to_string(),
+        EventFilterRequest {
+            event_types: None,
+            player_ids: None,
+            round_numbers: None,
+            start_timestamp: None,
+            end_timestamp: None,
+        },
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_rewind_to_round_replays_identically() {
+    let manager = create_simulation_manager();
+    let players = create_mock_players();
+    let simulation_id = create_simulation_with_seed(&manager, players, Some(7)).unwrap();
+
+    for _ in 0..3 {
+        advance_simulation_legacy(
+            &manager,
+            simulation_id.clone(),
+            None,
+            Some("round".to_string()),
+        )
+        .unwrap();
+    }
+    let original_events = all_events(&manager, &simulation_id);
+
+    rewind_to_round_legacy(&manager, simulation_id.clone(), 2).unwrap();
+    let rewound_state = get_simulation_state(&manager, &simulation_id).unwrap();
+    assert_eq!(rewound_state.current_round, 2);
+    assert!(all_events(&manager, &simulation_id).len() < original_events.len());
+
+    for _ in 0..2 {
+        advance_simulation_legacy(
+            &manager,
+            simulation_id.clone(),
+            None,
+            Some("round".to_string()),
+        )
+        .unwrap();
+    }
+    let replayed_events = all_events(&manager, &simulation_id);
+    assert_eq!(
+        format!("{:?}", replayed_events),
+        format!("{:?}", original_events)
+    );
+}
+
+#[test]
+fn test_rewind_to_future_round_fails() {
+    let manager = create_simulation_manager();
+    let players = create_mock_players();
+    let simulation_id = create_simulation(&manager, players).unwrap();
+
+    advance_simulation_legacy(
+        &manager,
+        simulation_id.clone(),
+        None,
+        Some("round".to_string()),
+    )
+    .unwrap();
+
+    assert!(rewind_to_round_legacy(&manager, simulation_id.clone(), 0).is_err());
+    assert!(rewind_to_round_legacy(&manager, simulation_id, 5).is_err());
+}
+
+#[test]
+fn test_replay_from_timestamp() {
+    let manager = create_simulation_manager();
+    let players = create_mock_players();
+    let simulation_id = create_simulation_with_seed(&manager, players, Some(11)).unwrap();
+
+    for _ in 0..2 {
+        advance_simulation_legacy(
+            &manager,
+            simulation_id.clone(),
+            None,
+            Some("round".to_string()),
+        )
+        .unwrap();
+    }
+    let original_events = all_events(&manager, &simulation_id);
+    let end_timestamp = get_simulation_state(&manager, &simulation_id)
+        .unwrap()
+        .current_timestamp;
+    let target = end_timestamp / 2;
+
+    replay_from_legacy(&manager, simulation_id.clone(), target).unwrap();
+    let state = get_simulation_state(&manager, &simulation_id).unwrap();
+    assert!(state.current_timestamp >= target);
+    assert!(state.current_timestamp < end_timestamp);
+
+    let replayed_events = all_events(&manager, &simulation_id);
+    assert_eq!(
+        format!("{:?}", replayed_events),
+        format!("{:?}", &original_events[..replayed_events.len()])
+    );
+
+    assert!(replay_from_legacy(&manager, simulation_id, end_timestamp * 2).is_err());
+}
+
+#[test]
+fn test_subscribe_to_events_receives_live_events() {
+    let manager = create_simulation_manager();
+    let players = create_mock_players();
+    let simulation_id = create_simulation(&manager, players).unwrap();
+
+    let mut all_receiver = subscribe_to_events(
+        &manager,
+        &simulation_id,
+        "all".to_string(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    let mut kill_receiver = subscribe_to_events(
+        &manager,
+        &simulation_id,
+        "kills".to_string(),
+        Some(vec!["Kill".to_string()]),
+        Some(vec![1]),
+        None,
+    )
+    .unwrap();
+
+    advance_simulation_legacy(
+        &manager,
+        simulation_id.clone(),
+        None,
+        Some("round".to_string()),
+    )
+    .unwrap();
+
+    let events = all_events(&manager, &simulation_id);
+    let mut streamed = Vec::new();
+    while let Ok(streamed_event) = all_receiver.try_recv() {
+        streamed.push(streamed_event);
+    }
+    assert_eq!(streamed.len(), events.len());
+    for (i, streamed_event) in streamed.iter().enumerate() {
+        assert_eq!(streamed_event.index, i);
+    }
+
+    while let Ok(streamed_event) = kill_receiver.try_recv() {
+        match streamed_event.event {
+            // Assisting a kill also counts as being involved in it
+            GameEvent::Kill {
+                killer_id,
+                victim_id,
+                assister_ids,
+                ..
+            } => assert!(killer_id == 1 || victim_id == 1 || assister_ids.contains(&1)),
+            other => panic!("Unexpected event streamed: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_subscribe_to_events_resumes_from_index() {
+    let manager = create_simulation_manager();
+    let players = create_mock_players();
+    let simulation_id = create_simulation(&manager, players).unwrap();
+
+    advance_simulation_legacy(
+        &manager,
+        simulation_id.clone(),
+        Some(10),
+        Some("tick".to_string()),
+    )
+    .unwrap();
+    let events = all_events(&manager, &simulation_id);
+    assert!(events.len() >= 2);
+
+    let mut receiver = subscribe_to_events(
+        &manager,
+        &simulation_id,
+        "resume".to_string(),
+        None,
+        None,
+        Some(1),
+    )
+    .unwrap();
+
+    let first = receiver.try_recv().unwrap();
+    assert_eq!(first.index, 1);
+    assert!(first.to_sse_frame().starts_with("id: 1\n"));
+
+    drop(receiver);
+    advance_simulation_legacy(
+        &manager,
+        simulation_id.clone(),
+        Some(70),
+        Some("tick".to_string()),
+    )
+    .unwrap();
+    let simulations = manager.lock().unwrap();
+    let sim = simulations
+        .get(&Uuid::parse_str(&simulation_id).unwrap())
+        .unwrap();
+    assert!(sim.event_stream.subscribers.is_empty());
+}
+
+#[test]
+fn test_playback_driver_requires_play() {
+    let manager = create_simulation_manager();
+    let players = create_mock_players();
+    let simulation_id = create_simulation(&manager, players).unwrap();
+
+    // Playing is the default mode, but only an explicit play hands ticking to the server
+    assert_eq!(
+        claim_playback_driver(&manager, &simulation_id).unwrap(),
+        None
+    );
+
+    control_simulation_legacy(&manager, simulation_id.clone(), "play".to_string(), None).unwrap();
+    let generation = claim_playback_driver(&manager, &simulation_id).unwrap();
+    assert_eq!(generation, Some(1));
+
+    control_simulation_legacy(&manager, simulation_id.clone(), "pause".to_string(), None).unwrap();
+    assert_eq!(
+        claim_playback_driver(&manager, &simulation_id).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_playback_driver_ticks_until_paused() {
+    let manager = create_simulation_manager();
+    let players = create_mock_players();
+    let simulation_id = create_simulation(&manager, players).unwrap();
+    let uuid_id = Uuid::parse_str(&simulation_id).unwrap();
+
+    control_simulation_legacy(&manager, simulation_id.clone(), "play".to_string(), None).unwrap();
+    control_simulation_legacy(
+        &manager,
+        simulation_id.clone(),
+        "set_speed".to_string(),
+        Some(5.0),
+    )
+    .unwrap();
+    let generation = claim_playback_driver(&manager, &simulation_id)
+        .unwrap()
+        .unwrap();
+
+    actix_web::rt::System::new().block_on(async {
+        actix_web::rt::spawn(drive_playback(manager.clone(), uuid_id, generation));
+        actix_web::rt::time::sleep(Duration::from_millis(300)).await;
+
+        let state = get_simulation_state(&manager, &simulation_id).unwrap();
+        assert!(state.tick_count >= FAST_FORWARD_TICKS_PER_STEP as u64);
+
+        control_simulation_legacy(&manager, simulation_id.clone(), "pause".to_string(), None)
+            .unwrap();
+        actix_web::rt::time::sleep(Duration::from_millis(200)).await;
+        let paused_ticks = get_simulation_state(&manager, &simulation_id)
+            .unwrap()
+            .tick_count;
+        actix_web::rt::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(
+            get_simulation_state(&manager, &simulation_id)
+                .unwrap()
+                .tick_count,
+            paused_ticks
+        );
+    });
+}
+
+#[test]
+fn test_scoreboard_reports_assists() {
+    let manager = create_simulation_manager();
+    let players = create_mock_players();
+    let simulation_id = create_simulation_with_seed(&manager, players, Some(3)).unwrap();
+    advance_simulation_legacy(
+        &manager,
+        simulation_id.clone(),
+        None,
+        Some("match".to_string()),
+    )
+    .unwrap();
+
+    let scoreboard = get_scoreboard(&manager, &simulation_id).unwrap();
+    let ranking_assists: u32 = scoreboard.player_rankings.iter().map(|r| r.assists).sum();
+
+    let events = all_events(&manager, &simulation_id);
+    let event_assists: u32 = events
+        .iter()
+        .map(|e| match e {
+            GameEvent::Kill { assister_ids, .. } => assister_ids.len() as u32,
+            _ => 0,
+        })
+        .sum();
+    assert_eq!(ranking_assists, event_assists);
+}
+
+#[test]
+fn test_create_simulation_with_options_sets_map() {
+    let manager = create_simulation_manager();
+    let simulation_id = create_simulation_with_options(
+        &manager,
+        create_mock_players(),
+        SimulationOptions {
+            seed: Some(5),
+            map: Some(Map::Lotus),
+            ..SimulationOptions::default()
+        },
+    )
+    .unwrap();
+
+    let state = get_simulation_state(&manager, &simulation_id).unwrap();
+    assert_eq!(state.map, Map::Lotus);
+    assert_eq!(state.seed, 5);
+}
+
+#[test]
+fn test_create_simulation_with_unknown_patch_fails() {
+    let manager = create_simulation_manager();
+    let result = create_simulation_with_options(
+        &manager,
+        create_mock_players(),
+        SimulationOptions {
+            patch_version: Some("no-such-patch".to_string()),
+            ..SimulationOptions::default()
+        },
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_live_stats_report_win_probability() {
+    let manager = create_simulation_manager();
+    let players = create_mock_players();
+    let simulation_id = create_simulation_with_seed(&manager, players, Some(8)).unwrap();
+    for _ in 0..2 {
+        advance_simulation_legacy(
+            &manager,
+            simulation_id.clone(),
+            None,
+            Some("round".to_string()),
+        )
+        .unwrap();
+    }
+
+    let stats = get_live_stats(&manager, &simulation_id).unwrap();
+    let estimate = stats.win_probability;
+    assert!((0.0..=1.0).contains(&estimate.attacker_round));
+    assert!((0.0..=1.0).contains(&estimate.attacker_match));
+
+    let timeline = get_win_probability_timeline(&manager, &simulation_id).unwrap();
+    assert_eq!(timeline.len(), 2);
+    assert!(get_win_probability_timeline(&manager, "not-a-uuid").is_err());
+}
+
+#[test]
+fn test_rewind_drops_later_win_probability_samples() {
+    let manager = create_simulation_manager();
+    let players = create_mock_players();
+    let simulation_id = create_simulation_with_seed(&manager, players, Some(7)).unwrap();
+    for _ in 0..3 {
+        advance_simulation_legacy(
+            &manager,
+            simulation_id.clone(),
+            None,
+            Some("round".to_string()),
+        )
+        .unwrap();
+    }
+
+    rewind_to_round_legacy(&manager, simulation_id.clone(), 2).unwrap();
+    let timeline = get_win_probability_timeline(&manager, &simulation_id).unwrap();
+    assert!(timeline.iter().all(|round| round.round_number < 2));
+}
@@ -0,0 +1,215 @@
+mod common;
+
+use vctcareer_backend::maps::Map;
+use vctcareer_backend::sim::{Agent, ROUND_TIME_MS, SPIKE_TIME_MS};
+use vctcareer_backend::win_probability::{
+    RoundSituation, estimate, match_win_probability, match_win_probability_from,
+    round_win_probability, timeline,
+};
+
+fn even_round() -> RoundSituation {
+    RoundSituation {
+        attackers_alive: 5,
+        defenders_alive: 5,
+        attacker_equipment: 20_000,
+        defender_equipment: 20_000,
+        spike_planted: false,
+        round_timer_ms: ROUND_TIME_MS,
+        spike_timer_ms: SPIKE_TIME_MS,
+    }
+}
+
+#[test]
+fn test_even_round_is_close_to_a_coin_flip() {
+    let probability = round_win_probability(&even_round());
+    assert!((0.45..=0.55).contains(&probability));
+}
+
+#[test]
+fn test_decided_rounds_are_certain() {
+    let wiped_defenders = RoundSituation {
+        defenders_alive: 0,
+        ..even_round()
+    };
+    assert_eq!(round_win_probability(&wiped_defenders), 1.0);
+
+    let wiped_attackers = RoundSituation {
+        attackers_alive: 0,
+        ..even_round()
+    };
+    assert_eq!(round_win_probability(&wiped_attackers), 0.0);
+
+    let no_time_to_defuse = RoundSituation {
+        spike_planted: true,
+        spike_timer_ms: 3_000,
+        ..even_round()
+    };
+    assert_eq!(round_win_probability(&no_time_to_defuse), 1.0);
+}
+
+#[test]
+fn test_man_and_equipment_advantages_favor_attackers() {
+    let even = round_win_probability(&even_round());
+    let man_up = round_win_probability(&RoundSituation {
+        defenders_alive: 4,
+        defender_equipment: 16_000,
+        ..even_round()
+    });
+    let eco_defenders = round_win_probability(&RoundSituation {
+        defender_equipment: 4_000,
+        ..even_round()
+    });
+
+    assert!(man_up > even);
+    assert!(eco_defenders > man_up);
+}
+
+#[test]
+fn test_clock_and_spike_move_the_odds() {
+    let even = round_win_probability(&even_round());
+    let clock_running_out = round_win_probability(&RoundSituation {
+        round_timer_ms: 10_000,
+        ..even_round()
+    });
+    let fresh_plant = round_win_probability(&RoundSituation {
+        spike_planted: true,
+        ..even_round()
+    });
+    let late_plant = round_win_probability(&RoundSituation {
+        spike_planted: true,
+        spike_timer_ms: 12_000,
+        ..even_round()
+    });
+
+    assert!(clock_running_out < even);
+    assert!(fresh_plant > even);
+    assert!(late_plant > fresh_plant);
+}
+
+#[test]
+fn test_match_probability_from_score() {
+    assert_eq!(match_win_probability_from(0, 0), 0.5);
+    assert_eq!(match_win_probability_from(13, 11), 1.0);
+    assert_eq!(match_win_probability_from(9, 13), 0.0);
+    assert_eq!(match_win_probability_from(12, 12), 0.5);
+    assert_eq!(match_win_probability_from(13, 12), 0.75);
+    assert!(match_win_probability_from(12, 5) > 0.99);
+    assert!(match_win_probability_from(7, 5) > match_win_probability_from(6, 5));
+
+    // Winning the current round is what the round estimate weighs
+    assert_eq!(
+        match_win_probability(12, 5, 1.0),
+        match_win_probability_from(13, 5)
+    );
+    let halfway = match_win_probability(6, 6, 0.5);
+    assert!((halfway - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_timeline_covers_every_round() {
+    let sim = common::run_seeded_match(21, Map::Ascent, &[Agent::Sova], |_| {
+        common::skills(0.7, 0.5, 0.7, 0.7)
+    });
+    let rounds = timeline(&sim);
+    let rounds_played = (sim.state.attacker_score + sim.state.defender_score) as usize;
+    assert_eq!(rounds.len(), rounds_played);
+
+    for (index, round) in rounds.iter().enumerate() {
+        assert_eq!(round.round_number as usize, index + 1);
+        assert_eq!(round.samples.first().unwrap().trigger, "RoundStart");
+
+        // The last sample settles the round
+        let last = round.samples.last().unwrap();
+        assert_eq!(last.trigger, "RoundEnd");
+        assert!(last.attacker_round == 0.0 || last.attacker_round == 1.0);
+
+        for sample in &round.samples {
+            assert!((0.0..=1.0).contains(&sample.attacker_round));
+            assert!((0.0..=1.0).contains(&sample.attacker_match));
+        }
+    }
+}
+
+#[test]
+fn test_finished_match_is_certain() {
+    let sim = common::run_seeded_match(4, Map::Ascent, &[Agent::Sova], |_| {
+        common::skills(0.7, 0.5, 0.7, 0.7)
+    });
+    let final_estimate = estimate(&sim);
+    assert!(final_estimate.attacker_match == 0.0 || final_estimate.attacker_match == 1.0);
+}
+
+/// Every mid-round estimate from `seeds` matches, paired with whether the
+/// attackers went on to win that round.
+fn round_predictions(seeds: u64) -> Vec<(f32, bool)> {
+    let mut predictions = Vec::new();
+    for seed in 0..seeds {
+        let sim = common::run_seeded_match(seed, Map::Ascent, &[Agent::Sova], |_| {
+            common::skills(0.7, 0.5, 0.7, 0.7)
+        });
+        for round in timeline(&sim) {
+            let (settled, live) = round.samples.split_last().unwrap();
+            let attackers_won = settled.attacker_round == 1.0;
+            predictions.extend(live.iter().map(|s| (s.attacker_round, attackers_won)));
+        }
+    }
+    predictions
+}
+
+fn outcome(attackers_won: bool) -> f32 {
+    if attackers_won { 1.0 } else { 0.0 }
+}
+
+#[test]
+fn test_calibration_estimates_match_outcomes() {
+    let predictions = round_predictions(20);
+
+    // Within each band of estimates, attackers win about as often as predicted
+    for band in 0..5 {
+        let (low, high) = (band as f32 * 0.2, band as f32 * 0.2 + 0.2);
+        let in_band: Vec<_> = predictions
+            .iter()
+            .filter(|(p, _)| *p >= low && (*p < high || band == 4))
+            .collect();
+        assert!(
+            in_band.len() > 100,
+            "only {} estimates in {}-{}",
+            in_band.len(),
+            low,
+            high
+        );
+        let predicted = in_band.iter().map(|(p, _)| p).sum::<f32>() / in_band.len() as f32;
+        let observed = in_band.iter().filter(|(_, won)| *won).count() as f32 / in_band.len() as f32;
+        assert!(
+            (predicted - observed).abs() < 0.08,
+            "{}-{}: predicted {} but attackers won {}",
+            low,
+            high,
+            predicted,
+            observed
+        );
+    }
+
+    // And the estimates beat always guessing the attackers' overall round win rate
+    let count = predictions.len() as f32;
+    let base_rate = predictions
+        .iter()
+        .map(|(_, won)| outcome(*won))
+        .sum::<f32>()
+        / count;
+    let brier = |guess: &dyn Fn(f32) -> f32| {
+        predictions
+            .iter()
+            .map(|(p, won)| (guess(*p) - outcome(*won)).powi(2))
+            .sum::<f32>()
+            / count
+    };
+    let model = brier(&|p| p);
+    let baseline = brier(&|_| base_rate);
+    assert!(
+        model < baseline * 0.8,
+        "Brier {} against {}",
+        model,
+        baseline
+    );
+}